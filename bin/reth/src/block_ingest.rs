@@ -1,123 +1,70 @@
-use std::collections::BTreeMap;
-use std::io::{BufRead, BufReader};
-use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
-use alloy_consensus::{BlockBody, BlockHeader, Transaction};
-use alloy_primitives::TxKind;
-use alloy_primitives::{Address, PrimitiveSignature, B256, U256};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Address, B256, U256};
 use alloy_rpc_types::engine::{
     ExecutionPayloadEnvelopeV3, ForkchoiceState, PayloadAttributes, PayloadStatusEnum,
 };
 use jsonrpsee::http_client::{transport::HttpBackend, HttpClient};
 use reth::network::PeersHandleProvider;
 use reth_chainspec::{EthChainSpec, EthereumHardforks};
-use reth_hyperliquid_types::{PrecompileData, PrecompilesCache};
+use reth_hyperliquid_types::{
+    seal_block_with_system_txs, BlockSource, BlockSourceBoxed, FallbackBlockSource, IngestError,
+    PrecompileDataStoreBoxed, SpotTokenStoreBoxed, SpotTokens,
+};
+use reth_network_api::NetworkInfo;
 use reth_node_api::{Block, FullNodeComponents, PayloadTypes};
 use reth_node_builder::EngineTypes;
 use reth_node_builder::NodeTypesWithEngine;
 use reth_node_builder::{rpc::RethRpcAddOns, FullNode};
 use reth_payload_builder::{EthBuiltPayload, EthPayloadBuilderAttributes, PayloadId};
-use reth_primitives::{Transaction as TypedTransaction, TransactionSigned};
+use reth_primitives::EthPrimitives;
 use reth_provider::{BlockHashReader, BlockReader, StageCheckpointReader};
 use reth_rpc_api::EngineApiClient;
 use reth_rpc_layer::AuthClientService;
 use reth_stages::StageId;
-use serde::Deserialize;
-use time::{format_description, Duration, OffsetDateTime};
-use tokio::sync::Mutex;
-use tracing::{debug, info};
-
-use crate::serialized::{BlockAndReceipts, EvmBlock};
-use crate::spot_meta::erc20_contract_to_spot_token;
+use tracing::{debug, error, info, warn};
 
-/// Poll interval when tailing an *open* hourly file.
-const TAIL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(25);
-/// Sub‑directory that contains day folders (inside `local_ingest_dir`).
-const HOURLY_SUBDIR: &str = "hourly";
+use crate::consistency_auditor::ConsistencyAuditor;
+use crate::ingest_status::{IngestMode, IngestStatusHandle};
+use crate::local_block_source::LocalBlockSource;
+use crate::receipt_verifier::{ReceiptVerificationArgs, ReceiptVerifier};
+use crate::s3_http_source::S3HttpBlockSource;
 
 pub(crate) struct BlockIngest {
-    pub ingest_dir: PathBuf,
-    pub local_ingest_dir: Option<PathBuf>,
-    pub local_blocks_cache: Arc<Mutex<BTreeMap<u64, BlockAndReceipts>>>, // height → block
-    pub precompiles_cache: PrecompilesCache,
-}
-
-#[derive(Deserialize)]
-struct LocalBlockAndReceipts(String, BlockAndReceipts);
-
-struct ScanResult {
-    next_expected_height: u64,
-    new_blocks: Vec<BlockAndReceipts>,
+    /// Archive of blocks, i.e. `--ingest-dir` or the S3 bucket.
+    pub archive: BlockSourceBoxed,
+    /// Sources of new blocks once the archive has no more blocks, in priority order.
+    pub live_sources: Vec<BlockSourceBoxed>,
+    /// Tail of the local hl-node output, if configured.
+    pub local_source: Option<Arc<LocalBlockSource>>,
+    /// Downloader of blocks over the S3 REST API, if configured.
+    pub s3_http_source: Option<Arc<S3HttpBlockSource>>,
+    /// Whether blocks of the live sources are compared with the archive.
+    pub audit_live_blocks: bool,
+    /// Database store of the read precompile calls of imported blocks.
+    pub precompile_store: PrecompileDataStoreBoxed,
+    /// Database store of the spot token mapping, used to derive the senders of system
+    /// transactions.
+    pub spot_token_store: SpotTokenStoreBoxed,
+    /// Status of block ingestion, shared with the RPC server.
+    pub status: IngestStatusHandle,
+    /// Verification of executed receipts against the ingested ones.
+    pub receipt_verification: ReceiptVerificationArgs,
 }
 
-fn scan_hour_file(path: &Path, last_line: &mut usize, start_height: u64) -> ScanResult {
-    // info!(
-    //     "Scanning hour block file @ {:?} for height [{:?}] | Last Line {:?}",
-    //     path, start_height, last_line
-    // );
-    let file = std::fs::File::open(path).expect("Failed to open hour file path");
-    let reader = BufReader::new(file);
-
-    let mut new_blocks = Vec::<BlockAndReceipts>::new();
-    let mut last_height = start_height;
-    let lines: Vec<String> = reader.lines().collect::<Result<_, _>>().unwrap();
-    let skip = if *last_line == 0 { 0 } else { (last_line.clone()) - 1 };
-
-    for (line_idx, line) in lines.iter().enumerate().skip(skip) {
-        // Safety check ensuring efficiency
-        if line_idx < *last_line {
-            continue;
-        }
-        if line.trim().is_empty() {
-            continue;
-        }
-
-        let (_block_timestamp, parsed_block) = match serde_json::from_str(&line) {
-            Ok(LocalBlockAndReceipts(_block_timestamp, parsed_block)) => {
-                (_block_timestamp, parsed_block)
-            }
-            Err(_) => {
-                // Possible scenarios:
-                let is_last_line = line_idx == lines.len() - 1;
-                if is_last_line {
-                    // 1. It's not written fully yet - in this case, just wait for the next line
-                    break;
-                } else {
-                    // 2. hl-node previously terminated while writing the lines
-                    // In this case, try to skip this line
-                    continue;
-                }
-            }
-        };
-
-        let height = match &parsed_block.block {
-            EvmBlock::Reth115(b) => {
-                let block_number = b.header().number() as u64;
-                // Another check to ensure not returning an older block
-                if block_number < start_height {
-                    continue;
-                }
-                block_number
-            }
-        };
-        // println!("Iterating block height {:?} | Line {}", height, line_idx);
-        if height >= start_height {
-            last_height = last_height.max(height);
-            new_blocks.push(parsed_block);
-            *last_line = line_idx;
-        }
-    }
-
-    ScanResult { next_expected_height: last_height + 1, new_blocks }
-}
+/// Initial delay before retrying a block after a transient error.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+/// Upper bound of the delay between retries of a block.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
 
 async fn submit_payload<Engine: PayloadTypes + EngineTypes>(
     engine_api_client: &HttpClient<AuthClientService<HttpBackend>>,
+    height: u64,
     payload: EthBuiltPayload,
     payload_builder_attributes: EthPayloadBuilderAttributes,
     expected_status: PayloadStatusEnum,
-) -> Result<B256, Box<dyn std::error::Error>> {
+) -> Result<B256, IngestError> {
     let versioned_hashes =
         payload.block().blob_versioned_hashes_iter().copied().collect::<Vec<_>>();
     // submit payload to engine api
@@ -130,138 +77,53 @@ async fn submit_payload<Engine: PayloadTypes + EngineTypes>(
             versioned_hashes,
             payload_builder_attributes.parent_beacon_block_root.unwrap(),
         )
-        .await?
+        .await
+        .map_err(|err| IngestError::Engine { height, reason: err.to_string() })?
     };
 
-    assert_eq!(submission.status.as_str(), expected_status.as_str());
+    if submission.status.as_str() != expected_status.as_str() {
+        let status = format!("{:?}", submission.status);
+        if submission.status.is_invalid() {
+            return Err(IngestError::InvalidPayload { height, status });
+        }
+        return Err(IngestError::Engine { height, reason: format!("unexpected status {status}") });
+    }
 
     Ok(submission.latest_valid_hash.unwrap_or_default())
 }
 
-fn datetime_from_timestamp(ts_sec: u64) -> OffsetDateTime {
-    OffsetDateTime::from_unix_timestamp_nanos((ts_sec as i128) * 1_000 * 1_000_000)
-        .expect("timestamp out of range")
-}
-
-fn date_from_datetime(dt: OffsetDateTime) -> String {
-    dt.format(&format_description::parse("[year][month][day]").unwrap()).unwrap()
-}
-
 impl BlockIngest {
-    pub(crate) async fn collect_block(&self, height: u64) -> Option<BlockAndReceipts> {
-        // info!("Attempting to collect block @ height [{height}]");
-
-        // Not a one liner (using .or) to include logs
-        if let Some(block) = self.try_collect_local_block(height).await {
-            info!("Returning locally synced block for @ Height [{height}]");
-            return Some(block);
-        } else {
-            self.try_collect_s3_block(height)
-        }
-    }
-
-    pub(crate) fn try_collect_s3_block(&self, height: u64) -> Option<BlockAndReceipts> {
-        let f = ((height - 1) / 1_000_000) * 1_000_000;
-        let s = ((height - 1) / 1_000) * 1_000;
-        let path = format!("{}/{f}/{s}/{height}.rmp.lz4", self.ingest_dir.to_string_lossy());
-        let file = std::fs::read(path).ok()?;
-        let mut decoder = lz4_flex::frame::FrameDecoder::new(&file[..]);
-        let blocks: Vec<BlockAndReceipts> = rmp_serde::from_read(&mut decoder).unwrap();
-        info!("Returning s3 synced block for @ Height [{height}]");
-        Some(blocks[0].clone())
-    }
-
-    async fn try_collect_local_block(&self, height: u64) -> Option<BlockAndReceipts> {
-        let mut u_cache = self.local_blocks_cache.lock().await;
-        u_cache.remove(&height)
-    }
-
-    async fn start_local_ingest_loop(&self, current_head: u64, current_ts: u64) {
-        let Some(root) = &self.local_ingest_dir else { return }; // nothing to do
-        let root = root.to_owned();
-        let cache = self.local_blocks_cache.clone();
-        let precompiles_cache = self.precompiles_cache.clone();
-
-        tokio::spawn(async move {
-            let mut next_height = current_head;
-            let mut dt = datetime_from_timestamp(current_ts)
-                .replace_minute(0)
-                .unwrap()
-                .replace_second(0)
-                .unwrap()
-                .replace_nanosecond(0)
-                .unwrap();
-
-            let mut hour = dt.hour();
-            let mut day_str = date_from_datetime(dt);
-            let mut last_line = 0;
-
-            loop {
-                let hour_file = root.join(HOURLY_SUBDIR).join(&day_str).join(format!("{hour}"));
-
-                if hour_file.exists() {
-                    let ScanResult { next_expected_height, new_blocks } =
-                        scan_hour_file(&hour_file, &mut last_line, next_height);
-                    if !new_blocks.is_empty() {
-                        let mut u_cache = cache.lock().await;
-                        let mut u_pre_cache = precompiles_cache.lock();
-                        for blk in new_blocks {
-                            let precompiles = PrecompileData {
-                                precompiles: blk.read_precompile_calls.clone(),
-                                highest_precompile_address: blk.highest_precompile_address,
-                            };
-                            let h = match &blk.block {
-                                EvmBlock::Reth115(b) => {
-                                    let block_number = b.header().number() as u64;
-                                    block_number
-                                }
-                            };
-                            u_cache.insert(h, blk);
-                            u_pre_cache.insert(h, precompiles);
-                        }
-                        next_height = next_expected_height;
-                    }
-                }
-
-                // Decide whether the *current* hour file is closed (past) or
-                // still live. If it’s in the past by > 1 h, move to next hour;
-                // otherwise, keep tailing the same file.
-                let now = OffsetDateTime::now_utc();
-
-                // println!("Date Current {:?}", dt);
-                // println!("Now Current {:?}", now);
-
-                if dt + Duration::HOUR < now {
-                    dt += Duration::HOUR;
-                    hour = dt.hour();
-                    day_str = date_from_datetime(dt);
-                    last_line = 0;
-                    info!(
-                        "Moving to a new file. {:?}",
-                        root.join(HOURLY_SUBDIR).join(&day_str).join(format!("{hour}"))
-                    );
-                    continue;
-                }
-
-                tokio::time::sleep(TAIL_INTERVAL).await;
-            }
-        });
-    }
-
+    /// Imports blocks from the configured sources through the engine API.
+    ///
+    /// Blocks are imported from the archive until it has no more blocks, and from the live
+    /// sources afterwards. Ingestion switches back to the archive if the live sources fall
+    /// behind it.
+    ///
+    /// Transient errors are retried with an exponential backoff. Any other error stops block
+    /// ingestion and is returned, while the node itself keeps running.
     pub(crate) async fn run<Node, Engine, AddOns>(
         &self,
-        node: FullNode<Node, AddOns>,
-    ) -> Result<(), Box<dyn std::error::Error>>
+        node: &FullNode<Node, AddOns>,
+    ) -> eyre::Result<()>
     where
         Node: FullNodeComponents,
         AddOns: RethRpcAddOns<Node>,
         Engine: EngineTypes,
-        Node::Types: NodeTypesWithEngine<ChainSpec: EthereumHardforks, Engine = Engine>,
+        Node::Types: NodeTypesWithEngine<
+            ChainSpec: EthereumHardforks,
+            Engine = Engine,
+            Primitives = EthPrimitives,
+        >,
         Node::Network: PeersHandleProvider,
         AddOns: RethRpcAddOns<Node>,
         Engine::ExecutionPayloadEnvelopeV3: From<Engine::BuiltPayload>,
         Engine::ExecutionPayloadEnvelopeV4: From<Engine::BuiltPayload>,
     {
+        // Wait for the pipeline to backfill the blocks already in the ingest directory
+        while node.network.is_syncing() {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+        }
+
         let provider = &node.provider;
         let checkpoint = provider.get_stage_checkpoint(StageId::Finish)?;
         let head = checkpoint.unwrap_or_default().block_number;
@@ -273,77 +135,80 @@ impl BlockIngest {
             std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis();
 
         let engine_api = node.auth_server_handle().http_client();
-        let mut evm_map = erc20_contract_to_spot_token(node.chain_spec().chain_id()).await?;
+        let mut spot_tokens = SpotTokens::load(self.spot_token_store.clone())?;
+        let mut receipt_verifier = ReceiptVerifier::new(&self.receipt_verification)?;
+        let auditor =
+            self.audit_live_blocks.then(|| ConsistencyAuditor::new(self.archive.clone()).spawn());
+        // Sources in priority order per mode. The sources of the other mode are queried as well,
+        // which switches the mode if they have the block.
+        let backfill_sources = FallbackBlockSource::new(
+            std::iter::once(self.archive.clone()).chain(self.live_sources.clone()).collect(),
+        );
+        let live_sources = FallbackBlockSource::new(
+            self.live_sources
+                .iter()
+                .cloned()
+                .chain(std::iter::once(self.archive.clone()))
+                .collect(),
+        );
 
         let current_block_timestamp: u64 = provider
-            .block_by_number(head)
-            .expect("Failed to fetch current block in db")
-            .expect("Block does not exist")
+            .block_by_number(head)?
+            .ok_or_else(|| eyre::eyre!("block {head} does not exist"))?
             .into_header()
             .timestamp();
 
         info!("Current height {height}, timestamp {current_block_timestamp}");
-        self.start_local_ingest_loop(height, current_block_timestamp).await;
+        if let Some(local_source) = &self.local_source {
+            local_source.start_tailing(height, current_block_timestamp);
+        }
+        if let Some(s3_http_source) = &self.s3_http_source {
+            s3_http_source.start_syncing(height);
+        }
 
+        let mut backoff = INITIAL_RETRY_BACKOFF;
         loop {
-            let Some(original_block) = self.collect_block(height).await else {
-                tokio::time::sleep(std::time::Duration::from_millis(25)).await;
-                continue;
-            };
-            let EvmBlock::Reth115(mut block) = original_block.block;
-            {
-                debug!(target: "reth::cli", ?block, "Built new payload");
-                let timestamp = block.header().timestamp();
-
-                let block_hash = block.clone().try_recover()?.hash();
-                {
-                    let BlockBody { transactions, ommers, withdrawals } =
-                        std::mem::take(block.body_mut());
-                    let mut system_txs = vec![];
-
-                    for transaction in original_block.system_txs {
-                        let TypedTransaction::Legacy(tx) = &transaction.tx else {
-                            panic!("Unexpected transaction type");
-                        };
-                        let TxKind::Call(to) = tx.to else {
-                            panic!("Unexpected contract creation");
-                        };
-                        let s = if tx.input().is_empty() {
-                            U256::from(0x1)
-                        } else {
-                            loop {
-                                if let Some(spot) = evm_map.get(&to) {
-                                    break spot.to_s();
-                                }
-
-                                info!(
-                                    "Contract not found: {:?} from spot mapping, fetching again...",
-                                    to
-                                );
-                                evm_map =
-                                    erc20_contract_to_spot_token(node.chain_spec().chain_id())
-                                        .await?;
-                            }
-                        };
-                        let signature = PrimitiveSignature::new(
-                            // from anvil
-                            U256::from(0x1),
-                            s,
-                            true,
-                        );
-                        let typed_transaction = transaction.tx;
-                        let tx = TransactionSigned::new(
-                            typed_transaction,
-                            signature,
-                            Default::default(),
-                        );
-                        system_txs.push(tx);
+            // Returns `false` if the block is not available yet.
+            let imported = async {
+                let sources = match self.status.mode() {
+                    IngestMode::Backfill => &backfill_sources,
+                    IngestMode::Live => &live_sources,
+                };
+                let Some((original_block, source)) = sources.collect_block_with_source(height)?
+                else {
+                    return Ok(false);
+                };
+                let mode = if Arc::ptr_eq(source, &self.archive) {
+                    IngestMode::Backfill
+                } else {
+                    IngestMode::Live
+                };
+                if let Some(auditor) = &auditor {
+                    if mode == IngestMode::Live {
+                        auditor.audit(&original_block);
                     }
-                    let mut txs = vec![];
-                    txs.extend(system_txs);
-                    txs.extend(transactions);
-                    *block.body_mut() = BlockBody { transactions: txs, ommers, withdrawals };
                 }
+                if let Some(verifier) = &mut receipt_verifier {
+                    verifier.expect_block(&original_block);
+                }
+                // The read precompile calls are stored before the block is submitted, so that
+                // the engine never persists a block that cannot be executed again. Storing them
+                // replaces the entry of a block that is submitted again after an error. The write
+                // waits for the database write lock, so it runs on the blocking pool.
+                let precompile_store = self.precompile_store.clone();
+                let precompile_data = original_block.precompile_data();
+                tokio::task::spawn_blocking(move || {
+                    precompile_store.insert_precompile_data(&[(height, precompile_data)])
+                })
+                .await
+                .map_err(eyre::Error::from)
+                .and_then(|stored| stored)
+                .map_err(|err| IngestError::Database { height, reason: err.to_string() })?;
+                let block = seal_block_with_system_txs(original_block, &mut spot_tokens)?;
+
+                debug!(target: "reth::cli", ?block, "Built new payload");
+                let timestamp = block.header().timestamp();
+                let block_hash = block.hash();
 
                 let total_fees = U256::ZERO;
                 let payload = EthBuiltPayload::new(
@@ -365,6 +230,7 @@ impl BlockIngest {
                 );
                 submit_payload::<Engine>(
                     &engine_api,
+                    height,
                     payload,
                     attributes,
                     PayloadStatusEnum::Valid,
@@ -387,12 +253,49 @@ impl BlockIngest {
                         None,
                     )
                     .await
-                    .unwrap();
+                    .map_err(|err| IngestError::Engine { height, reason: err.to_string() })?;
                     previous_timestamp = current_timestamp;
+                    if let Some(local_source) = &self.local_source {
+                        local_source.set_canonical_head(height);
+                    }
+
+                    if let Some(verifier) = &mut receipt_verifier {
+                        verifier.verify(provider)?;
+                    }
                 }
                 previous_hash = block_hash;
+                self.status.record_import(height, mode, source.name());
+                Ok::<_, IngestError>(true)
+            }
+            .await;
+
+            match imported {
+                Ok(true) => {
+                    height += 1;
+                    backoff = INITIAL_RETRY_BACKOFF;
+                }
+                Ok(false) => tokio::time::sleep(Duration::from_millis(25)).await,
+                Err(err) if err.is_transient() => {
+                    warn!(
+                        target: "reth::cli",
+                        height,
+                        %err,
+                        ?backoff,
+                        "Failed to ingest block, retrying"
+                    );
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+                }
+                Err(err) => {
+                    error!(
+                        target: "reth::cli",
+                        height,
+                        %err,
+                        "Block ingestion halted, the node keeps serving RPC requests"
+                    );
+                    return Err(err.into());
+                }
             }
-            height += 1;
         }
     }
 }
@@ -1,6 +1,10 @@
 use alloy_eips::BlockId;
 use alloy_primitives::{Bytes, U256};
-use alloy_rpc_types_eth::{state::StateOverride, transaction::TransactionRequest, BlockOverrides};
+use alloy_rpc_types_eth::{
+    state::{EvmOverrides, StateOverride},
+    transaction::TransactionRequest,
+    BlockOverrides,
+};
 use jsonrpsee::{
     http_client::{HttpClient, HttpClientBuilder},
     proc_macros::rpc,
@@ -8,6 +12,9 @@ use jsonrpsee::{
     types::{error::INTERNAL_ERROR_CODE, ErrorObject},
 };
 use jsonrpsee_core::{async_trait, client::ClientT, ClientError, RpcResult};
+use reth_hyperliquid_types::PRECOMPILE_DATA_UNAVAILABLE_CODE;
+use reth_rpc_eth_api::helpers::EthCall;
+use tracing::debug;
 
 #[rpc(server, namespace = "eth")]
 pub(crate) trait CallForwarderApi {
@@ -95,3 +102,79 @@ impl CallForwarderApiServer for CallForwarderExt {
         Ok(result)
     }
 }
+
+/// Serves `eth_call` and `eth_estimateGas` locally, and forwards them to the upstream RPC if the
+/// read precompile calls of the block are unavailable.
+pub(crate) struct CallFallbackExt<Eth> {
+    eth_api: Eth,
+    upstream: CallForwarderExt,
+}
+
+impl<Eth> CallFallbackExt<Eth> {
+    pub(crate) fn new(eth_api: Eth, upstream_rpc_url: String) -> Self {
+        Self { eth_api, upstream: CallForwarderExt::new(upstream_rpc_url) }
+    }
+}
+
+/// Returns `true` if the call failed because the read precompile calls of its block are
+/// unavailable.
+fn is_precompile_data_unavailable(err: &ErrorObject<'static>) -> bool {
+    err.code() == PRECOMPILE_DATA_UNAVAILABLE_CODE
+}
+
+#[async_trait]
+impl<Eth> CallForwarderApiServer for CallFallbackExt<Eth>
+where
+    Eth: EthCall + 'static,
+{
+    async fn call(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_overrides: Option<StateOverride>,
+        block_overrides: Option<Box<BlockOverrides>>,
+    ) -> RpcResult<Bytes> {
+        let overrides = EvmOverrides::new(state_overrides.clone(), block_overrides.clone());
+        match EthCall::call(&self.eth_api, request.clone(), block_number, overrides).await {
+            Ok(output) => Ok(output),
+            Err(err) => {
+                let err: ErrorObject<'static> = err.into();
+                if !is_precompile_data_unavailable(&err) {
+                    return Err(err);
+                }
+                debug!(
+                    target: "rpc::eth",
+                    ?block_number,
+                    "Forwarding eth_call to the upstream RPC"
+                );
+                self.upstream.call(request, block_number, state_overrides, block_overrides).await
+            }
+        }
+    }
+
+    async fn estimate_gas(
+        &self,
+        request: TransactionRequest,
+        block_number: Option<BlockId>,
+        state_override: Option<StateOverride>,
+    ) -> RpcResult<U256> {
+        let at = block_number.unwrap_or_default();
+        match EthCall::estimate_gas_at(&self.eth_api, request.clone(), at, state_override.clone())
+            .await
+        {
+            Ok(gas) => Ok(gas),
+            Err(err) => {
+                let err: ErrorObject<'static> = err.into();
+                if !is_precompile_data_unavailable(&err) {
+                    return Err(err);
+                }
+                debug!(
+                    target: "rpc::eth",
+                    ?block_number,
+                    "Forwarding eth_estimateGas to the upstream RPC"
+                );
+                self.upstream.estimate_gas(request, block_number, state_override).await
+            }
+        }
+    }
+}
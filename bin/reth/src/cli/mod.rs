@@ -2,7 +2,7 @@
 
 use crate::{
     args::LogArgs,
-    commands::debug_cmd,
+    commands::{debug_cmd, hl},
     version::{LONG_VERSION, SHORT_VERSION},
 };
 use clap::{value_parser, Parser, Subcommand};
@@ -188,6 +188,9 @@ impl<C: ChainSpecParser<ChainSpec = ChainSpec>, Ext: clap::Args + fmt::Debug> Cl
                 runner.run_command_until_exit(|ctx| command.execute::<EthereumNode>(ctx))
             }
             Commands::Prune(command) => runner.run_until_ctrl_c(command.execute::<EthereumNode>()),
+            Commands::Hl(command) => {
+                runner.run_blocking_until_ctrl_c(command.execute::<EthereumNode>())
+            }
         }
     }
 
@@ -243,6 +246,9 @@ pub enum Commands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
     /// Prune according to the configuration without any limits
     #[command(name = "prune")]
     Prune(prune::PruneCommand<C>),
+    /// Hyperliquid specific utilities
+    #[command(name = "hl")]
+    Hl(hl::Command<C>),
 }
 
 #[cfg(test)]
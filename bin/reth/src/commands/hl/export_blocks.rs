@@ -0,0 +1,221 @@
+//! Command that exports canonical blocks into the `.rmp.lz4` layout of the S3 bucket.
+
+use clap::Parser;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_db_api::{tables, transaction::DbTx};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_hyperliquid_types::{
+    block_source::write_block_file, BlockAndReceipts, BlockSource, EvmBlock, LegacyReceipt,
+    PrecompileData, S3BlockSource, SystemTx,
+};
+use reth_primitives::{
+    BlockBody, Receipt, SealedBlock, SealedHeader, Transaction, TransactionSigned,
+};
+use reth_provider::{BlockNumReader, BlockReader, DBProvider, HeaderProvider, ReceiptProvider};
+use std::{path::PathBuf, time::Instant};
+use tracing::{info, warn};
+
+/// Interval of the progress log, in blocks.
+const LOG_INTERVAL: u64 = 10_000;
+
+/// `reth hl export-blocks` command
+///
+/// Reads blocks, their receipts and read precompile calls from the database and static files,
+/// and writes them as `{f}/{s}/{height}.rmp.lz4` files that `--ingest-dir` of another node can
+/// read.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to export.
+    #[arg(long, value_name = "BLOCK", default_value_t = 1)]
+    from: u64,
+
+    /// The last block to export, inclusive. Defaults to the highest block in the database.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<u64>,
+
+    /// Directory the block files are written to.
+    #[arg(long, value_name = "DIR")]
+    out: PathBuf,
+
+    /// Directory laid out like the S3 bucket to copy the read precompile calls of the blocks
+    /// from, usually the `--ingest-dir` of the node. Only used for blocks whose read precompile
+    /// calls are not stored in the database.
+    ///
+    /// Blocks are exported without read precompile calls if they are found in neither.
+    #[arg(long, value_name = "DIR")]
+    precompiles_dir: Option<PathBuf>,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `hl export-blocks` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RO)?;
+        let provider = provider_factory.provider()?;
+
+        let last_block = provider.last_block_number()?;
+        let to = self.to.unwrap_or(last_block);
+        eyre::ensure!(self.from > 0, "the genesis block cannot be exported");
+        eyre::ensure!(self.from <= to, "--from {} is above --to {to}", self.from);
+        eyre::ensure!(
+            to <= last_block,
+            "block {to} is not in the database, the highest block is {last_block}"
+        );
+
+        let precompiles_source = self.precompiles_dir.map(S3BlockSource::new);
+        let mut missing_precompiles = 0u64;
+        let start = Instant::now();
+        info!(target: "reth::cli", from = self.from, to, out = ?self.out, "Exporting blocks");
+
+        for height in self.from..=to {
+            let header = provider
+                .sealed_header(height)?
+                .ok_or_else(|| eyre::eyre!("header of block {height} not found"))?;
+            let block = provider
+                .block_by_number(height)?
+                .ok_or_else(|| eyre::eyre!("block {height} not found"))?;
+            let receipts = provider
+                .receipts_by_block(height.into())?
+                .ok_or_else(|| eyre::eyre!("receipts of block {height} not found"))?;
+
+            let stored = provider.tx_ref().get::<tables::BlockPrecompileCalls>(height)?;
+            let precompile_data = match (stored, &precompiles_source) {
+                (Some(bytes), _) => Some(PrecompileData::decode(&bytes)?),
+                (None, Some(source)) => match source.collect_block(height) {
+                    Ok(block) => block.map(|block| block.precompile_data()),
+                    Err(err) => {
+                        warn!(target: "reth::cli", height, %err, "Failed to read precompile calls");
+                        None
+                    }
+                },
+                (None, None) => None,
+            };
+            if precompile_data.is_none() {
+                missing_precompiles += 1;
+            }
+
+            let block = into_block_and_receipts(
+                header,
+                block.body,
+                &receipts,
+                precompile_data.unwrap_or_default(),
+            )?;
+            write_block_file(&self.out, &block)?;
+
+            if height % LOG_INTERVAL == 0 {
+                info!(target: "reth::cli", height, to, "Exported blocks");
+            }
+        }
+
+        if missing_precompiles > 0 {
+            warn!(
+                target: "reth::cli",
+                blocks = missing_precompiles,
+                "Exported blocks without read precompile calls"
+            );
+        }
+        info!(
+            target: "reth::cli",
+            blocks = to - self.from + 1,
+            elapsed = ?start.elapsed(),
+            "Exported blocks"
+        );
+        Ok(())
+    }
+}
+
+/// Converts a canonical block back into the format hl-node produces.
+///
+/// This is the inverse of [`BlockAndReceipts::into_sealed_block`]: the system transactions
+/// prepended to the block transactions are split off again, together with their receipts.
+fn into_block_and_receipts(
+    header: SealedHeader,
+    body: BlockBody,
+    receipts: &[Receipt],
+    precompile_data: PrecompileData,
+) -> eyre::Result<BlockAndReceipts> {
+    let height = header.header().number;
+    eyre::ensure!(
+        receipts.len() == body.transactions.len(),
+        "block {height} has {} transactions but {} receipts",
+        body.transactions.len(),
+        receipts.len()
+    );
+
+    // hl-node stores the system transactions as the legacy transactions they execute.
+    let BlockBody { mut transactions, ommers, withdrawals } = body;
+    let system_txs: Vec<_> = transactions
+        .iter()
+        .map_while(TransactionSigned::as_system)
+        .zip(receipts)
+        .map(|(tx, receipt)| SystemTx {
+            tx: Transaction::Legacy(tx.tx.clone()),
+            receipt: Some(LegacyReceipt::from(receipt)),
+        })
+        .collect();
+    let system_tx_count = system_txs.len();
+    let body =
+        BlockBody { transactions: transactions.split_off(system_tx_count), ommers, withdrawals };
+
+    Ok(BlockAndReceipts {
+        block: EvmBlock::Reth115(SealedBlock::from_sealed_parts(header, body)),
+        receipts: receipts[system_tx_count..].iter().map(LegacyReceipt::from).collect(),
+        system_txs,
+        read_precompile_calls: precompile_data.precompiles,
+        highest_precompile_address: precompile_data.highest_precompile_address,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, PrimitiveSignature, TxKind, U256};
+    use reth_primitives::{Block, Header};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn splits_system_transactions() {
+        let system_tx = Transaction::Legacy(TxLegacy {
+            to: TxKind::Call(Address::with_last_byte(0x22)),
+            value: U256::from(1),
+            ..Default::default()
+        });
+        let user_tx = TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy { gas_price: 1, ..Default::default() }),
+            PrimitiveSignature::test_signature(),
+        );
+        let block = Block {
+            header: Header { number: 1, ..Default::default() },
+            body: BlockBody { transactions: vec![user_tx], ..Default::default() },
+        };
+        let original = BlockAndReceipts {
+            block: EvmBlock::Reth115(SealedBlock::seal_slow(block)),
+            receipts: vec![],
+            system_txs: vec![SystemTx { tx: system_tx, receipt: None }],
+            read_precompile_calls: vec![],
+            highest_precompile_address: None,
+        };
+        let EvmBlock::Reth115(original_block) = &original.block;
+        let original_hash = original_block.hash();
+
+        let canonical = original.into_sealed_block(&BTreeMap::new()).unwrap();
+        let (header, body) = canonical.split_sealed_header_body();
+        let receipts = vec![Receipt::default(); 2];
+        let exported =
+            into_block_and_receipts(header, body, &receipts, PrecompileData::default()).unwrap();
+
+        let EvmBlock::Reth115(exported_block) = &exported.block;
+        assert_eq!(exported_block.hash(), original_hash);
+        assert_eq!(exported_block.body().transactions.len(), 1);
+        assert_eq!(exported.system_txs.len(), 1);
+        assert!(matches!(exported.system_txs[0].tx, Transaction::Legacy(_)));
+        assert_eq!(exported.receipts.len(), 1);
+    }
+}
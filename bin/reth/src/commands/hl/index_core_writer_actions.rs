@@ -0,0 +1,94 @@
+//! Command that indexes the CoreWriter actions of blocks executed before the index existed.
+
+use clap::Parser;
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::{AccessRights, CliNodeTypes, Environment, EnvironmentArgs};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_provider::{BlockBodyIndicesProvider, BlockNumReader, ReceiptProvider};
+use std::time::Instant;
+use tracing::{info, warn};
+
+/// Number of blocks indexed per database transaction, which is also the interval of the progress
+/// log.
+const COMMIT_INTERVAL: u64 = 10_000;
+
+/// `reth hl index-core-writer-actions` command
+///
+/// Blocks are indexed as their receipts are written, so this only has work to do for blocks
+/// executed by a version of the node without the index. Blocks whose receipts are pruned cannot
+/// be indexed.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    env: EnvironmentArgs<C>,
+
+    /// The first block to index.
+    #[arg(long, value_name = "BLOCK", default_value_t = 1)]
+    from: u64,
+
+    /// The last block to index, inclusive. Defaults to the highest block in the database.
+    #[arg(long, value_name = "BLOCK")]
+    to: Option<u64>,
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `hl index-core-writer-actions` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } = self.env.init::<N>(AccessRights::RW)?;
+
+        let last_block = provider_factory.provider()?.last_block_number()?;
+        let to = self.to.unwrap_or(last_block);
+        eyre::ensure!(self.from <= to, "--from {} is above --to {to}", self.from);
+        eyre::ensure!(
+            to <= last_block,
+            "block {to} is not in the database, the highest block is {last_block}"
+        );
+
+        let mut missing_receipts = 0u64;
+        let start = Instant::now();
+        info!(target: "reth::cli", from = self.from, to, "Indexing CoreWriter actions");
+
+        let mut batch_start = self.from;
+        while batch_start <= to {
+            let batch_end = to.min(batch_start.saturating_add(COMMIT_INTERVAL - 1));
+            let provider = provider_factory.provider_rw()?;
+            for height in batch_start..=batch_end {
+                let body = provider
+                    .block_body_indices(height)?
+                    .ok_or_else(|| eyre::eyre!("block {height} not found"))?;
+                // Pruned receipts are missing, so the actions cannot be placed in their block.
+                let receipts = provider.receipts_by_tx_range(body.tx_num_range())?;
+                if receipts.len() as u64 != body.tx_count {
+                    missing_receipts += 1;
+                    continue;
+                }
+                provider.insert_core_writer_actions(height, &receipts)?;
+            }
+            provider.commit()?;
+            info!(target: "reth::cli", height = batch_end, to, "Indexed CoreWriter actions");
+
+            if batch_end == to {
+                break;
+            }
+            batch_start = batch_end + 1;
+        }
+
+        if missing_receipts > 0 {
+            warn!(
+                target: "reth::cli",
+                blocks = missing_receipts,
+                "Blocks without receipts were not indexed"
+            );
+        }
+        info!(
+            target: "reth::cli",
+            blocks = to - self.from + 1,
+            elapsed = ?start.elapsed(),
+            "Indexed CoreWriter actions"
+        );
+        Ok(())
+    }
+}
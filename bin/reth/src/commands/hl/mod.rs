@@ -0,0 +1,38 @@
+//! `reth hl` command. Hyperliquid specific utilities.
+
+use clap::{Parser, Subcommand};
+use reth_chainspec::ChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_commands::common::CliNodeTypes;
+use reth_ethereum_primitives::EthPrimitives;
+
+mod export_blocks;
+mod index_core_writer_actions;
+
+/// `reth hl` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth hl` subcommands
+#[derive(Subcommand, Debug)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Export canonical blocks into the `.rmp.lz4` layout of the S3 bucket.
+    ExportBlocks(export_blocks::Command<C>),
+    /// Index the CoreWriter actions of blocks executed before the index existed.
+    IndexCoreWriterActions(index_core_writer_actions::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec = ChainSpec>> Command<C> {
+    /// Execute `hl` command
+    pub async fn execute<N: CliNodeTypes<Primitives = EthPrimitives, ChainSpec = C::ChainSpec>>(
+        self,
+    ) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::ExportBlocks(command) => command.execute::<N>().await,
+            Subcommands::IndexCoreWriterActions(command) => command.execute::<N>().await,
+        }
+    }
+}
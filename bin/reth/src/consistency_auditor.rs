@@ -0,0 +1,299 @@
+//! Consistency check of the blocks of the local hl-node against the S3 archive.
+//!
+//! Blocks imported from the local hl-node are compared with the copy of the same height in the
+//! archive, so that a misbehaving hl-node is noticed. Heights the archive does not have yet are
+//! compared once it catches up. Blocks are audited on a blocking task, so that reading the
+//! archive does not slow down block ingestion.
+
+use std::{
+    collections::VecDeque,
+    sync::mpsc::{self, RecvTimeoutError, SyncSender, TrySendError},
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::{keccak256, B256};
+use reth_hyperliquid_types::{BlockAndReceipts, BlockSourceBoxed, EvmBlock};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use serde::Serialize;
+use tracing::{debug, error, warn};
+
+/// Maximum number of blocks waiting for the archive to catch up. The oldest blocks are not
+/// audited if there are more.
+const MAX_PENDING_BLOCKS: usize = 100_000;
+
+/// Maximum number of blocks queued for the auditor task. Blocks are not audited while the queue
+/// is full.
+const MAX_QUEUED_BLOCKS: usize = 1_024;
+
+/// Interval at which the blocks waiting for the archive are compared again.
+const PENDING_AUDIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Metrics of the consistency check against the archive.
+#[derive(Metrics)]
+#[metrics(scope = "hl.audit")]
+struct ConsistencyAuditorMetrics {
+    /// Number of blocks compared with the archive.
+    audited_blocks: Counter,
+    /// Number of blocks that differ from the archive.
+    mismatched_blocks: Counter,
+    /// Number of blocks waiting for the archive to catch up.
+    pending_blocks: Gauge,
+    /// Number of blocks that could not be compared with the archive.
+    skipped_blocks: Counter,
+}
+
+/// Digests of the parts of a block that are compared between sources.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct BlockFingerprint {
+    block_hash: B256,
+    system_txs: B256,
+    receipts: B256,
+    read_precompile_calls: B256,
+}
+
+fn digest(value: &impl Serialize) -> B256 {
+    keccak256(rmp_serde::to_vec(value).unwrap_or_default())
+}
+
+impl From<&BlockAndReceipts> for BlockFingerprint {
+    fn from(block: &BlockAndReceipts) -> Self {
+        let EvmBlock::Reth115(sealed) = &block.block;
+        Self {
+            block_hash: sealed.hash(),
+            system_txs: digest(&block.system_txs),
+            receipts: digest(&block.receipts),
+            read_precompile_calls: digest(&block.read_precompile_calls),
+        }
+    }
+}
+
+impl BlockFingerprint {
+    /// Returns the names of the parts that differ from `other`.
+    fn diff(&self, other: &Self) -> Vec<&'static str> {
+        let mut fields = vec![];
+        if self.block_hash != other.block_hash {
+            fields.push("block_hash");
+        }
+        if self.system_txs != other.system_txs {
+            fields.push("system_txs");
+        }
+        if self.receipts != other.receipts {
+            fields.push("receipts");
+        }
+        if self.read_precompile_calls != other.read_precompile_calls {
+            fields.push("read_precompile_calls");
+        }
+        fields
+    }
+}
+
+/// Block of the local hl-node that differs from the archive.
+#[derive(Debug, Serialize)]
+struct MismatchReport<'a> {
+    block_number: u64,
+    fields: Vec<&'static str>,
+    local: &'a BlockFingerprint,
+    archive: &'a BlockFingerprint,
+}
+
+/// Compares blocks imported from the local hl-node with the archive.
+#[derive(Debug)]
+pub(crate) struct ConsistencyAuditor {
+    archive: BlockSourceBoxed,
+    /// Blocks that are not in the archive yet, in ascending order.
+    pending: VecDeque<(u64, BlockFingerprint)>,
+    metrics: ConsistencyAuditorMetrics,
+}
+
+impl ConsistencyAuditor {
+    pub(crate) fn new(archive: BlockSourceBoxed) -> Self {
+        Self { archive, pending: VecDeque::new(), metrics: Default::default() }
+    }
+
+    /// Spawns the task auditing the blocks sent to the returned handle.
+    ///
+    /// The task stops once the handle is dropped.
+    pub(crate) fn spawn(mut self) -> ConsistencyAuditorHandle {
+        let (to_auditor, blocks) = mpsc::sync_channel(MAX_QUEUED_BLOCKS);
+        let skipped_blocks = self.metrics.skipped_blocks.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut last_pending_audit = Instant::now();
+            loop {
+                match blocks.recv_timeout(PENDING_AUDIT_INTERVAL) {
+                    Ok(block) => {
+                        self.audit(&block);
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+                if last_pending_audit.elapsed() >= PENDING_AUDIT_INTERVAL {
+                    self.audit_pending();
+                    last_pending_audit = Instant::now();
+                }
+            }
+        });
+        ConsistencyAuditorHandle { to_auditor, skipped_blocks }
+    }
+
+    /// Compares a block of the local hl-node with the archive, or remembers it until the archive
+    /// has the block.
+    ///
+    /// Returns `false` if the block differs from the archive.
+    pub(crate) fn audit(&mut self, block: &BlockAndReceipts) -> bool {
+        let height = block.number();
+        let fingerprint = BlockFingerprint::from(block);
+        if !self.pending.is_empty() {
+            self.enqueue(height, fingerprint);
+            return true;
+        }
+        match self.compare(height, &fingerprint) {
+            Some(matches) => matches,
+            None => {
+                self.enqueue(height, fingerprint);
+                true
+            }
+        }
+    }
+
+    /// Compares the pending blocks the archive has caught up with.
+    pub(crate) fn audit_pending(&mut self) {
+        while let Some((height, fingerprint)) = self.pending.pop_front() {
+            if self.compare(height, &fingerprint).is_none() {
+                self.pending.push_front((height, fingerprint));
+                break;
+            }
+        }
+        self.metrics.pending_blocks.set(self.pending.len() as f64);
+    }
+
+    fn enqueue(&mut self, height: u64, fingerprint: BlockFingerprint) {
+        if self.pending.len() >= MAX_PENDING_BLOCKS {
+            self.pending.pop_front();
+            self.metrics.skipped_blocks.increment(1);
+        }
+        self.pending.push_back((height, fingerprint));
+        self.metrics.pending_blocks.set(self.pending.len() as f64);
+    }
+
+    /// Compares the block at `height` with the archive.
+    ///
+    /// Returns `None` if the archive does not have the block yet.
+    fn compare(&self, height: u64, local: &BlockFingerprint) -> Option<bool> {
+        let block = match self.archive.collect_block(height) {
+            Ok(block) => block?,
+            Err(err) => {
+                warn!(target: "reth::cli", height, %err, "Failed to read block from the archive");
+                self.metrics.skipped_blocks.increment(1);
+                return Some(true);
+            }
+        };
+
+        self.metrics.audited_blocks.increment(1);
+        let archive = BlockFingerprint::from(&block);
+        let fields = local.diff(&archive);
+        if fields.is_empty() {
+            debug!(target: "reth::cli", height, "Local block matches the archive");
+            return Some(true);
+        }
+
+        self.metrics.mismatched_blocks.increment(1);
+        let report = MismatchReport { block_number: height, fields, local, archive: &archive };
+        error!(
+            target: "reth::cli",
+            height,
+            report = %serde_json::to_string(&report).unwrap_or_default(),
+            "Block of the local hl-node differs from the archive"
+        );
+        Some(false)
+    }
+}
+
+/// Handle of the task spawned by [`ConsistencyAuditor::spawn`].
+#[derive(Debug)]
+pub(crate) struct ConsistencyAuditorHandle {
+    to_auditor: SyncSender<BlockAndReceipts>,
+    skipped_blocks: Counter,
+}
+
+impl ConsistencyAuditorHandle {
+    /// Queues a block of the local hl-node to be compared with the archive.
+    pub(crate) fn audit(&self, block: &BlockAndReceipts) {
+        match self.to_auditor.try_send(block.clone()) {
+            Ok(()) => {}
+            Err(TrySendError::Full(block)) => {
+                debug!(
+                    target: "reth::cli",
+                    height = block.number(),
+                    "Auditor is behind, skipping block"
+                );
+                self.skipped_blocks.increment(1);
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                warn!(target: "reth::cli", "Auditor stopped, blocks are no longer audited");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::Address;
+    use reth_hyperliquid_types::{test_utils::block, BlockSource, IngestError};
+    use std::{collections::BTreeMap, sync::Arc};
+
+    #[derive(Debug, Default)]
+    struct TestArchive(parking_lot::Mutex<BTreeMap<u64, BlockAndReceipts>>);
+
+    impl BlockSource for TestArchive {
+        fn name(&self) -> &str {
+            "archive"
+        }
+
+        fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError> {
+            Ok(self.0.lock().get(&height).cloned())
+        }
+
+        fn find_latest_block_number(&self) -> Option<u64> {
+            self.0.lock().keys().next_back().copied()
+        }
+    }
+
+    #[test]
+    fn detects_mismatching_fields() {
+        let archive = Arc::new(TestArchive::default());
+        archive.0.lock().insert(1, block(1));
+        let mut auditor = ConsistencyAuditor::new(archive);
+
+        assert!(auditor.audit(&block(1)));
+
+        let mut local = block(1);
+        local.read_precompile_calls.push((Address::ZERO, vec![]));
+        assert_eq!(
+            BlockFingerprint::from(&local).diff(&BlockFingerprint::from(&block(1))),
+            vec!["read_precompile_calls"]
+        );
+        assert!(!auditor.audit(&local));
+    }
+
+    #[test]
+    fn audits_pending_blocks_once_archived() {
+        let archive = Arc::new(TestArchive::default());
+        let mut auditor = ConsistencyAuditor::new(archive.clone());
+
+        assert!(auditor.audit(&block(1)));
+        assert!(auditor.audit(&block(2)));
+        assert_eq!(auditor.pending.len(), 2);
+
+        archive.0.lock().insert(1, block(1));
+        auditor.audit_pending();
+        assert_eq!(auditor.pending.iter().map(|(height, _)| *height).collect::<Vec<_>>(), [2]);
+
+        archive.0.lock().insert(2, block(2));
+        auditor.audit_pending();
+        assert!(auditor.pending.is_empty());
+    }
+}
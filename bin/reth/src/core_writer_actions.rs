@@ -0,0 +1,205 @@
+//! `hl_getCoreWriterActions` RPC, which exposes the actions sent from the EVM to HyperCore
+//! through the CoreWriter contract, indexed by sender and action id.
+
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, Bytes, B256};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee_core::{async_trait, RpcResult};
+use reth_db_api::{models::CoreWriterActionKey, table::Encode, tables, transaction::DbTx};
+use reth_hyperliquid_types::{decode_core_writer_action, DecodedCoreWriterAction};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{
+    BlockBodyIndicesProvider, BlockIdReader, BlockReader, DBProvider, DatabaseProviderFactory,
+    HeaderProvider, TransactionsProvider,
+};
+use reth_rpc_server_types::result::internal_rpc_err;
+use serde::Serialize;
+
+use crate::index_page::{block_range, decode_cursor, read_page};
+
+/// Maximum number of actions returned by one call.
+const MAX_CORE_WRITER_ACTIONS: usize = 1_000;
+
+/// An action sent to HyperCore through the CoreWriter contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CoreWriterAction {
+    /// Version, action id, and name and parameters if the action is known.
+    #[serde(flatten)]
+    pub action: DecodedCoreWriterAction,
+    /// Encoded action, as emitted by the `RawAction` event.
+    pub data: Bytes,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub transaction_hash: B256,
+    pub transaction_index: u64,
+    pub log_index: u64,
+}
+
+/// A page of the CoreWriter actions sent by an address.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CoreWriterActions {
+    /// Actions ordered by action id, then by block.
+    pub actions: Vec<CoreWriterAction>,
+    /// Cursor to pass to get the next page, if there are more actions.
+    pub cursor: Option<Bytes>,
+}
+
+#[rpc(server, namespace = "hl")]
+pub(crate) trait HlCoreWriterApi {
+    /// Returns the CoreWriter actions sent by `address` in the given block range, optionally only
+    /// the ones with the given action id.
+    ///
+    /// At most 1000 actions are returned per call, the remaining ones are returned when calling
+    /// again with the returned cursor.
+    #[method(name = "getCoreWriterActions")]
+    async fn core_writer_actions(
+        &self,
+        address: Address,
+        action_id: Option<u32>,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<Bytes>,
+    ) -> RpcResult<CoreWriterActions>;
+}
+
+pub(crate) struct HlCoreWriterExt<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> HlCoreWriterExt<Provider> {
+    pub(crate) const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl<Provider> HlCoreWriterApiServer for HlCoreWriterExt<Provider>
+where
+    Provider: BlockIdReader
+        + DatabaseProviderFactory<Provider: BlockReader<Transaction: SignedTransaction>>
+        + Clone
+        + 'static,
+{
+    async fn core_writer_actions(
+        &self,
+        address: Address,
+        action_id: Option<u32>,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<Bytes>,
+    ) -> RpcResult<CoreWriterActions> {
+        let range = block_range(&self.provider, from_block, to_block)?;
+        let in_query = move |key: &CoreWriterActionKey| {
+            key.sender == address && action_id.is_none_or(|id| id == key.action_id)
+        };
+        let start = match cursor {
+            Some(cursor) => decode_cursor(&cursor, in_query)?,
+            None => CoreWriterActionKey::new(address, action_id.unwrap_or_default(), 0, 0, 0),
+        };
+
+        let provider = self.provider.clone();
+        tokio::task::spawn_blocking(move || -> eyre::Result<_> {
+            let provider = provider.database_provider_ro()?;
+            let mut cursor = provider.tx_ref().cursor_read::<tables::CoreWriterActions>()?;
+            let (entries, next) =
+                read_page(&mut cursor, start, range, MAX_CORE_WRITER_ACTIONS, in_query)?;
+
+            let mut actions = Vec::with_capacity(entries.len());
+            let mut block = None;
+            for (key, data) in entries {
+                let (timestamp, first_tx_num) = match block {
+                    Some((number, timestamp, first_tx_num)) if number == key.block_number => {
+                        (timestamp, first_tx_num)
+                    }
+                    _ => {
+                        let header = provider
+                            .header_by_number(key.block_number)?
+                            .ok_or_else(|| eyre::eyre!("missing header {}", key.block_number))?;
+                        let body = provider
+                            .block_body_indices(key.block_number)?
+                            .ok_or_else(|| eyre::eyre!("missing body {}", key.block_number))?;
+                        block = Some((key.block_number, header.timestamp(), body.first_tx_num));
+                        (header.timestamp(), body.first_tx_num)
+                    }
+                };
+                let transaction = provider
+                    .transaction_by_id(first_tx_num + key.tx_index)?
+                    .ok_or_else(|| eyre::eyre!("missing transaction of action {key:?}"))?;
+                let action = decode_core_writer_action(&data)
+                    .ok_or_else(|| eyre::eyre!("invalid action {key:?}"))?;
+
+                actions.push(CoreWriterAction {
+                    action,
+                    data,
+                    block_number: key.block_number,
+                    timestamp,
+                    transaction_hash: *transaction.tx_hash(),
+                    transaction_index: key.tx_index,
+                    log_index: key.log_index,
+                });
+            }
+
+            Ok(CoreWriterActions { actions, cursor: next.map(|key| key.encode().into()) })
+        })
+        .await
+        .map_err(|err| internal_rpc_err(err.to_string()))?
+        .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{init_db, mdbx::DatabaseArguments, Database};
+    use reth_db_api::{models::ClientVersion, transaction::DbTxMut};
+
+    #[test]
+    fn reads_actions_in_range_by_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = init_db(dir.path(), DatabaseArguments::new(ClientVersion::default())).unwrap();
+
+        let sender = Address::with_last_byte(1);
+        let tx = db.tx_mut().unwrap();
+        for key in [
+            CoreWriterActionKey::new(sender, 1, 1, 0, 0),
+            CoreWriterActionKey::new(sender, 1, 5, 0, 0),
+            CoreWriterActionKey::new(sender, 1, 5, 0, 1),
+            CoreWriterActionKey::new(sender, 1, 9, 0, 0),
+            CoreWriterActionKey::new(sender, 2, 5, 1, 3),
+            CoreWriterActionKey::new(Address::with_last_byte(2), 1, 5, 0, 0),
+        ] {
+            tx.put::<tables::CoreWriterActions>(key, Bytes::new()).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let mut cursor = tx.cursor_read::<tables::CoreWriterActions>().unwrap();
+        let keys = |actions: Vec<(CoreWriterActionKey, Bytes)>| {
+            actions.into_iter().map(|(key, _)| key).collect::<Vec<_>>()
+        };
+
+        let any_action = |key: &CoreWriterActionKey| key.sender == sender;
+        let start = CoreWriterActionKey::new(sender, 0, 0, 0, 0);
+        let (actions, next) = read_page(&mut cursor, start, 2..=8, 1, any_action).unwrap();
+        assert_eq!(keys(actions), vec![CoreWriterActionKey::new(sender, 1, 5, 0, 0)]);
+        assert_eq!(next, Some(CoreWriterActionKey::new(sender, 1, 5, 0, 1)));
+
+        let (actions, next) = read_page(&mut cursor, next.unwrap(), 2..=8, 10, any_action).unwrap();
+        assert_eq!(
+            keys(actions),
+            vec![
+                CoreWriterActionKey::new(sender, 1, 5, 0, 1),
+                CoreWriterActionKey::new(sender, 2, 5, 1, 3)
+            ]
+        );
+        assert_eq!(next, None);
+
+        let start = CoreWriterActionKey::new(sender, 1, 0, 0, 0);
+        let action_1 = |key: &CoreWriterActionKey| key.sender == sender && key.action_id == 1;
+        let (actions, _) = read_page(&mut cursor, start, 0..=9, 10, action_1).unwrap();
+        assert_eq!(actions.len(), 4);
+    }
+}
@@ -0,0 +1,123 @@
+//! Local pending set of the transactions forwarded to the upstream RPCs.
+//!
+//! Forwarded transactions are added to the local transaction pool as private transactions, which
+//! are not propagated, so that `eth_getTransactionByHash`, `newPendingTransactions`, `txpool_*` and
+//! the `pending` nonce see them before they are included in a block. The pool removes them once
+//! they are included, and transactions that are not included within the configured lifetime are
+//! removed as well, e.g. if the upstream dropped them.
+
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use alloy_primitives::TxHash;
+use parking_lot::Mutex;
+use reth_primitives::{Recovered, TransactionSigned};
+use reth_primitives_traits::SignedTransaction;
+use reth_transaction_pool::{PoolTransaction, TransactionOrigin, TransactionPool};
+use tracing::debug;
+
+/// Interval at which expired transactions are removed from the pool.
+const EVICTION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Forwarded transactions, in the order they expire.
+#[derive(Debug, Default)]
+struct Expiries(VecDeque<(Instant, TxHash)>);
+
+impl Expiries {
+    fn push(&mut self, expiry: Instant, hash: TxHash) {
+        self.0.push_back((expiry, hash));
+    }
+
+    /// Removes and returns the transactions that expired at `now`.
+    fn take_expired(&mut self, now: Instant) -> Vec<TxHash> {
+        let count = self.0.iter().take_while(|(expiry, _)| *expiry <= now).count();
+        self.0.drain(..count).map(|(_, hash)| hash).collect()
+    }
+}
+
+/// Adds forwarded transactions to the local transaction pool, and removes them after `lifetime`
+/// if they are still there.
+#[derive(Debug)]
+pub(crate) struct ForwardedTransactions<Pool> {
+    pool: Pool,
+    expiries: Arc<Mutex<Expiries>>,
+    lifetime: Duration,
+}
+
+impl<Pool> ForwardedTransactions<Pool>
+where
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Clone
+        + 'static,
+{
+    /// Creates the pending set, and starts removing expired transactions from the pool.
+    pub(crate) fn new(pool: Pool, lifetime: Duration) -> Self {
+        let this = Self { pool, expiries: Default::default(), lifetime };
+        this.start_eviction();
+        this
+    }
+
+    fn start_eviction(&self) {
+        let (pool, expiries) = (self.pool.clone(), self.expiries.clone());
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EVICTION_INTERVAL);
+            loop {
+                interval.tick().await;
+                let expired = expiries.lock().take_expired(Instant::now());
+                if expired.is_empty() {
+                    continue
+                }
+                let removed = pool.remove_transactions(expired);
+                if !removed.is_empty() {
+                    debug!(
+                        target: "reth::hl",
+                        count = removed.len(),
+                        "Removed forwarded transactions that were not included"
+                    );
+                }
+            }
+        });
+    }
+
+    /// Adds the forwarded transaction `tx` to the pool.
+    ///
+    /// Transactions the pool rejects, e.g. because they replace a transaction with a too low fee
+    /// bump, are only forwarded.
+    pub(crate) async fn insert(&self, tx: Recovered<TransactionSigned>) {
+        let hash = *tx.tx_hash();
+        let Ok(tx) = <Pool::Transaction as PoolTransaction>::try_from_consensus(tx) else { return };
+        match self.pool.add_transaction(TransactionOrigin::Private, tx).await {
+            Ok(_) => self.expiries.lock().push(Instant::now() + self.lifetime, hash),
+            Err(err) => {
+                debug!(target: "reth::hl", %hash, %err, "Forwarded transaction not added to the pool")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_expired_transactions_in_order() {
+        let now = Instant::now();
+        let mut expiries = Expiries::default();
+        expiries.push(now, TxHash::with_last_byte(1));
+        expiries.push(now + Duration::from_secs(1), TxHash::with_last_byte(2));
+        expiries.push(now + Duration::from_secs(2), TxHash::with_last_byte(3));
+
+        assert_eq!(
+            expiries.take_expired(now + Duration::from_secs(1)),
+            [TxHash::with_last_byte(1), TxHash::with_last_byte(2)]
+        );
+        assert!(expiries.take_expired(now + Duration::from_secs(1)).is_empty());
+        assert_eq!(
+            expiries.take_expired(now + Duration::from_secs(5)),
+            [TxHash::with_last_byte(3)]
+        );
+    }
+}
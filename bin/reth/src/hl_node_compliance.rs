@@ -0,0 +1,95 @@
+//! RPC server that serves the RPC modules in hl-node compliant mode, next to the regular server.
+//!
+//! This lets one node serve both the view with the system transactions and the view of hl-node.
+
+use std::net::SocketAddr;
+
+use jsonrpsee::{
+    server::{middleware::rpc::RpcServiceT, RpcServiceBuilder, Server, ServerHandle},
+    types::Request,
+    Methods, RpcModule,
+};
+use reth_hyperliquid_types::with_hl_node_compliance;
+use tokio::task::futures::TaskLocalFuture;
+use tower::Layer;
+use tracing::info;
+
+/// Middleware that serves every request in hl-node compliant mode.
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct HlNodeCompliantLayer;
+
+impl<S> Layer<S> for HlNodeCompliantLayer {
+    type Service = HlNodeCompliantService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HlNodeCompliantService { inner }
+    }
+}
+
+/// See [`HlNodeCompliantLayer`].
+#[derive(Debug, Clone)]
+pub(crate) struct HlNodeCompliantService<S> {
+    inner: S,
+}
+
+impl<'a, S> RpcServiceT<'a> for HlNodeCompliantService<S>
+where
+    S: RpcServiceT<'a>,
+{
+    type Future = TaskLocalFuture<bool, S::Future>;
+
+    fn call(&self, request: Request<'a>) -> Self::Future {
+        with_hl_node_compliance(true, self.inner.call(request))
+    }
+}
+
+/// Returns a copy of `module` whose subscriptions are served by `pubsub`.
+///
+/// Subscriptions run in tasks of their own, so `pubsub` has to be set up for hl-node compliant
+/// mode itself.
+pub(crate) fn hl_node_compliant_module(
+    module: &RpcModule<()>,
+    pubsub: impl Into<Methods>,
+) -> eyre::Result<RpcModule<()>> {
+    let mut module = module.clone();
+    module.remove_method("eth_subscribe");
+    module.remove_method("eth_unsubscribe");
+    module.merge(pubsub)?;
+    Ok(module)
+}
+
+/// Serves `module` over http and ws on `addr` in hl-node compliant mode.
+///
+/// Returns the address the server listens on. The server stops once the handle is dropped.
+pub(crate) async fn start_hl_node_compliant_server(
+    addr: SocketAddr,
+    module: RpcModule<()>,
+) -> eyre::Result<(SocketAddr, ServerHandle)> {
+    let server = Server::builder()
+        .set_rpc_middleware(RpcServiceBuilder::new().layer(HlNodeCompliantLayer))
+        .build(addr)
+        .await?;
+    let addr = server.local_addr()?;
+    info!(target: "reth::cli", %addr, "hl-node compliant RPC server started");
+    Ok((addr, server.start(module)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder, rpc_params};
+    use reth_hyperliquid_types::is_hl_node_compliant;
+
+    #[tokio::test]
+    async fn serves_requests_in_hl_node_compliant_mode() {
+        let mut module = RpcModule::new(());
+        module.register_method("test_hlNodeCompliant", |_, _, _| is_hl_node_compliant()).unwrap();
+        assert!(!is_hl_node_compliant());
+
+        let (addr, _handle) =
+            start_hl_node_compliant_server(([127, 0, 0, 1], 0).into(), module).await.unwrap();
+        let client = HttpClientBuilder::default().build(format!("http://{addr}")).unwrap();
+        let compliant: bool = client.request("test_hlNodeCompliant", rpc_params![]).await.unwrap();
+        assert!(compliant);
+    }
+}
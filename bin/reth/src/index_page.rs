@@ -0,0 +1,135 @@
+//! Pagination shared by the `hl_` RPCs reading an index of the database, such as the system
+//! transfers by recipient and the CoreWriter actions by sender.
+//!
+//! These indexes are keyed by an address, then by a group, such as the token or the action id,
+//! then by block. A page is read by seeking to the block range within each group, and the key of
+//! the first entry of the next page is returned to the caller as the cursor.
+
+use std::ops::RangeInclusive;
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{aliases::U160, Address, Bytes};
+use jsonrpsee_core::RpcResult;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    models::{CoreWriterActionKey, SystemTransferKey},
+    table::{Decode, Table},
+    DatabaseError,
+};
+use reth_provider::BlockIdReader;
+use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
+
+/// Key of an index ordered by group, then by block.
+pub(crate) trait GroupedByBlock: Sized {
+    /// Number of the block the entry is included in.
+    fn block_number(&self) -> u64;
+
+    /// Returns the key of the first entry of the group of this key in block `block_number`.
+    fn group_start(&self, block_number: u64) -> Self;
+
+    /// Returns the key of the first entry of the next group in block `block_number`, if any.
+    fn next_group_start(&self, block_number: u64) -> Option<Self>;
+}
+
+impl GroupedByBlock for SystemTransferKey {
+    fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    fn group_start(&self, block_number: u64) -> Self {
+        Self::new(self.recipient, self.token, block_number, 0)
+    }
+
+    fn next_group_start(&self, block_number: u64) -> Option<Self> {
+        let token = U160::from_be_bytes(self.token.into_array()).checked_add(U160::from(1))?;
+        Some(Self::new(self.recipient, Address::from(token.to_be_bytes::<20>()), block_number, 0))
+    }
+}
+
+impl GroupedByBlock for CoreWriterActionKey {
+    fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    fn group_start(&self, block_number: u64) -> Self {
+        Self::new(self.sender, self.action_id, block_number, 0, 0)
+    }
+
+    fn next_group_start(&self, block_number: u64) -> Option<Self> {
+        Some(Self::new(self.sender, self.action_id.checked_add(1)?, block_number, 0, 0))
+    }
+}
+
+/// Entries of a page and the key of the first entry of the next page, if any.
+pub(crate) type Page<T> =
+    (Vec<(<T as Table>::Key, <T as Table>::Value)>, Option<<T as Table>::Key>);
+
+/// Returns the block range of a query, from `from_block`, or genesis if not set, to `to_block`,
+/// or the latest block if not set.
+pub(crate) fn block_range(
+    provider: &impl BlockIdReader,
+    from_block: Option<BlockNumberOrTag>,
+    to_block: Option<BlockNumberOrTag>,
+) -> RpcResult<RangeInclusive<u64>> {
+    let block_number = |block: BlockNumberOrTag| -> RpcResult<u64> {
+        provider
+            .convert_block_number(block)
+            .map_err(|err| internal_rpc_err(err.to_string()))?
+            .ok_or_else(|| invalid_params_rpc_err(format!("unknown block {block}")))
+    };
+    Ok(block_number(from_block.unwrap_or(BlockNumberOrTag::Earliest))?
+        ..=block_number(to_block.unwrap_or(BlockNumberOrTag::Latest))?)
+}
+
+/// Decodes the cursor returned by a previous call, which must belong to the same query.
+pub(crate) fn decode_cursor<K: Decode>(
+    cursor: &Bytes,
+    same_query: impl FnOnce(&K) -> bool,
+) -> RpcResult<K> {
+    let key = K::decode(cursor).map_err(|_| invalid_params_rpc_err("invalid cursor"))?;
+    if !same_query(&key) {
+        return Err(invalid_params_rpc_err("cursor of another query"));
+    }
+    Ok(key)
+}
+
+/// Reads at most `limit` entries, starting at `start`, that are included in a block in `range`.
+///
+/// Entries are read until the first one that is not part of the query, as told by `in_query`.
+pub(crate) fn read_page<T>(
+    cursor: &mut impl DbCursorRO<T>,
+    start: T::Key,
+    range: RangeInclusive<u64>,
+    limit: usize,
+    in_query: impl Fn(&T::Key) -> bool,
+) -> Result<Page<T>, DatabaseError>
+where
+    T: Table<Key: GroupedByBlock>,
+{
+    let mut entries = Vec::new();
+    let mut entry = cursor.seek(start)?;
+    while let Some((key, value)) = entry {
+        if !in_query(&key) {
+            break;
+        }
+
+        // Entries are ordered by group, so skip to the range within the current group, or to the
+        // next group once past the range.
+        if key.block_number() < *range.start() {
+            entry = cursor.seek(key.group_start(*range.start()))?;
+            continue;
+        }
+        if key.block_number() > *range.end() {
+            let Some(next) = key.next_group_start(*range.start()) else { break };
+            entry = cursor.seek(next)?;
+            continue;
+        }
+
+        if entries.len() == limit {
+            return Ok((entries, Some(key)));
+        }
+        entries.push((key, value));
+        entry = cursor.next()?;
+    }
+    Ok((entries, None))
+}
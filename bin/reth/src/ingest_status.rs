@@ -0,0 +1,147 @@
+//! Status of block ingestion, reported over RPC and in metrics.
+
+use std::{fmt, sync::Arc};
+
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee_core::{async_trait, RpcResult};
+use parking_lot::RwLock;
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use serde::Serialize;
+use tracing::info;
+
+/// Where blocks are currently imported from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum IngestMode {
+    /// Blocks are imported from the archive, i.e. `--ingest-dir` or the S3 bucket.
+    #[default]
+    Backfill,
+    /// The archive has no more blocks, so blocks are imported from the live sources, i.e. the
+    /// local hl-node output.
+    Live,
+}
+
+impl fmt::Display for IngestMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Backfill => f.write_str("backfill"),
+            Self::Live => f.write_str("live"),
+        }
+    }
+}
+
+/// Metrics of block ingestion.
+#[derive(Metrics)]
+#[metrics(scope = "hl.ingest")]
+struct IngestMetrics {
+    /// Current mode, `0` for backfill and `1` for live.
+    mode: Gauge,
+    /// Number of switches between backfill and live mode.
+    mode_switches: Counter,
+    /// Height of the last imported block.
+    height: Gauge,
+}
+
+/// Status of block ingestion.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct IngestStatus {
+    pub mode: IngestMode,
+    /// Height of the last imported block.
+    pub height: Option<u64>,
+    /// Name of the source the last block was imported from.
+    pub source: Option<String>,
+}
+
+/// Shared handle to the status of block ingestion.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct IngestStatusHandle {
+    status: Arc<RwLock<IngestStatus>>,
+    metrics: Arc<IngestMetrics>,
+}
+
+impl IngestStatusHandle {
+    /// Returns the current status.
+    pub(crate) fn status(&self) -> IngestStatus {
+        self.status.read().clone()
+    }
+
+    /// Returns the current mode.
+    pub(crate) fn mode(&self) -> IngestMode {
+        self.status.read().mode
+    }
+
+    /// Records that the block at `height` was imported from `source` in `mode`.
+    pub(crate) fn record_import(&self, height: u64, mode: IngestMode, source: &str) {
+        let mut status = self.status.write();
+        if status.mode != mode {
+            info!(
+                target: "reth::cli",
+                height,
+                source,
+                from = %status.mode,
+                to = %mode,
+                "Switched block ingestion mode"
+            );
+            self.metrics.mode_switches.increment(1);
+            status.mode = mode;
+        }
+        if status.source.as_deref() != Some(source) {
+            status.source = Some(source.to_string());
+        }
+        status.height = Some(height);
+
+        self.metrics.mode.set(mode as u8 as f64);
+        self.metrics.height.set(height as f64);
+    }
+}
+
+#[rpc(server, namespace = "hl")]
+pub(crate) trait HlIngestApi {
+    /// Returns the status of block ingestion.
+    #[method(name = "ingestStatus")]
+    async fn ingest_status(&self) -> RpcResult<IngestStatus>;
+}
+
+pub(crate) struct HlIngestExt {
+    status: IngestStatusHandle,
+}
+
+impl HlIngestExt {
+    pub(crate) fn new(status: IngestStatusHandle) -> Self {
+        Self { status }
+    }
+}
+
+#[async_trait]
+impl HlIngestApiServer for HlIngestExt {
+    async fn ingest_status(&self) -> RpcResult<IngestStatus> {
+        Ok(self.status.status())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_mode_switches() {
+        let handle = IngestStatusHandle::default();
+        assert_eq!(handle.mode(), IngestMode::Backfill);
+
+        handle.record_import(1, IngestMode::Backfill, "archive");
+        handle.record_import(2, IngestMode::Live, "local");
+        let status = handle.status();
+        assert_eq!(status.mode, IngestMode::Live);
+        assert_eq!(status.height, Some(2));
+        assert_eq!(status.source.as_deref(), Some("local"));
+
+        assert_eq!(
+            serde_json::to_value(&status).unwrap(),
+            serde_json::json!({ "mode": "live", "height": 2, "source": "local" })
+        );
+    }
+}
@@ -0,0 +1,589 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use parking_lot::Mutex;
+use reth_hyperliquid_types::{BlockAndReceipts, BlockSource, IngestError, PrecompilesCache};
+use reth_metrics::{
+    metrics::{Counter, Gauge},
+    Metrics,
+};
+use serde::Deserialize;
+use time::{format_description, Duration, OffsetDateTime};
+use tokio::sync::Notify;
+use tracing::{info, warn};
+
+/// Poll interval when tailing an *open* hourly file without filesystem notifications.
+const TAIL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(25);
+/// Poll interval when tailing with filesystem notifications, in case an event is missed.
+const WATCHED_TAIL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+/// Interval at which an hourly file that could not be read is retried.
+const SCAN_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+/// Time after the end of an hour after which the next hourly file is tailed, even if hl-node
+/// has not created it yet.
+const ROTATION_GRACE_PERIOD: Duration = Duration::seconds(10);
+/// Sub‑directory that contains day folders (inside `local_ingest_dir`).
+const HOURLY_SUBDIR: &str = "hourly";
+
+/// Metrics of the blocks read ahead from the hl-node output.
+#[derive(Metrics)]
+#[metrics(scope = "hl.local_ingest")]
+struct LocalBlocksCacheMetrics {
+    /// Number of cached blocks.
+    cached_blocks: Gauge,
+    /// Number of blocks with cached precompile calls.
+    cached_precompiles: Gauge,
+    /// Distance between the highest cached block and the block being imported.
+    cache_depth: Gauge,
+    /// Number of cached blocks that were evicted because the import advanced past them.
+    evicted_blocks: Counter,
+    /// Number of times the tailer waited for the import to catch up with the cache.
+    backpressure_waits: Counter,
+}
+
+/// Blocks read from the hl-node output that have not been imported yet, keyed by height.
+///
+/// Only blocks in a window of `capacity` blocks starting at the import height are cached, and
+/// the tailer waits for the import to advance once the window is full.
+#[derive(Debug)]
+struct LocalBlocksCache {
+    blocks: Mutex<BTreeMap<u64, BlockAndReceipts>>,
+    /// Precompile calls of the cached blocks, consumed when the blocks are executed.
+    precompiles: PrecompilesCache,
+    /// Height of the block being imported.
+    import_height: AtomicU64,
+    capacity: u64,
+    /// Notified when the import height advances.
+    advanced: Notify,
+    metrics: LocalBlocksCacheMetrics,
+}
+
+impl LocalBlocksCache {
+    fn new(precompiles: PrecompilesCache, capacity: u64) -> Self {
+        Self {
+            blocks: Default::default(),
+            precompiles,
+            import_height: AtomicU64::new(0),
+            capacity: capacity.max(1),
+            advanced: Notify::new(),
+            metrics: Default::default(),
+        }
+    }
+
+    fn import_height(&self) -> u64 {
+        self.import_height.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of blocks that can be cached starting at `next_height`.
+    fn room(&self, next_height: u64) -> u64 {
+        (self.import_height() + self.capacity).saturating_sub(next_height)
+    }
+
+    /// Caches `blocks` and their precompile calls, ignoring blocks below the import height.
+    fn insert(&self, blocks: Vec<BlockAndReceipts>) {
+        let import_height = self.import_height();
+        let mut u_cache = self.blocks.lock();
+        let mut u_pre_cache = self.precompiles.lock();
+        for blk in blocks {
+            let h = blk.number();
+            if h < import_height {
+                continue;
+            }
+            u_pre_cache.insert(h, blk.precompile_data());
+            u_cache.insert(h, blk);
+        }
+        self.update_metrics(&u_cache, u_pre_cache.len());
+    }
+
+    /// Returns the block at `height`, which is about to be imported.
+    ///
+    /// Blocks below `height` are evicted, since they have been imported already. The block itself
+    /// stays cached until the import advances past it, so that it can be imported again if the
+    /// import fails.
+    fn get(&self, height: u64) -> Option<BlockAndReceipts> {
+        self.advance(height);
+        self.blocks.lock().get(&height).cloned()
+    }
+
+    /// Evicts the blocks and precompile calls up to the canonical `head`.
+    fn set_canonical_head(&self, head: u64) {
+        self.advance(head + 1);
+        let u_cache = self.blocks.lock();
+        let mut u_pre_cache = self.precompiles.lock();
+        *u_pre_cache = u_pre_cache.split_off(&(head + 1));
+        self.update_metrics(&u_cache, u_pre_cache.len());
+    }
+
+    /// Raises the import height to `height`, evicting the blocks below it.
+    fn advance(&self, height: u64) {
+        if self.import_height.fetch_max(height, Ordering::Relaxed) >= height {
+            return;
+        }
+        let mut u_cache = self.blocks.lock();
+        let remaining = u_cache.split_off(&height);
+        self.metrics.evicted_blocks.increment(u_cache.len() as u64);
+        *u_cache = remaining;
+        self.update_metrics(&u_cache, self.precompiles.lock().len());
+        drop(u_cache);
+        self.advanced.notify_one();
+    }
+
+    fn update_metrics(&self, blocks: &BTreeMap<u64, BlockAndReceipts>, precompiles: usize) {
+        self.metrics.cached_blocks.set(blocks.len() as f64);
+        self.metrics.cached_precompiles.set(precompiles as f64);
+        let depth = blocks
+            .last_key_value()
+            .map_or(0, |(height, _)| (height + 1).saturating_sub(self.import_height()));
+        self.metrics.cache_depth.set(depth as f64);
+    }
+}
+
+/// Block source tailing the hourly files written by a local hl-node.
+#[derive(Debug)]
+pub(crate) struct LocalBlockSource {
+    root: PathBuf,
+    cache: Arc<LocalBlocksCache>,
+}
+
+#[derive(Deserialize)]
+struct LocalBlockAndReceipts(String, BlockAndReceipts);
+
+/// Reader of an hourly file that keeps its position between reads, so that every line is read
+/// exactly once.
+#[derive(Debug)]
+struct HourFileTail {
+    path: PathBuf,
+    reader: Option<BufReader<File>>,
+    /// Identity of the open file, used to detect the file being replaced.
+    file_id: Option<u64>,
+    /// Byte offset after the last complete line.
+    offset: u64,
+    /// Trailing line that has not been fully written yet.
+    partial: Vec<u8>,
+}
+
+impl HourFileTail {
+    fn new(path: PathBuf) -> Self {
+        Self { path, reader: None, file_id: None, offset: 0, partial: Vec::new() }
+    }
+
+    /// Returns up to `max_lines` complete lines appended to the file since the last read.
+    ///
+    /// The file is read from the start again if it was truncated or replaced, e.g. by a
+    /// restarted hl-node.
+    fn read_lines(&mut self, max_lines: usize) -> std::io::Result<Vec<Vec<u8>>> {
+        let metadata = match std::fs::metadata(&self.path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+        let consumed = self.offset + self.partial.len() as u64;
+        let replaced = file_id(&metadata) != self.file_id;
+        if self.reader.is_some() && (metadata.len() < consumed || replaced) {
+            warn!(path = ?self.path, "Hour file was truncated or replaced, reading it again");
+            self.reader = None;
+        }
+
+        if self.reader.is_none() {
+            let file = File::open(&self.path)?;
+            self.file_id = file_id(&file.metadata()?);
+            self.offset = 0;
+            self.partial.clear();
+            self.reader = Some(BufReader::new(file));
+        }
+        let reader = self.reader.as_mut().expect("reader is opened above");
+
+        let mut lines = Vec::new();
+        while lines.len() < max_lines {
+            // Stops in the middle of a line if it is not fully written yet, the rest of it is
+            // appended by the next read.
+            let read = reader.read_until(b'\n', &mut self.partial)?;
+            if read == 0 || !self.partial.ends_with(b"\n") {
+                break;
+            }
+            self.offset += self.partial.len() as u64;
+            lines.push(std::mem::take(&mut self.partial));
+        }
+        Ok(lines)
+    }
+}
+
+#[cfg(unix)]
+fn file_id(metadata: &std::fs::Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.ino())
+}
+
+#[cfg(not(unix))]
+fn file_id(_metadata: &std::fs::Metadata) -> Option<u64> {
+    None
+}
+
+/// Parses the blocks at or above `start_height` from `lines` of the hourly file at `path`.
+fn parse_blocks(path: &Path, lines: Vec<Vec<u8>>, start_height: u64) -> Vec<BlockAndReceipts> {
+    lines
+        .into_iter()
+        .filter(|line| !line.trim_ascii().is_empty())
+        .filter_map(|line| match serde_json::from_slice(&line) {
+            Ok(LocalBlockAndReceipts(_block_timestamp, block)) => Some(block),
+            Err(err) => {
+                // hl-node previously terminated while writing the line
+                warn!(?path, %err, "Skipping undecodable block line");
+                None
+            }
+        })
+        // Blocks below the start height have already been imported
+        .filter(|block| block.number() >= start_height)
+        .collect()
+}
+
+/// Watches `dir` and its subdirectories, notifying `wakeup` on every change.
+///
+/// Returns `None` if the directory cannot be watched, in which case the files are polled.
+fn watch_dir(dir: &Path, wakeup: Arc<Notify>) -> Option<RecommendedWatcher> {
+    let watcher = RecommendedWatcher::new(
+        move |event: notify::Result<notify::Event>| {
+            if event.is_ok() {
+                wakeup.notify_one();
+            }
+        },
+        notify::Config::default(),
+    );
+    let mut watcher = match watcher {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            warn!(%err, "Failed to create file watcher, polling hour files instead");
+            return None;
+        }
+    };
+    if let Err(err) = watcher.watch(dir, RecursiveMode::Recursive) {
+        warn!(?dir, %err, "Failed to watch hourly directory, polling hour files instead");
+        return None;
+    }
+    Some(watcher)
+}
+
+fn datetime_from_timestamp(ts_sec: u64) -> OffsetDateTime {
+    OffsetDateTime::from_unix_timestamp_nanos((ts_sec as i128) * 1_000 * 1_000_000)
+        .expect("timestamp out of range")
+}
+
+fn date_from_datetime(dt: OffsetDateTime) -> String {
+    dt.format(&format_description::parse("[year][month][day]").unwrap()).unwrap()
+}
+
+/// Returns the path of the hourly file starting at `dt`.
+fn hour_file_path(root: &Path, dt: OffsetDateTime) -> PathBuf {
+    root.join(HOURLY_SUBDIR).join(date_from_datetime(dt)).join(format!("{}", dt.hour()))
+}
+
+/// Caches up to `max_blocks` blocks appended to the file of `tail`, and advances `next_height`
+/// past them.
+///
+/// Returns `true` if the end of the file was reached.
+fn cache_new_blocks(
+    tail: &mut HourFileTail,
+    next_height: &mut u64,
+    cache: &LocalBlocksCache,
+    max_blocks: u64,
+) -> std::io::Result<bool> {
+    let max_lines = max_blocks.try_into().unwrap_or(usize::MAX);
+    let lines = tail.read_lines(max_lines)?;
+    let reached_end = lines.len() < max_lines;
+
+    // Blocks that have been imported from another source in the meantime are skipped
+    *next_height = (*next_height).max(cache.import_height());
+    let new_blocks = parse_blocks(&tail.path, lines, *next_height);
+    if let Some(highest) = new_blocks.iter().map(|blk| blk.number()).max() {
+        *next_height = (*next_height).max(highest + 1);
+    }
+    cache.insert(new_blocks);
+    Ok(reached_end)
+}
+
+/// Runs [`cache_new_blocks`] on the blocking pool, since reading the hourly file and decoding its
+/// blocks would stall the runtime.
+///
+/// The file is read from the start again if the blocking task panicked.
+async fn cache_new_blocks_blocking(
+    tail: &mut HourFileTail,
+    next_height: &mut u64,
+    cache: &Arc<LocalBlocksCache>,
+    max_blocks: u64,
+) -> std::io::Result<bool> {
+    let fresh_tail = HourFileTail::new(tail.path.clone());
+    let mut moved_tail = std::mem::replace(tail, fresh_tail);
+    let (mut height, cache) = (*next_height, cache.clone());
+    let (moved_tail, height, result) = tokio::task::spawn_blocking(move || {
+        let result = cache_new_blocks(&mut moved_tail, &mut height, &cache, max_blocks);
+        (moved_tail, height, result)
+    })
+    .await
+    .map_err(std::io::Error::other)?;
+    *tail = moved_tail;
+    *next_height = height;
+    result
+}
+
+impl LocalBlockSource {
+    /// Creates a source caching up to `cache_size` blocks ahead of the block being imported.
+    pub(crate) fn new(root: PathBuf, precompiles_cache: PrecompilesCache, cache_size: u64) -> Self {
+        Self { root, cache: Arc::new(LocalBlocksCache::new(precompiles_cache, cache_size)) }
+    }
+
+    /// Evicts the cached blocks and precompile calls up to the canonical `head`.
+    pub(crate) fn set_canonical_head(&self, head: u64) {
+        self.cache.set_canonical_head(head);
+    }
+
+    /// Spawns the task tailing the hourly files, starting at the file containing `current_ts`.
+    ///
+    /// The task wakes up on filesystem notifications for the hourly directory, and falls back to
+    /// polling if the directory cannot be watched. It stops reading while the cache is full.
+    pub(crate) fn start_tailing(&self, current_head: u64, current_ts: u64) {
+        let root = self.root.clone();
+        let cache = self.cache.clone();
+        cache.advance(current_head);
+
+        tokio::spawn(async move {
+            let wakeup = Arc::new(Notify::new());
+            let watcher = watch_dir(&root.join(HOURLY_SUBDIR), wakeup.clone());
+            let poll_interval =
+                if watcher.is_some() { WATCHED_TAIL_INTERVAL } else { TAIL_INTERVAL };
+
+            let mut next_height = current_head;
+            let mut dt = datetime_from_timestamp(current_ts)
+                .replace_minute(0)
+                .unwrap()
+                .replace_second(0)
+                .unwrap()
+                .replace_nanosecond(0)
+                .unwrap();
+            let mut tail = HourFileTail::new(hour_file_path(&root, dt));
+
+            loop {
+                let room = cache.room(next_height);
+                if room == 0 {
+                    // Wait for the import to catch up before reading further
+                    cache.metrics.backpressure_waits.increment(1);
+                    let _ = tokio::time::timeout(WATCHED_TAIL_INTERVAL, cache.advanced.notified())
+                        .await;
+                    continue;
+                }
+
+                let reached_end = match cache_new_blocks_blocking(
+                    &mut tail,
+                    &mut next_height,
+                    &cache,
+                    room,
+                )
+                .await
+                {
+                    Ok(reached_end) => reached_end,
+                    Err(err) => {
+                        warn!(path = ?tail.path, %err, "Failed to read hour file");
+                        tokio::time::sleep(SCAN_RETRY_INTERVAL).await;
+                        continue;
+                    }
+                };
+                if !reached_end {
+                    continue;
+                }
+
+                // Move on to the next hourly file once hl-node has started writing it, or once
+                // the hour has been over for a while, e.g. because hl-node was not running.
+                let next_dt = dt + Duration::HOUR;
+                let next_file = hour_file_path(&root, next_dt);
+                let now = OffsetDateTime::now_utc();
+                let next_file_exists = tokio::fs::try_exists(&next_file).await.unwrap_or(false);
+                if next_dt < now && (next_file_exists || next_dt + ROTATION_GRACE_PERIOD < now) {
+                    // Lines written to the current file before the next one was created
+                    let room = cache.room(next_height);
+                    if !matches!(
+                        cache_new_blocks_blocking(&mut tail, &mut next_height, &cache, room).await,
+                        Ok(true)
+                    ) {
+                        continue;
+                    }
+
+                    dt = next_dt;
+                    tail = HourFileTail::new(next_file);
+                    info!("Moving to a new file. {:?}", tail.path);
+                    continue;
+                }
+
+                tokio::select! {
+                    _ = wakeup.notified() => {}
+                    _ = tokio::time::sleep(poll_interval) => {}
+                }
+            }
+        });
+    }
+}
+
+impl BlockSource for LocalBlockSource {
+    fn name(&self) -> &str {
+        "local"
+    }
+
+    fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError> {
+        Ok(self.cache.get(height))
+    }
+
+    fn find_latest_block_number(&self) -> Option<u64> {
+        self.cache.blocks.lock().last_key_value().map(|(height, _)| *height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_hyperliquid_types::test_utils::block;
+    use std::io::Write;
+
+    #[test]
+    fn cache_is_bounded_by_import_height() {
+        let cache = LocalBlocksCache::new(Default::default(), 4);
+        cache.advance(10);
+        assert_eq!(cache.room(10), 4);
+
+        cache.insert((8..14).map(block).collect());
+        // Blocks below the import height are not cached
+        assert_eq!(cache.blocks.lock().keys().copied().collect::<Vec<_>>(), vec![10, 11, 12, 13]);
+        assert_eq!(cache.room(14), 0);
+
+        assert_eq!(cache.get(10).map(|block| block.number()), Some(10));
+        assert_eq!(cache.room(14), 0);
+        // Importing block 12 from another source evicts block 11
+        assert!(cache.get(12).is_some());
+        assert_eq!(cache.blocks.lock().keys().copied().collect::<Vec<_>>(), vec![12, 13]);
+        assert_eq!(cache.room(14), 2);
+    }
+
+    #[test]
+    fn collected_blocks_stay_cached_until_imported() {
+        let source = LocalBlockSource::new(PathBuf::new(), Default::default(), 4);
+        source.cache.advance(1);
+        source.cache.insert((1..=2).map(block).collect());
+
+        // The import of block 1 is retried, e.g. after the engine failed
+        assert_eq!(source.collect_block(1).unwrap().map(|block| block.number()), Some(1));
+        assert_eq!(source.collect_block(1).unwrap().map(|block| block.number()), Some(1));
+
+        source.set_canonical_head(1);
+        assert!(source.collect_block(1).unwrap().is_none());
+        assert_eq!(source.collect_block(2).unwrap().map(|block| block.number()), Some(2));
+    }
+
+    #[test]
+    fn canonical_head_evicts_precompiles() {
+        let cache = LocalBlocksCache::new(Default::default(), 8);
+        cache.advance(1);
+        cache.insert((1..=4).map(block).collect());
+        assert_eq!(cache.precompiles.lock().len(), 4);
+
+        cache.set_canonical_head(2);
+        assert_eq!(cache.precompiles.lock().keys().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(cache.blocks.lock().keys().copied().collect::<Vec<_>>(), vec![3, 4]);
+        assert_eq!(cache.import_height(), 3);
+    }
+
+    #[test]
+    fn tail_reads_at_most_max_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0");
+        let mut tail = HourFileTail::new(path.clone());
+
+        append(&path, b"first\nsecond\nthird\n");
+        assert_eq!(tail.read_lines(2).unwrap().len(), 2);
+        assert_eq!(tail.read_lines(2).unwrap(), vec![b"third\n".to_vec()]);
+    }
+
+    fn append(path: &Path, data: &[u8]) {
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+        file.write_all(data).unwrap();
+    }
+
+    #[test]
+    fn tail_reads_complete_lines_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0");
+        let mut tail = HourFileTail::new(path.clone());
+
+        // Missing files are created by hl-node later on
+        assert!(tail.read_lines(usize::MAX).unwrap().is_empty());
+
+        append(&path, b"first\nsec");
+        assert_eq!(tail.read_lines(usize::MAX).unwrap(), vec![b"first\n".to_vec()]);
+        assert!(tail.read_lines(usize::MAX).unwrap().is_empty());
+
+        append(&path, b"ond\nthird\n");
+        assert_eq!(
+            tail.read_lines(usize::MAX).unwrap(),
+            vec![b"second\n".to_vec(), b"third\n".to_vec()]
+        );
+        assert!(tail.read_lines(usize::MAX).unwrap().is_empty());
+        assert_eq!(tail.offset, 19);
+    }
+
+    #[test]
+    fn tail_rereads_truncated_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0");
+        let mut tail = HourFileTail::new(path.clone());
+
+        append(&path, b"first\nsecond\n");
+        assert_eq!(tail.read_lines(usize::MAX).unwrap().len(), 2);
+
+        // A restarted hl-node rewrites the file
+        std::fs::write(&path, b"first\n").unwrap();
+        assert_eq!(tail.read_lines(usize::MAX).unwrap(), vec![b"first\n".to_vec()]);
+        append(&path, b"second\n");
+        assert_eq!(tail.read_lines(usize::MAX).unwrap(), vec![b"second\n".to_vec()]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn tail_rereads_replaced_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0");
+        let mut tail = HourFileTail::new(path.clone());
+
+        append(&path, b"first\n");
+        assert_eq!(tail.read_lines(usize::MAX).unwrap().len(), 1);
+
+        let replacement = dir.path().join("replacement");
+        append(&replacement, b"first\nsecond\n");
+        std::fs::rename(&replacement, &path).unwrap();
+        assert_eq!(
+            tail.read_lines(usize::MAX).unwrap(),
+            vec![b"first\n".to_vec(), b"second\n".to_vec()]
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_blocks_read_on_blocking_pool() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("0");
+        let line = serde_json::to_vec(&("2025-01-01T00:00:00", block(1))).unwrap();
+        append(&path, &[line.as_slice(), b"\n"].concat());
+
+        let cache = Arc::new(LocalBlocksCache::new(Default::default(), 4));
+        let (mut tail, mut next_height) = (HourFileTail::new(path), 1);
+        assert!(cache_new_blocks_blocking(&mut tail, &mut next_height, &cache, 4).await.unwrap());
+        assert_eq!(next_height, 2);
+        assert_eq!(tail.offset, line.len() as u64 + 1);
+        assert!(cache.blocks.lock().contains_key(&1));
+    }
+
+    #[test]
+    fn parse_skips_undecodable_lines() {
+        let path = Path::new("0");
+        let lines = vec![b"not a block\n".to_vec(), b"\n".to_vec()];
+        assert!(parse_blocks(path, lines, 0).is_empty());
+    }
+}
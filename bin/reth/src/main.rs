@@ -5,34 +5,67 @@ static ALLOC: reth_cli_util::allocator::Allocator = reth_cli_util::allocator::ne
 
 mod block_ingest;
 mod call_forwarder;
-mod serialized;
-mod spot_meta;
+mod consistency_auditor;
+mod core_writer_actions;
+mod forwarded_txs;
+mod hl_node_compliance;
+mod index_page;
+mod ingest_status;
+mod local_block_source;
+mod precompile_calls;
+mod receipt_verifier;
+mod s3_http_source;
+mod system_transfers;
 mod tx_forwarder;
 
-use std::{collections::BTreeMap, sync::Arc};
+use std::{collections::BTreeMap, net::SocketAddr, path::PathBuf, sync::Arc};
 
 use block_ingest::BlockIngest;
 use call_forwarder::CallForwarderApiServer;
 use clap::{Args, Parser};
+use core_writer_actions::{HlCoreWriterApiServer, HlCoreWriterExt};
+use eyre::Context;
+use hl_node_compliance::{hl_node_compliant_module, start_hl_node_compliant_server};
+use ingest_status::{HlIngestApiServer, HlIngestExt, IngestStatusHandle};
+use local_block_source::LocalBlockSource;
+use precompile_calls::{HlPrecompileApiServer, HlPrecompileExt};
+use receipt_verifier::ReceiptVerificationArgs;
 use reth::cli::Cli;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
-use reth_hyperliquid_types::PrecompilesCache;
+use reth_hyperliquid_types::{
+    read_spot_meta_snapshot, set_default_hl_node_compliance, BlockSourceBoxed,
+    MissingPrecompileDataPolicy, PrecompilesCache, S3BlockSource,
+};
+use reth_node_builder::{DatabasePrecompileDataStore, DatabaseSpotTokenStore};
 use reth_node_ethereum::EthereumNode;
-use tokio::sync::Mutex;
-use tracing::info;
-use tx_forwarder::EthForwarderApiServer;
+use reth_rpc_eth_api::EthPubSubApiServer;
+use s3_http_source::{S3HttpBlockSource, S3SyncArgs};
+use system_transfers::{HlSystemTransfersApiServer, HlSystemTransfersExt};
+use tracing::{error, info, warn};
+use tx_forwarder::{EthForwarderApiServer, EthForwarderExt, TxForwarderArgs};
 
 #[derive(Args, Debug, Clone)]
 struct HyperliquidExtArgs {
-    /// Upstream RPC URL to forward incoming transactions.
-    #[arg(long, default_value = "https://rpc.hyperliquid.xyz/evm")]
-    pub upstream_rpc_url: String,
-
     /// Forward eth_call and eth_estimateGas to the upstream RPC.
     #[arg(long)]
     pub forward_call: bool,
 
-    /// Enable hl-node compliant mode.
+    /// What calls do when they reach a read precompile of a block whose read precompile calls are
+    /// not available, e.g. `pending` or the latest block before its file is available.
+    ///
+    /// `error` fails the call with error code -32002, `revert` fails the read precompile call,
+    /// and `forward` forwards `eth_call` and `eth_estimateGas` to the upstream RPC.
+    #[arg(long, value_name = "POLICY", default_value_t = MissingPrecompileDataPolicy::Error)]
+    pub precompile_data_unavailable: MissingPrecompileDataPolicy,
+
+    /// Fail block execution on read precompile calls that hl-node did not record for the block,
+    /// instead of failing only the precompile call.
+    ///
+    /// Such calls almost always mean that the execution diverges from hl-node.
+    #[arg(long)]
+    pub strict_precompile_replay: bool,
+
+    /// Enable hl-node compliant mode on the regular RPC servers.
     ///
     /// This option
     /// 1. filters out system transactions from block transaction list.
@@ -40,6 +73,46 @@ struct HyperliquidExtArgs {
     /// 3. filters out logs and transactions from subscription.
     #[arg(long, default_value = "false")]
     pub hl_node_compliant: bool,
+
+    /// Serve the RPC modules of `--http.api` in hl-node compliant mode over http and ws on this
+    /// port of `--http.addr`, next to the regular servers.
+    #[arg(long, value_name = "PORT")]
+    pub hl_node_compliant_port: Option<u16>,
+
+    /// Forwarding transactions to the upstream RPCs.
+    #[command(flatten)]
+    pub tx_forwarder: TxForwarderArgs,
+
+    /// Fetching blocks over the S3 REST API.
+    #[command(flatten)]
+    pub s3: S3SyncArgs,
+
+    /// Verifying executed receipts against the ingested ones.
+    #[command(flatten)]
+    pub receipt_verification: ReceiptVerificationArgs,
+
+    /// Maximum number of blocks read ahead from `--local-ingest-dir` before they are imported.
+    #[arg(long, default_value_t = 1024)]
+    pub local_ingest_cache_size: u64,
+
+    /// Directory undecodable block files of the ingest directory are moved to.
+    ///
+    /// Defaults to `<DIR>/<CHAIN_ID>/quarantine` of the data directory.
+    #[arg(long, value_name = "DIR")]
+    pub quarantine_dir: Option<PathBuf>,
+
+    /// Compare the blocks of `--local-ingest-dir` with the archive of `--ingest-dir` or the S3
+    /// bucket, and report the blocks that differ.
+    #[arg(long)]
+    pub audit_local_blocks: bool,
+
+    /// Snapshot of the spot tokens, i.e. a saved response of the `spotMeta` info request of the
+    /// Hyperliquid API, written to the database on launch.
+    ///
+    /// Only needed when the database has no blocks yet. Spot tokens missing from the database are
+    /// otherwise learned from the system transactions of the imported blocks.
+    #[arg(long, value_name = "FILE")]
+    pub spot_meta_snapshot: Option<PathBuf>,
 }
 
 fn main() {
@@ -51,25 +124,67 @@ fn main() {
     }
 
     let precompiles_cache = PrecompilesCache::new(parking_lot::Mutex::new(BTreeMap::new()));
-    let local_blocks_cache = Arc::new(Mutex::new(BTreeMap::new()));
 
     if let Err(err) = Cli::<EthereumChainSpecParser, HyperliquidExtArgs>::parse().run(
         |builder, ext_args| async move {
             if ext_args.hl_node_compliant {
                 info!(target: "reth::cli", "hl-node compliant mode enabled");
-                std::env::set_var("HL_NODE_COMPLIANT", "true");
+                set_default_hl_node_compliance(true);
             }
+            let hl_node_compliant_addr = ext_args
+                .hl_node_compliant_port
+                .map(|port| SocketAddr::new(builder.config().rpc.http_addr, port));
+            let hl_node_compliant_module = Arc::new(parking_lot::Mutex::new(None));
+            let rpc_hl_node_compliant_module = hl_node_compliant_module.clone();
 
-            let ingest_dir = builder.config().ingest_dir.clone().expect("ingest dir not set");
+            // Blocks of the archive are read from, or with `--s3.enable` cached into, this
+            // directory.
+            let Some(ingest_dir) = builder.config().ingest_dir.clone() else {
+                eyre::bail!("no block directory configured, set --ingest-dir");
+            };
             let local_ingest_dir = builder.config().local_ingest_dir.clone();
+            let quarantine_dir = ext_args
+                .quarantine_dir
+                .clone()
+                .unwrap_or_else(|| builder.config().datadir().quarantine());
+            let s3_http_source = if ext_args.s3.enable {
+                let source = S3HttpBlockSource::new(&ext_args.s3, ingest_dir.clone())?
+                    .with_quarantine_dir(quarantine_dir.clone());
+                Some(Arc::new(source))
+            } else {
+                None
+            };
+            let archive: BlockSourceBoxed = match &s3_http_source {
+                Some(s3_http_source) => s3_http_source.clone(),
+                None => {
+                    Arc::new(S3BlockSource::new(ingest_dir).with_quarantine_dir(quarantine_dir))
+                }
+            };
+            let rpc_archive = archive.clone();
+            let ingest_status = IngestStatusHandle::default();
+            let rpc_ingest_status = ingest_status.clone();
+            let spot_token_snapshot = match &ext_args.spot_meta_snapshot {
+                Some(path) => read_spot_meta_snapshot(path).wrap_err_with(|| {
+                    format!("failed to read spot meta snapshot {}", path.display())
+                })?,
+                None => BTreeMap::new(),
+            };
             info!(target: "reth::cli", "Launching node");
             let handle = builder
                 .node(EthereumNode::default())
                 .add_precompiles_cache(precompiles_cache.clone())
+                .missing_precompile_data_policy(ext_args.precompile_data_unavailable)
+                .strict_precompile_replay(ext_args.strict_precompile_replay)
+                .spot_token_snapshot(spot_token_snapshot)
                 .extend_rpc_modules(move |ctx| {
-                    let upstream_rpc_url = ext_args.upstream_rpc_url;
+                    let upstream_rpc_url = ext_args.tx_forwarder.primary_upstream();
                     ctx.modules.replace_configured(
-                        tx_forwarder::EthForwarderExt::new(upstream_rpc_url.clone()).into_rpc(),
+                        EthForwarderExt::new(
+                            &ext_args.tx_forwarder,
+                            ctx.provider().clone(),
+                            ctx.pool().clone(),
+                        )?
+                        .into_rpc(),
                     )?;
 
                     if ext_args.forward_call {
@@ -77,17 +192,102 @@ fn main() {
                             call_forwarder::CallForwarderExt::new(upstream_rpc_url.clone())
                                 .into_rpc(),
                         )?;
+                    } else if ext_args.precompile_data_unavailable ==
+                        MissingPrecompileDataPolicy::Forward
+                    {
+                        ctx.modules.replace_configured(
+                            call_forwarder::CallFallbackExt::new(
+                                ctx.registry.eth_api().clone(),
+                                upstream_rpc_url.clone(),
+                            )
+                            .into_rpc(),
+                        )?;
                     }
 
                     info!("Transaction forwarder extension enabled");
+
+                    ctx.modules.merge_configured(HlIngestExt::new(rpc_ingest_status).into_rpc())?;
+                    ctx.modules.merge_configured(
+                        HlPrecompileExt::new(
+                            ctx.registry.eth_api().clone(),
+                            ctx.provider().clone(),
+                            Arc::new(DatabasePrecompileDataStore::new(ctx.provider().clone())),
+                            rpc_archive,
+                        )
+                        .into_rpc(),
+                    )?;
+                    ctx.modules.merge_configured(
+                        HlSystemTransfersExt::new(ctx.provider().clone()).into_rpc(),
+                    )?;
+                    ctx.modules.merge_configured(
+                        HlCoreWriterExt::new(ctx.provider().clone()).into_rpc(),
+                    )?;
+
+                    if hl_node_compliant_addr.is_some() {
+                        let Some(module) = ctx.modules.http_module().or(ctx.modules.ws_module())
+                        else {
+                            eyre::bail!("--hl-node-compliant-port requires --http or --ws");
+                        };
+                        let pubsub = ctx
+                            .registry
+                            .eth_handlers()
+                            .pubsub
+                            .clone()
+                            .with_hl_node_compliance(true);
+                        *rpc_hl_node_compliant_module.lock() =
+                            Some(hl_node_compliant_module(module, pubsub.into_rpc())?);
+                    }
                     Ok(())
                 })
                 .launch()
                 .await?;
 
-            let ingest =
-                BlockIngest { ingest_dir, local_ingest_dir, local_blocks_cache, precompiles_cache };
-            ingest.run(handle.node).await.unwrap();
+            let _hl_node_compliant_server =
+                match (hl_node_compliant_addr, hl_node_compliant_module.lock().take()) {
+                    (Some(addr), Some(module)) => {
+                        Some(start_hl_node_compliant_server(addr, module).await?)
+                    }
+                    _ => None,
+                };
+
+            // Blocks are imported from the archive, and from the local hl-node output once the
+            // archive has no more blocks.
+            let local_source = local_ingest_dir.map(|dir| {
+                Arc::new(LocalBlockSource::new(
+                    dir,
+                    precompiles_cache.clone(),
+                    ext_args.local_ingest_cache_size,
+                ))
+            });
+            let mut live_sources: Vec<BlockSourceBoxed> = vec![];
+            if let Some(local_source) = &local_source {
+                live_sources.push(local_source.clone());
+            }
+            if ext_args.audit_local_blocks && local_source.is_none() {
+                warn!(
+                    target: "reth::cli",
+                    "--audit-local-blocks has no effect without --local-ingest-dir"
+                );
+            }
+
+            let ingest = BlockIngest {
+                archive,
+                live_sources,
+                local_source,
+                s3_http_source,
+                audit_live_blocks: ext_args.audit_local_blocks,
+                precompile_store: Arc::new(DatabasePrecompileDataStore::new(
+                    handle.node.provider.clone(),
+                )),
+                spot_token_store: Arc::new(DatabaseSpotTokenStore::new(
+                    handle.node.provider.clone(),
+                )),
+                status: ingest_status,
+                receipt_verification: ext_args.receipt_verification,
+            };
+            if let Err(err) = ingest.run(&handle.node).await {
+                error!(target: "reth::cli", %err, "Block ingestion stopped");
+            }
             handle.node_exit_future.await
         },
     ) {
@@ -0,0 +1,265 @@
+//! `hl_getPrecompileCalls` RPC, which exposes the read precompile calls hl-node recorded for a
+//! block.
+
+use std::collections::HashMap;
+
+use alloy_eips::BlockId;
+use alloy_primitives::{Address, Bytes, B256};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee_core::{async_trait, RpcResult};
+use reth_evm_ethereum::{ReadPrecompileCall, ReadPrecompileCallCollector};
+use reth_hyperliquid_types::{
+    BlockSourceBoxed, PrecompileData, PrecompileDataStoreBoxed, ReadPrecompileInput,
+    ReadPrecompileResult,
+};
+use reth_provider::BlockIdReader;
+use reth_rpc_eth_api::helpers::{Call, LoadPendingBlock, LoadTransaction, Trace};
+use reth_rpc_server_types::result::internal_rpc_err;
+use serde::Serialize;
+
+/// Result of a read precompile call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) enum PrecompileCallStatus {
+    Ok,
+    OutOfGas,
+    Error,
+    /// hl-node failed the call with an unexpected error.
+    UnexpectedError,
+    /// The call was made during execution but hl-node did not record it for the block.
+    NotRecorded,
+}
+
+/// A read precompile call with its recorded result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PrecompileCall {
+    pub address: Address,
+    pub input: Bytes,
+    pub gas_limit: u64,
+    pub status: PrecompileCallStatus,
+    /// Gas used by the call, all of the gas limit unless the call succeeded.
+    pub gas_used: u64,
+    /// Output of the call, if it succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+}
+
+impl PrecompileCall {
+    fn new(
+        address: Address,
+        input: &ReadPrecompileInput,
+        result: Option<&ReadPrecompileResult>,
+    ) -> Self {
+        let (status, gas_used, output) = match result {
+            Some(ReadPrecompileResult::Ok { gas_used, bytes }) => {
+                (PrecompileCallStatus::Ok, *gas_used, Some(bytes.clone()))
+            }
+            Some(ReadPrecompileResult::OutOfGas) => {
+                (PrecompileCallStatus::OutOfGas, input.gas_limit, None)
+            }
+            Some(ReadPrecompileResult::Error) => {
+                (PrecompileCallStatus::Error, input.gas_limit, None)
+            }
+            Some(ReadPrecompileResult::UnexpectedError) => {
+                (PrecompileCallStatus::UnexpectedError, input.gas_limit, None)
+            }
+            None => (PrecompileCallStatus::NotRecorded, input.gas_limit, None),
+        };
+        Self {
+            address,
+            input: input.input.clone(),
+            gas_limit: input.gas_limit,
+            status,
+            gas_used,
+            output,
+        }
+    }
+}
+
+/// Read precompile calls recorded for a block.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BlockPrecompileCalls {
+    pub block_number: u64,
+    /// Recorded calls, grouped by precompile.
+    pub calls: Vec<PrecompileCall>,
+}
+
+impl BlockPrecompileCalls {
+    fn new(block_number: u64, data: &PrecompileData) -> Self {
+        let calls = data
+            .precompiles
+            .iter()
+            .flat_map(|(address, calls)| {
+                calls
+                    .iter()
+                    .map(|(input, result)| PrecompileCall::new(*address, input, Some(result)))
+            })
+            .collect();
+        Self { block_number, calls }
+    }
+}
+
+/// Read precompile calls made by a transaction.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TransactionPrecompileCalls {
+    pub block_number: u64,
+    pub transaction_hash: B256,
+    pub transaction_index: u64,
+    /// Calls in the order they were made, with the result recorded for the block.
+    pub calls: Vec<PrecompileCall>,
+}
+
+/// Matches the calls made by a transaction with the calls recorded for its block.
+fn match_recorded_calls(
+    calls: &[ReadPrecompileCall],
+    data: &PrecompileData,
+) -> Vec<PrecompileCall> {
+    let recorded: HashMap<_, _> = data
+        .precompiles
+        .iter()
+        .flat_map(|(address, calls)| {
+            calls.iter().map(move |(input, result)| ((*address, input), result))
+        })
+        .collect();
+    calls
+        .iter()
+        .map(|call| {
+            let result = recorded.get(&(call.address, &call.input)).copied();
+            PrecompileCall::new(call.address, &call.input, result)
+        })
+        .collect()
+}
+
+#[rpc(server, namespace = "hl")]
+pub(crate) trait HlPrecompileApi {
+    /// Returns the read precompile calls hl-node recorded for a block.
+    #[method(name = "getPrecompileCalls")]
+    async fn precompile_calls(&self, block: BlockId) -> RpcResult<Option<BlockPrecompileCalls>>;
+
+    /// Returns the read precompile calls made by a transaction, by re-executing it.
+    #[method(name = "getTransactionPrecompileCalls")]
+    async fn transaction_precompile_calls(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<TransactionPrecompileCalls>>;
+}
+
+pub(crate) struct HlPrecompileExt<Eth, Provider> {
+    eth_api: Eth,
+    provider: Provider,
+    /// Read precompile calls of the imported blocks.
+    store: PrecompileDataStoreBoxed,
+    /// Source of the read precompile calls of blocks imported before they were stored.
+    archive: BlockSourceBoxed,
+}
+
+impl<Eth, Provider> HlPrecompileExt<Eth, Provider> {
+    pub(crate) fn new(
+        eth_api: Eth,
+        provider: Provider,
+        store: PrecompileDataStoreBoxed,
+        archive: BlockSourceBoxed,
+    ) -> Self {
+        Self { eth_api, provider, store, archive }
+    }
+
+    async fn precompile_data(&self, height: u64) -> RpcResult<Option<PrecompileData>> {
+        let (store, archive) = (self.store.clone(), self.archive.clone());
+        tokio::task::spawn_blocking(move || -> eyre::Result<Option<PrecompileData>> {
+            if let Some(data) = store.precompile_data(height)? {
+                return Ok(Some(data));
+            }
+            Ok(archive.collect_block(height)?.map(|block| block.precompile_data()))
+        })
+        .await
+        .map_err(|err| internal_rpc_err(err.to_string()))?
+        .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+#[async_trait]
+impl<Eth, Provider> HlPrecompileApiServer for HlPrecompileExt<Eth, Provider>
+where
+    Eth: Trace + LoadPendingBlock + LoadTransaction + Call + 'static,
+    Provider: BlockIdReader + 'static,
+{
+    async fn precompile_calls(&self, block: BlockId) -> RpcResult<Option<BlockPrecompileCalls>> {
+        let Some(block_number) = self
+            .provider
+            .block_number_for_id(block)
+            .map_err(|err| internal_rpc_err(err.to_string()))?
+        else {
+            return Ok(None);
+        };
+        let data = self.precompile_data(block_number).await?;
+        Ok(data.map(|data| BlockPrecompileCalls::new(block_number, &data)))
+    }
+
+    async fn transaction_precompile_calls(
+        &self,
+        hash: B256,
+    ) -> RpcResult<Option<TransactionPrecompileCalls>> {
+        let traced = self
+            .eth_api
+            .spawn_trace_transaction_in_block_with_inspector(
+                hash,
+                ReadPrecompileCallCollector::default(),
+                |tx_info, inspector, _, _| Ok((tx_info, inspector.into_calls())),
+            )
+            .await;
+        let (tx_info, calls) = match traced {
+            Ok(Some(traced)) => traced,
+            Ok(None) => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+
+        let block_number = tx_info.block_number.unwrap_or_default();
+        let data = self.precompile_data(block_number).await?.unwrap_or_default();
+        Ok(Some(TransactionPrecompileCalls {
+            block_number,
+            transaction_hash: hash,
+            transaction_index: tx_info.index.unwrap_or_default(),
+            calls: match_recorded_calls(&calls, &data),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_calls_with_recorded_results() {
+        let address = Address::with_last_byte(0x01);
+        let recorded = ReadPrecompileInput { input: Bytes::from_static(&[1]), gas_limit: 100 };
+        let unrecorded = ReadPrecompileInput { input: Bytes::from_static(&[2]), gas_limit: 100 };
+        let data = PrecompileData {
+            precompiles: vec![(
+                address,
+                vec![(
+                    recorded.clone(),
+                    ReadPrecompileResult::Ok { gas_used: 10, bytes: Bytes::from_static(&[3]) },
+                )],
+            )],
+            highest_precompile_address: None,
+        };
+
+        let calls = [
+            ReadPrecompileCall { address, input: recorded },
+            ReadPrecompileCall { address, input: unrecorded },
+        ];
+        let matched = match_recorded_calls(&calls, &data);
+        assert_eq!(matched[0].status, PrecompileCallStatus::Ok);
+        assert_eq!(matched[0].gas_used, 10);
+        assert_eq!(matched[0].output, Some(Bytes::from_static(&[3])));
+        assert_eq!(matched[1].status, PrecompileCallStatus::NotRecorded);
+        assert_eq!(matched[1].gas_used, 100);
+
+        let block = BlockPrecompileCalls::new(1, &data);
+        assert_eq!(block.calls.len(), 1);
+        assert_eq!(block.calls[0], matched[0]);
+    }
+}
@@ -0,0 +1,342 @@
+//! Verification of the receipts of executed blocks against the receipts produced by hl-node.
+//!
+//! Blocks are ingested together with the receipts hl-node produced for them. Comparing those
+//! with the receipts of our own execution surfaces a divergence, e.g. in precompile replay or
+//! state fixes, at the transaction that caused it instead of as a state root mismatch later on.
+
+use std::{
+    collections::VecDeque,
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    path::PathBuf,
+};
+
+use alloy_primitives::{Log, B256};
+use clap::{Args, ValueEnum};
+use reth_hyperliquid_types::{BlockAndReceipts, EvmBlock, IngestError, LegacyReceipt};
+use reth_primitives::Receipt;
+use reth_provider::ReceiptProvider;
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::{debug, warn};
+
+/// What to do when executed receipts diverge from the ingested ones.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub(crate) enum ReceiptVerificationMode {
+    /// Receipts are not verified.
+    #[default]
+    Off,
+    /// Stop ingesting blocks at the first divergence.
+    Halt,
+    /// Record the divergence and keep ingesting blocks.
+    Record,
+}
+
+/// Arguments for verifying executed receipts against the ingested ones.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Receipt verification")]
+pub(crate) struct ReceiptVerificationArgs {
+    /// Compare the receipts of blocks imported through the engine API against the receipts
+    /// ingested along with them.
+    #[arg(long = "verify-receipts", value_enum, default_value_t = ReceiptVerificationMode::Off)]
+    pub mode: ReceiptVerificationMode,
+
+    /// File that divergence reports are appended to, one JSON object per line.
+    ///
+    /// Reports are only logged if unset.
+    #[arg(long = "verify-receipts.report", value_name = "FILE")]
+    pub report_path: Option<PathBuf>,
+}
+
+/// Difference of a single field between the ingested and the executed receipt.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct FieldDiff {
+    pub field: String,
+    pub expected: Value,
+    pub actual: Value,
+}
+
+impl FieldDiff {
+    fn new(field: impl Into<String>, expected: impl Serialize, actual: impl Serialize) -> Self {
+        Self { field: field.into(), expected: json!(expected), actual: json!(actual) }
+    }
+}
+
+/// Differences between the ingested and the executed receipt of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct TxDivergence {
+    /// Index of the transaction in the block, system transactions included.
+    pub tx_index: usize,
+    pub system_tx: bool,
+    pub diffs: Vec<FieldDiff>,
+}
+
+/// Report of a block whose executed receipts diverge from the ingested ones.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct DivergenceReport {
+    pub block_number: u64,
+    pub block_hash: B256,
+    /// Differences that do not belong to a single transaction, like the number of receipts.
+    pub diffs: Vec<FieldDiff>,
+    pub transactions: Vec<TxDivergence>,
+}
+
+/// Receipts hl-node produced for a block, system transactions first.
+#[derive(Debug)]
+struct ExpectedReceipts {
+    block_number: u64,
+    block_hash: B256,
+    system_tx_count: usize,
+    /// Receipts of system transactions are optional.
+    receipts: Vec<Option<LegacyReceipt>>,
+}
+
+impl From<&BlockAndReceipts> for ExpectedReceipts {
+    fn from(block: &BlockAndReceipts) -> Self {
+        let EvmBlock::Reth115(sealed) = &block.block;
+        let receipts = block
+            .system_txs
+            .iter()
+            .map(|tx| tx.receipt.clone())
+            .chain(block.receipts.iter().cloned().map(Some))
+            .collect();
+        Self {
+            block_number: block.number(),
+            block_hash: sealed.hash(),
+            system_tx_count: block.system_txs.len(),
+            receipts,
+        }
+    }
+}
+
+fn diff_logs(expected: &[Log], actual: &[Log]) -> Vec<FieldDiff> {
+    if expected.len() != actual.len() {
+        return vec![FieldDiff::new("logs.len", expected.len(), actual.len())]
+    }
+
+    let mut diffs = vec![];
+    for (index, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+        if expected.address != actual.address {
+            diffs.push(FieldDiff::new(
+                format!("logs[{index}].address"),
+                expected.address,
+                actual.address,
+            ));
+        }
+        if expected.topics() != actual.topics() {
+            diffs.push(FieldDiff::new(
+                format!("logs[{index}].topics"),
+                expected.topics(),
+                actual.topics(),
+            ));
+        }
+        if expected.data.data != actual.data.data {
+            diffs.push(FieldDiff::new(
+                format!("logs[{index}].data"),
+                &expected.data.data,
+                &actual.data.data,
+            ));
+        }
+    }
+    diffs
+}
+
+fn diff_receipt(expected: &LegacyReceipt, actual: &Receipt) -> Vec<FieldDiff> {
+    let mut diffs = vec![];
+    if expected.success != actual.success {
+        diffs.push(FieldDiff::new("success", expected.success, actual.success));
+    }
+    if expected.cumulative_gas_used != actual.cumulative_gas_used {
+        diffs.push(FieldDiff::new(
+            "cumulative_gas_used",
+            expected.cumulative_gas_used,
+            actual.cumulative_gas_used,
+        ));
+    }
+    diffs.extend(diff_logs(&expected.logs, &actual.logs));
+    diffs
+}
+
+/// Compares the executed receipts of a block with the ingested ones.
+///
+/// Returns `None` if they match.
+fn compare_receipts(expected: &ExpectedReceipts, actual: &[Receipt]) -> Option<DivergenceReport> {
+    let mut report = DivergenceReport {
+        block_number: expected.block_number,
+        block_hash: expected.block_hash,
+        diffs: vec![],
+        transactions: vec![],
+    };
+
+    if expected.receipts.len() != actual.len() {
+        report.diffs.push(FieldDiff::new("receipts.len", expected.receipts.len(), actual.len()));
+    }
+
+    for (tx_index, (expected_receipt, actual)) in expected.receipts.iter().zip(actual).enumerate() {
+        let Some(expected_receipt) = expected_receipt else { continue };
+        let diffs = diff_receipt(expected_receipt, actual);
+        if !diffs.is_empty() {
+            report.transactions.push(TxDivergence {
+                tx_index,
+                system_tx: tx_index < expected.system_tx_count,
+                diffs,
+            });
+        }
+    }
+
+    (!report.diffs.is_empty() || !report.transactions.is_empty()).then_some(report)
+}
+
+/// Verifies the receipts of ingested blocks once they are canonical.
+#[derive(Debug)]
+pub(crate) struct ReceiptVerifier {
+    mode: ReceiptVerificationMode,
+    report: Option<BufWriter<File>>,
+    /// Blocks that have been submitted but not verified yet, in ascending order.
+    pending: VecDeque<ExpectedReceipts>,
+}
+
+impl ReceiptVerifier {
+    /// Returns the verifier configured by `args`, or `None` if verification is disabled.
+    pub(crate) fn new(args: &ReceiptVerificationArgs) -> eyre::Result<Option<Self>> {
+        if args.mode == ReceiptVerificationMode::Off {
+            return Ok(None)
+        }
+
+        let report = match &args.report_path {
+            Some(path) => {
+                Some(BufWriter::new(OpenOptions::new().create(true).append(true).open(path)?))
+            }
+            None => None,
+        };
+        Ok(Some(Self { mode: args.mode, report, pending: VecDeque::new() }))
+    }
+
+    /// Remembers the ingested receipts of a block that is about to be submitted.
+    ///
+    /// A block that is submitted again after a failed attempt replaces its previous entry.
+    pub(crate) fn expect_block(&mut self, block: &BlockAndReceipts) {
+        let expected = ExpectedReceipts::from(block);
+        if self.pending.back().is_some_and(|last| last.block_number == expected.block_number) {
+            self.pending.pop_back();
+        }
+        self.pending.push_back(expected);
+    }
+
+    /// Verifies the pending blocks whose receipts are available from `provider`.
+    ///
+    /// Returns an error on divergence if the node is configured to halt.
+    pub(crate) fn verify<P>(&mut self, provider: &P) -> Result<(), IngestError>
+    where
+        P: ReceiptProvider<Receipt = Receipt>,
+    {
+        while let Some(expected) = self.pending.front() {
+            let receipts =
+                provider.receipts_by_block(expected.block_hash.into()).map_err(|err| {
+                    IngestError::Database { height: expected.block_number, reason: err.to_string() }
+                })?;
+            let Some(receipts) = receipts else {
+                // Not canonical yet
+                break
+            };
+
+            if let Some(report) = compare_receipts(expected, &receipts) {
+                self.record(&report);
+                if self.mode == ReceiptVerificationMode::Halt {
+                    return Err(IngestError::ReceiptDivergence { height: report.block_number })
+                }
+            } else {
+                debug!(target: "reth::cli", block_number = expected.block_number, "Receipts verified");
+            }
+            self.pending.pop_front();
+        }
+
+        Ok(())
+    }
+
+    fn record(&mut self, report: &DivergenceReport) {
+        let json = serde_json::to_string(report).unwrap_or_default();
+        warn!(
+            target: "reth::cli",
+            block_number = report.block_number,
+            divergent_txs = report.transactions.len(),
+            report = %json,
+            "Executed receipts diverge from the ingested receipts"
+        );
+
+        if let Some(file) = &mut self.report {
+            if let Err(err) = writeln!(file, "{json}").and_then(|_| file.flush()) {
+                warn!(target: "reth::cli", %err, "Failed to write divergence report");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, b256, Bytes};
+    use reth_hyperliquid_types::LegacyTxType;
+    use reth_primitives::TxType;
+
+    fn log(data: &'static [u8]) -> Log {
+        Log::new_unchecked(
+            address!("0x2222222222222222222222222222222222222222"),
+            vec![b256!("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef")],
+            Bytes::from_static(data),
+        )
+    }
+
+    fn expected(receipts: Vec<Option<LegacyReceipt>>, system_tx_count: usize) -> ExpectedReceipts {
+        ExpectedReceipts { block_number: 1, block_hash: B256::ZERO, system_tx_count, receipts }
+    }
+
+    fn legacy_receipt(success: bool, cumulative_gas_used: u64, logs: Vec<Log>) -> LegacyReceipt {
+        LegacyReceipt { tx_type: LegacyTxType::Legacy, success, cumulative_gas_used, logs }
+    }
+
+    fn receipt(success: bool, cumulative_gas_used: u64, logs: Vec<Log>) -> Receipt {
+        Receipt { tx_type: TxType::Legacy, success, cumulative_gas_used, logs }
+    }
+
+    #[test]
+    fn matching_receipts() {
+        let expected = expected(vec![None, Some(legacy_receipt(true, 21_000, vec![log(b"a")]))], 1);
+        let actual = [receipt(true, 0, vec![]), receipt(true, 21_000, vec![log(b"a")])];
+        assert_eq!(compare_receipts(&expected, &actual), None);
+    }
+
+    #[test]
+    fn field_level_diff() {
+        let expected = expected(
+            vec![
+                Some(legacy_receipt(true, 0, vec![])),
+                Some(legacy_receipt(true, 21_000, vec![log(b"a")])),
+            ],
+            1,
+        );
+        let actual = [receipt(true, 0, vec![]), receipt(false, 25_000, vec![log(b"b")])];
+
+        let report = compare_receipts(&expected, &actual).unwrap();
+        assert!(report.diffs.is_empty());
+        assert_eq!(report.transactions.len(), 1);
+
+        let divergence = &report.transactions[0];
+        assert_eq!(divergence.tx_index, 1);
+        assert!(!divergence.system_tx);
+        let fields = divergence.diffs.iter().map(|diff| diff.field.as_str()).collect::<Vec<_>>();
+        assert_eq!(fields, ["success", "cumulative_gas_used", "logs[0].data"]);
+        assert_eq!(divergence.diffs[1].expected, json!(21_000));
+        assert_eq!(divergence.diffs[1].actual, json!(25_000));
+    }
+
+    #[test]
+    fn receipt_count_diff() {
+        let expected = expected(vec![Some(legacy_receipt(true, 21_000, vec![]))], 0);
+        let actual = [receipt(true, 21_000, vec![]), receipt(true, 42_000, vec![])];
+
+        let report = compare_receipts(&expected, &actual).unwrap();
+        assert_eq!(report.diffs, vec![FieldDiff::new("receipts.len", 1, 2)]);
+        assert!(report.transactions.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! `hl_getSystemTransfers` RPC, which exposes the transfers of HYPE and spot tokens from HyperCore
+//! to the EVM, indexed by recipient.
+
+use alloy_eips::BlockNumberOrTag;
+use alloy_primitives::{Address, Bytes, B256, U256};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee_core::{async_trait, RpcResult};
+use reth_db_api::{
+    models::{StoredSystemTransfer, SystemTransferKey},
+    table::Encode,
+    tables,
+    transaction::DbTx,
+};
+use reth_provider::{BlockIdReader, DBProvider, DatabaseProviderFactory};
+use reth_rpc_server_types::result::internal_rpc_err;
+use serde::Serialize;
+
+use crate::index_page::{block_range, decode_cursor, read_page};
+
+/// Maximum number of transfers returned by one call.
+const MAX_SYSTEM_TRANSFERS: usize = 1_000;
+
+/// A transfer of HYPE or of a spot token from HyperCore to the EVM.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SystemTransfer {
+    /// ERC20 contract of the spot token, or `0x2222…2222` for HYPE.
+    pub token: Address,
+    pub amount: U256,
+    pub block_number: u64,
+    pub timestamp: u64,
+    pub transaction_hash: B256,
+    pub transaction_index: u64,
+}
+
+impl SystemTransfer {
+    fn new(key: SystemTransferKey, transfer: StoredSystemTransfer) -> Self {
+        Self {
+            token: key.token,
+            amount: transfer.amount,
+            block_number: key.block_number,
+            timestamp: transfer.timestamp,
+            transaction_hash: transfer.tx_hash,
+            transaction_index: key.tx_index,
+        }
+    }
+}
+
+/// A page of the system transfers received by an address.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SystemTransfers {
+    /// Transfers ordered by token, then by block.
+    pub transfers: Vec<SystemTransfer>,
+    /// Cursor to pass to get the next page, if there are more transfers.
+    pub cursor: Option<Bytes>,
+}
+
+#[rpc(server, namespace = "hl")]
+pub(crate) trait HlSystemTransfersApi {
+    /// Returns the system transfers received by `address` in the given block range.
+    ///
+    /// At most 1000 transfers are returned per call, the remaining ones are returned when calling
+    /// again with the returned cursor.
+    #[method(name = "getSystemTransfers")]
+    async fn system_transfers(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<Bytes>,
+    ) -> RpcResult<SystemTransfers>;
+}
+
+pub(crate) struct HlSystemTransfersExt<Provider> {
+    provider: Provider,
+}
+
+impl<Provider> HlSystemTransfersExt<Provider> {
+    pub(crate) const fn new(provider: Provider) -> Self {
+        Self { provider }
+    }
+}
+
+#[async_trait]
+impl<Provider> HlSystemTransfersApiServer for HlSystemTransfersExt<Provider>
+where
+    Provider: BlockIdReader + DatabaseProviderFactory + Clone + 'static,
+{
+    async fn system_transfers(
+        &self,
+        address: Address,
+        from_block: Option<BlockNumberOrTag>,
+        to_block: Option<BlockNumberOrTag>,
+        cursor: Option<Bytes>,
+    ) -> RpcResult<SystemTransfers> {
+        let range = block_range(&self.provider, from_block, to_block)?;
+        let in_query = move |key: &SystemTransferKey| key.recipient == address;
+        let start = match cursor {
+            Some(cursor) => decode_cursor(&cursor, in_query)?,
+            None => SystemTransferKey::new(address, Address::ZERO, *range.start(), 0),
+        };
+
+        let provider = self.provider.clone();
+        let (transfers, next) = tokio::task::spawn_blocking(move || -> eyre::Result<_> {
+            let provider = provider.database_provider_ro()?;
+            let mut cursor = provider.tx_ref().cursor_read::<tables::SystemTransfers>()?;
+            Ok(read_page(&mut cursor, start, range, MAX_SYSTEM_TRANSFERS, in_query)?)
+        })
+        .await
+        .map_err(|err| internal_rpc_err(err.to_string()))?
+        .map_err(|err| internal_rpc_err(err.to_string()))?;
+
+        Ok(SystemTransfers {
+            transfers: transfers
+                .into_iter()
+                .map(|(key, transfer)| SystemTransfer::new(key, transfer))
+                .collect(),
+            cursor: next.map(|key| key.encode().into()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_db::{init_db, mdbx::DatabaseArguments, Database};
+    use reth_db_api::{models::ClientVersion, transaction::DbTxMut};
+
+    #[test]
+    fn reads_transfers_in_range_by_page() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = init_db(dir.path(), DatabaseArguments::new(ClientVersion::default())).unwrap();
+
+        let recipient = Address::with_last_byte(1);
+        let (token_a, token_b) = (Address::with_last_byte(2), Address::with_last_byte(3));
+        let tx = db.tx_mut().unwrap();
+        for key in [
+            SystemTransferKey::new(recipient, token_a, 1, 0),
+            SystemTransferKey::new(recipient, token_a, 5, 0),
+            SystemTransferKey::new(recipient, token_a, 9, 0),
+            SystemTransferKey::new(recipient, token_b, 5, 1),
+            SystemTransferKey::new(Address::with_last_byte(4), token_a, 5, 0),
+        ] {
+            tx.put::<tables::SystemTransfers>(key, StoredSystemTransfer::default()).unwrap();
+        }
+        tx.commit().unwrap();
+
+        let tx = db.tx().unwrap();
+        let mut cursor = tx.cursor_read::<tables::SystemTransfers>().unwrap();
+        let start = SystemTransferKey::new(recipient, Address::ZERO, 2, 0);
+        let in_query = |key: &SystemTransferKey| key.recipient == recipient;
+        let (transfers, next) = read_page(&mut cursor, start, 2..=9, 1, in_query).unwrap();
+        assert_eq!(transfers[0].0, SystemTransferKey::new(recipient, token_a, 5, 0));
+        assert_eq!(next, Some(SystemTransferKey::new(recipient, token_a, 9, 0)));
+
+        let (transfers, next) = read_page(&mut cursor, next.unwrap(), 2..=8, 10, in_query).unwrap();
+        assert_eq!(
+            transfers.into_iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            vec![SystemTransferKey::new(recipient, token_b, 5, 1)]
+        );
+        assert_eq!(next, None);
+    }
+}
@@ -1,10 +1,99 @@
-use alloy_primitives::{Bytes, B256};
+//! `eth_sendRawTransaction` forwarding to the upstream RPCs.
+//!
+//! The node does not gossip transactions, so incoming transactions are forwarded to the first
+//! healthy upstream instead, after being checked against the local state so that obviously
+//! invalid transactions are rejected without a round trip.
+
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_consensus::Transaction as _;
+use alloy_primitives::{Bytes, B256, U256};
+use clap::Args;
+use eyre::Context;
 use jsonrpsee::{
     http_client::{HttpClient, HttpClientBuilder},
     proc_macros::rpc,
+    rpc_params,
     types::{error::INTERNAL_ERROR_CODE, ErrorObject},
 };
 use jsonrpsee_core::{async_trait, client::ClientT, ClientError, RpcResult};
+use reth_chainspec::{ChainSpec, EthChainSpec};
+use reth_evm_ethereum::revm_spec_by_timestamp_and_block_number;
+use reth_primitives::{Account, Recovered, TransactionSigned};
+use reth_primitives_traits::{transaction::signed::is_impersonated_tx, SignedTransaction};
+use reth_provider::{BlockNumReader, ChainSpecProvider, StateProviderFactory};
+use reth_revm::{interpreter::gas::calculate_initial_tx_gas, specification::hardfork::SpecId};
+use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
+use reth_transaction_pool::{PoolTransaction, TransactionPool};
+use tracing::{debug, info, warn};
+
+use crate::forwarded_txs::ForwardedTransactions;
+
+/// Default upstream RPC.
+const DEFAULT_UPSTREAM_RPC_URL: &str = "https://rpc.hyperliquid.xyz/evm";
+
+/// Delay before the first retry once all upstreams failed.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Arguments for forwarding transactions to the upstream RPCs.
+#[derive(Debug, Clone, Args)]
+#[command(next_help_heading = "Transaction forwarding")]
+pub(crate) struct TxForwarderArgs {
+    /// Upstream RPC URLs incoming transactions are forwarded to, in priority order.
+    ///
+    /// Transactions are sent to the first healthy upstream, and to the next one if it cannot be
+    /// reached. `eth_call` and `eth_estimateGas` are forwarded to the first one.
+    #[arg(
+        long = "upstream-rpc-url",
+        value_name = "URL",
+        value_delimiter = ',',
+        default_value = DEFAULT_UPSTREAM_RPC_URL
+    )]
+    pub upstream_rpc_urls: Vec<String>,
+
+    /// Timeout of a request to an upstream.
+    #[arg(
+        long = "upstream.timeout",
+        default_value = "10s",
+        value_parser = humantime::parse_duration
+    )]
+    pub timeout: Duration,
+
+    /// Number of times a transaction is sent again once all upstreams failed, with an exponential
+    /// backoff.
+    #[arg(long = "upstream.retries", default_value_t = 2)]
+    pub retries: u32,
+
+    /// Interval at which the upstreams are checked, by requesting their chain id.
+    #[arg(
+        long = "upstream.health-check-interval",
+        default_value = "15s",
+        value_parser = humantime::parse_duration
+    )]
+    pub health_check_interval: Duration,
+
+    /// How long a forwarded transaction is kept in the local transaction pool if it is not
+    /// included in a block.
+    #[arg(
+        long = "upstream.pending-lifetime",
+        default_value = "5m",
+        value_parser = humantime::parse_duration
+    )]
+    pub pending_lifetime: Duration,
+}
+
+impl TxForwarderArgs {
+    /// Returns the upstream that calls are forwarded to.
+    pub(crate) fn primary_upstream(&self) -> String {
+        self.upstream_rpc_urls.first().cloned().unwrap_or_else(|| DEFAULT_UPSTREAM_RPC_URL.into())
+    }
+}
 
 #[rpc(server, namespace = "eth")]
 pub(crate) trait EthForwarderApi {
@@ -12,33 +101,376 @@ pub(crate) trait EthForwarderApi {
     async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256>;
 }
 
-pub(crate) struct EthForwarderExt {
+/// An upstream RPC.
+#[derive(Debug)]
+struct Upstream {
+    url: String,
     client: HttpClient,
+    /// Whether the last request to the upstream succeeded.
+    healthy: AtomicBool,
+}
+
+impl Upstream {
+    /// Records the outcome of a request, and logs if it changes the health of the upstream.
+    fn set_healthy(&self, healthy: bool) {
+        if self.healthy.swap(healthy, Ordering::Relaxed) != healthy {
+            if healthy {
+                info!(target: "reth::hl", url = %self.url, "Upstream RPC is healthy again");
+            } else {
+                warn!(target: "reth::hl", url = %self.url, "Upstream RPC is unhealthy");
+            }
+        }
+    }
+}
+
+/// The upstream RPCs transactions are forwarded to, in priority order.
+#[derive(Debug, Clone)]
+struct Upstreams {
+    upstreams: Arc<Vec<Upstream>>,
+    retries: u32,
+}
+
+impl Upstreams {
+    fn new(urls: &[String], timeout: Duration, retries: u32) -> eyre::Result<Self> {
+        eyre::ensure!(!urls.is_empty(), "no upstream RPC configured");
+        let upstreams = urls
+            .iter()
+            .map(|url| {
+                let client = HttpClientBuilder::default()
+                    .request_timeout(timeout)
+                    .build(url)
+                    .wrap_err_with(|| format!("invalid upstream RPC URL {url}"))?;
+                Ok(Upstream { url: url.clone(), client, healthy: AtomicBool::new(true) })
+            })
+            .collect::<eyre::Result<_>>()?;
+        Ok(Self { upstreams: Arc::new(upstreams), retries })
+    }
+
+    /// Checks the upstreams every `interval`, by requesting their chain id.
+    ///
+    /// Upstreams of another chain are considered unhealthy.
+    fn start_health_checks(&self, interval: Duration, chain_id: u64) {
+        let upstreams = self.upstreams.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                for upstream in upstreams.iter() {
+                    let result: Result<U256, _> =
+                        upstream.client.request("eth_chainId", rpc_params![]).await;
+                    match result {
+                        Ok(id) if id == U256::from(chain_id) => upstream.set_healthy(true),
+                        Ok(id) => {
+                            warn!(
+                                target: "reth::hl",
+                                url = %upstream.url,
+                                %id,
+                                chain_id,
+                                "Upstream RPC is on another chain"
+                            );
+                            upstream.set_healthy(false);
+                        }
+                        Err(err) => {
+                            debug!(
+                                target: "reth::hl",
+                                url = %upstream.url,
+                                %err,
+                                "Health check of upstream RPC failed"
+                            );
+                            upstream.set_healthy(false);
+                        }
+                    }
+                }
+            }
+        });
+    }
+
+    /// Sends the raw transaction `tx` with hash `hash` to the healthy upstreams first, then to the
+    /// unhealthy ones, until one of them answers.
+    ///
+    /// Once all upstreams failed, this is retried up to `retries` times with an exponential
+    /// backoff. An error returned by an upstream is returned as is, unless it reports the
+    /// transaction as known after a failed attempt, which may have reached the upstreams.
+    async fn send_raw_transaction(&self, tx: &Bytes, hash: B256) -> RpcResult<B256> {
+        let mut backoff = INITIAL_RETRY_BACKOFF;
+        let mut last_error = None;
+        for attempt in 0..=self.retries {
+            if attempt > 0 {
+                tokio::time::sleep(backoff).await;
+                backoff *= 2;
+            }
+
+            let (healthy, unhealthy): (Vec<_>, Vec<_>) = self
+                .upstreams
+                .iter()
+                .partition(|upstream| upstream.healthy.load(Ordering::Relaxed));
+            for upstream in healthy.into_iter().chain(unhealthy) {
+                let result: Result<B256, _> =
+                    upstream.client.request("eth_sendRawTransaction", rpc_params![tx]).await;
+                match result {
+                    Ok(tx_hash) => {
+                        upstream.set_healthy(true);
+                        return Ok(tx_hash)
+                    }
+                    Err(ClientError::Call(err)) => {
+                        upstream.set_healthy(true);
+                        if last_error.is_some() && err.message().contains("already known") {
+                            return Ok(hash)
+                        }
+                        return Err(err)
+                    }
+                    Err(err) => {
+                        warn!(
+                            target: "reth::hl",
+                            url = %upstream.url,
+                            %hash,
+                            %err,
+                            "Failed to forward transaction"
+                        );
+                        upstream.set_healthy(false);
+                        last_error = Some(err);
+                    }
+                }
+            }
+        }
+
+        Err(ErrorObject::owned(
+            INTERNAL_ERROR_CODE,
+            format!(
+                "Failed to send transaction: {}",
+                last_error.map(|err| err.to_string()).unwrap_or_default()
+            ),
+            Some(()),
+        ))
+    }
+}
+
+/// Decodes the raw transaction `tx` and recovers its signer.
+///
+/// System transactions are rejected, since their signature only encodes their sender.
+fn decode_transaction(tx: &[u8]) -> Result<Recovered<TransactionSigned>, EthApiError> {
+    let recovered = recover_raw_transaction::<TransactionSigned>(tx)?;
+    if is_impersonated_tx(recovered.signature(), recovered.gas_price()).is_some() {
+        return Err(EthApiError::InvalidTransactionSignature)
+    }
+    Ok(recovered)
 }
 
-impl EthForwarderExt {
-    pub(crate) fn new(upstream_rpc_url: String) -> Self {
-        let client =
-            HttpClientBuilder::default().build(upstream_rpc_url).expect("Failed to build client");
+/// Checks `tx` against the chain and the state of its sender, `account`, like the upstream does
+/// before accepting it.
+fn check_transaction(
+    tx: &TransactionSigned,
+    chain_id: u64,
+    spec_id: SpecId,
+    account: Account,
+) -> Result<(), RpcInvalidTransactionError> {
+    if tx.chain_id().is_some_and(|id| id != chain_id) {
+        return Err(RpcInvalidTransactionError::InvalidChainId)
+    }
 
-        Self { client }
+    let access_list = tx.access_list();
+    let gas = calculate_initial_tx_gas(
+        spec_id,
+        tx.input(),
+        tx.kind().is_create(),
+        access_list.map(|list| list.len()).unwrap_or_default() as u64,
+        access_list
+            .map(|list| list.iter().map(|item| item.storage_keys.len()).sum::<usize>())
+            .unwrap_or_default() as u64,
+        tx.authorization_list().map(|list| list.len()).unwrap_or_default() as u64,
+    );
+    if tx.gas_limit() < gas.initial_gas || tx.gas_limit() < gas.floor_gas {
+        return Err(RpcInvalidTransactionError::GasTooLow)
+    }
+
+    if tx.nonce() < account.nonce {
+        return Err(RpcInvalidTransactionError::NonceTooLow {
+            tx: tx.nonce(),
+            state: account.nonce,
+        });
+    }
+
+    let cost = U256::from(tx.gas_limit())
+        .saturating_mul(U256::from(tx.max_fee_per_gas()))
+        .saturating_add(tx.value());
+    if cost > account.balance {
+        return Err(RpcInvalidTransactionError::InsufficientFunds {
+            cost,
+            balance: account.balance,
+        })
+    }
+
+    Ok(())
+}
+
+/// Forwards `eth_sendRawTransaction` to the upstream RPCs, once the transaction passed the
+/// checks of [`check_transaction`] against the latest state.
+///
+/// Forwarded transactions are added to the local pool until they are included, see
+/// [`ForwardedTransactions`].
+#[derive(Debug)]
+pub(crate) struct EthForwarderExt<Provider, Pool> {
+    provider: Provider,
+    upstreams: Upstreams,
+    forwarded: ForwardedTransactions<Pool>,
+}
+
+impl<Provider, Pool> EthForwarderExt<Provider, Pool>
+where
+    Provider: ChainSpecProvider<ChainSpec = ChainSpec>,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Clone
+        + 'static,
+{
+    /// Creates the forwarder, and starts checking the health of the upstreams.
+    pub(crate) fn new(
+        args: &TxForwarderArgs,
+        provider: Provider,
+        pool: Pool,
+    ) -> eyre::Result<Self> {
+        let upstreams = Upstreams::new(&args.upstream_rpc_urls, args.timeout, args.retries)?;
+        upstreams.start_health_checks(args.health_check_interval, provider.chain_spec().chain_id());
+        let forwarded = ForwardedTransactions::new(pool, args.pending_lifetime);
+        Ok(Self { provider, upstreams, forwarded })
+    }
+}
+
+impl<Provider, Pool> EthForwarderExt<Provider, Pool>
+where
+    Provider: StateProviderFactory
+        + BlockNumReader
+        + ChainSpecProvider<ChainSpec = ChainSpec>
+        + Clone
+        + 'static,
+{
+    /// Checks `tx` against the state of the latest block, which is read on the blocking pool.
+    async fn validate_transaction(
+        &self,
+        tx: &Recovered<TransactionSigned>,
+    ) -> Result<(), EthApiError> {
+        let provider = self.provider.clone();
+        let signer = tx.signer();
+        let (number, account) = tokio::task::spawn_blocking(move || -> Result<_, EthApiError> {
+            let number = provider.best_block_number()? + 1;
+            let account = provider.latest()?.basic_account(&signer)?.unwrap_or_default();
+            Ok((number, account))
+        })
+        .await
+        .map_err(|_| EthApiError::InternalBlockingTaskError)??;
+
+        let chain_spec = self.provider.chain_spec();
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+        let spec_id = revm_spec_by_timestamp_and_block_number(&chain_spec, timestamp, number);
+        check_transaction(tx, chain_spec.chain_id(), spec_id, account)?;
+        Ok(())
     }
 }
 
 #[async_trait]
-impl EthForwarderApiServer for EthForwarderExt {
+impl<Provider, Pool> EthForwarderApiServer for EthForwarderExt<Provider, Pool>
+where
+    Provider: StateProviderFactory
+        + BlockNumReader
+        + ChainSpecProvider<ChainSpec = ChainSpec>
+        + Clone
+        + 'static,
+    Pool: TransactionPool<Transaction: PoolTransaction<Consensus = TransactionSigned>>
+        + Clone
+        + 'static,
+{
     async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256> {
-        let txhash =
-            self.client.clone().request("eth_sendRawTransaction", vec![tx]).await.map_err(|e| {
-                match e {
-                    ClientError::Call(e) => e,
-                    _ => ErrorObject::owned(
-                        INTERNAL_ERROR_CODE,
-                        format!("Failed to send transaction: {:?}", e),
-                        Some(()),
-                    ),
-                }
-            })?;
-        Ok(txhash)
+        let recovered = decode_transaction(&tx)?;
+        self.validate_transaction(&recovered).await?;
+        let hash = self.upstreams.send_raw_transaction(&tx, *recovered.tx_hash()).await?;
+        self.forwarded.insert(recovered).await;
+        Ok(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{TxEip1559, TxLegacy};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_primitives::{address, Address, PrimitiveSignature as Signature, TxKind};
+    use jsonrpsee::{server::Server, RpcModule};
+    use reth_primitives::{Transaction, TxSystem};
+
+    fn transaction(tx: TxEip1559) -> TransactionSigned {
+        TransactionSigned::new_unhashed(Transaction::Eip1559(tx), Signature::test_signature())
+    }
+
+    fn transfer() -> TxEip1559 {
+        TxEip1559 {
+            chain_id: 999,
+            nonce: 5,
+            gas_limit: 21_000,
+            max_fee_per_gas: 10,
+            to: TxKind::Call(Address::with_last_byte(1)),
+            value: U256::from(1_000),
+            ..Default::default()
+        }
+    }
+
+    fn account(nonce: u64, balance: u64) -> Account {
+        Account { nonce, balance: U256::from(balance), bytecode_hash: None }
+    }
+
+    #[test]
+    fn checks_transaction_against_state() {
+        let check = |tx: TxEip1559, account: Account| {
+            check_transaction(&transaction(tx), 999, SpecId::CANCUN, account)
+        };
+
+        assert!(check(transfer(), account(5, 211_000)).is_ok());
+        assert!(matches!(
+            check(TxEip1559 { chain_id: 998, ..transfer() }, account(5, 211_000)),
+            Err(RpcInvalidTransactionError::InvalidChainId)
+        ));
+        assert!(matches!(
+            check(TxEip1559 { gas_limit: 20_999, ..transfer() }, account(5, 211_000)),
+            Err(RpcInvalidTransactionError::GasTooLow)
+        ));
+        assert!(matches!(
+            check(transfer(), account(6, 211_000)),
+            Err(RpcInvalidTransactionError::NonceTooLow { tx: 5, state: 6 })
+        ));
+        assert!(matches!(
+            check(transfer(), account(5, 210_999)),
+            Err(RpcInvalidTransactionError::InsufficientFunds { .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_system_transactions() {
+        let tx = TransactionSigned::from(TxSystem {
+            from: address!("2222222222222222222222222222222222222222"),
+            tx: TxLegacy { chain_id: Some(999), gas_limit: 21_000, ..Default::default() },
+        });
+        assert!(matches!(
+            decode_transaction(&tx.encoded_2718()),
+            Err(EthApiError::InvalidTransactionSignature)
+        ));
+        assert!(matches!(decode_transaction(&[]), Err(EthApiError::EmptyRawTransactionData)));
+    }
+
+    #[tokio::test]
+    async fn fails_over_to_next_upstream() {
+        let server = Server::builder().build("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", server.local_addr().unwrap());
+        let mut module = RpcModule::new(());
+        module
+            .register_method("eth_sendRawTransaction", |_, _, _| B256::with_last_byte(1))
+            .unwrap();
+        let _handle = server.start(module);
+
+        // Nothing listens on the port of a dropped listener
+        let closed = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let upstreams =
+            Upstreams::new(&[format!("http://{closed}"), url], Duration::from_secs(5), 0).unwrap();
+        let hash = upstreams.send_raw_transaction(&Bytes::new(), B256::ZERO).await.unwrap();
+        assert_eq!(hash, B256::with_last_byte(1));
+        assert!(!upstreams.upstreams[0].healthy.load(Ordering::Relaxed));
+        assert!(upstreams.upstreams[1].healthy.load(Ordering::Relaxed));
     }
 }
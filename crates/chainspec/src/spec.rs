@@ -23,7 +23,8 @@ use alloy_trie::root::state_root_ref_unhashed;
 use derive_more::From;
 use reth_ethereum_forks::{
     ChainHardforks, DisplayHardforks, EthereumHardfork, EthereumHardforks, ForkCondition,
-    ForkFilter, ForkFilterKey, ForkHash, ForkId, Hardfork, Hardforks, Head, DEV_HARDFORKS,
+    ForkFilter, ForkFilterKey, ForkHash, ForkId, Hardfork, Hardforks, Head, HyperliquidHardfork,
+    DEV_HARDFORKS,
 };
 use reth_network_peers::{
     base_nodes, base_testnet_nodes, holesky_nodes, mainnet_nodes, op_nodes, op_testnet_nodes,
@@ -729,6 +730,20 @@ impl From<Genesis> for ChainSpec {
         // append the remaining unknown hardforks to ensure we don't filter any out
         ordered_hardforks.append(&mut hardforks);
 
+        // Hyperliquid upgrades are activated at the blocks set in the extra fields of the config,
+        // e.g. `"coreWriterBlock": 0`, and never activated otherwise.
+        let hyperliquid_hardfork_opts = [
+            (HyperliquidHardfork::SelfDestructFix.boxed(), "selfDestructFixBlock"),
+            (HyperliquidHardfork::CoreWriter.boxed(), "coreWriterBlock"),
+            (HyperliquidHardfork::WarmPrecompiles.boxed(), "warmPrecompilesBlock"),
+        ];
+        ordered_hardforks.extend(hyperliquid_hardfork_opts.into_iter().filter_map(
+            |(hardfork, key)| {
+                let block = genesis.config.extra_fields.get_deserialized::<u64>(key)?.ok()?;
+                Some((hardfork, ForkCondition::Block(block)))
+            },
+        ));
+
         // Extract blob parameters directly from blob_schedule
         let blob_params = HardforkBlobParams::from_schedule(&genesis.config.blob_schedule);
 
@@ -2174,6 +2189,15 @@ Post-merge hard forks (timestamp based):
         assert_eq!(chainspec.paris_block_and_final_difficulty, Some((72, U256::from(9454784))));
     }
 
+    #[test]
+    fn test_hyperliquid_hardforks_from_genesis() {
+        let s = r#"{"config":{"chainId":1337,"londonBlock":0,"coreWriterBlock":10},"alloc":{}}"#;
+        let chainspec = ChainSpec::from(serde_json::from_str::<Genesis>(s).unwrap());
+        assert_eq!(chainspec.fork(HyperliquidHardfork::CoreWriter), ForkCondition::Block(10));
+        assert!(chainspec.fork(HyperliquidHardfork::CoreWriter).transitions_at_block(10));
+        assert_eq!(chainspec.fork(HyperliquidHardfork::SelfDestructFix), ForkCondition::Never);
+    }
+
     #[test]
     fn test_parse_genesis_json() {
         let s = r#"{"config":{"ethash":{},"chainId":1337,"homesteadBlock":0,"eip150Block":0,"eip155Block":0,"eip158Block":0,"byzantiumBlock":0,"constantinopleBlock":0,"petersburgBlock":0,"istanbulBlock":0,"berlinBlock":0,"londonBlock":0,"terminalTotalDifficulty":0,"terminalTotalDifficultyPassed":true,"shanghaiTime":0},"nonce":"0x0","timestamp":"0x0","extraData":"0x","gasLimit":"0x4c4b40","difficulty":"0x1","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","coinbase":"0x0000000000000000000000000000000000000000","alloc":{"658bdf435d810c91414ec09147daa6db62406379":{"balance":"0x487a9a304539440000"},"aa00000000000000000000000000000000000000":{"code":"0x6042","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x1","nonce":"0x1"},"bb00000000000000000000000000000000000000":{"code":"0x600154600354","storage":{"0x0000000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000000","0x0100000000000000000000000000000000000000000000000000000000000000":"0x0100000000000000000000000000000000000000000000000000000000000000","0x0200000000000000000000000000000000000000000000000000000000000000":"0x0200000000000000000000000000000000000000000000000000000000000000","0x0300000000000000000000000000000000000000000000000000000000000000":"0x0000000000000000000000000000000000000000000000000000000000000303"},"balance":"0x2","nonce":"0x1"}},"number":"0x0","gasUsed":"0x0","parentHash":"0x0000000000000000000000000000000000000000000000000000000000000000","baseFeePerGas":"0x1337"}"#;
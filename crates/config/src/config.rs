@@ -120,6 +120,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index System Transfers stage configuration.
+    pub index_system_transfers: IndexHistoryConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
 }
@@ -0,0 +1,54 @@
+use crate::{hardfork, ChainHardforks, ForkCondition, Hardfork};
+use alloc::{boxed::Box, format, string::String};
+use core::{
+    fmt,
+    fmt::{Display, Formatter},
+    str::FromStr,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+hardfork!(
+    /// The name of a Hyperliquid protocol upgrade.
+    HyperliquidHardfork {
+        /// Self-destructs are applied to the state. Before this fork, hl-node did not apply a few
+        /// self-destructs of mainnet, and the destroyed contracts were kept in the state.
+        SelfDestructFix,
+        /// Deployment of the CoreWriter system contract at `0x3333…3333`, at the activation block.
+        CoreWriter,
+        /// Read precompiles up to the highest one of the block are warm, even if not called.
+        WarmPrecompiles,
+    }
+);
+
+impl HyperliquidHardfork {
+    /// Hyperliquid mainnet list of hardforks.
+    pub const fn mainnet() -> [(Self, ForkCondition); 3] {
+        [
+            (Self::SelfDestructFix, ForkCondition::Block(1_531_367)),
+            (Self::CoreWriter, ForkCondition::Block(7_578_300)),
+            (Self::WarmPrecompiles, ForkCondition::Block(8_197_684)),
+        ]
+    }
+
+    /// Hyperliquid testnet list of hardforks.
+    ///
+    /// The testnet state starts from a snapshot taken after all of these upgrades.
+    pub const fn testnet() -> [(Self, ForkCondition); 3] {
+        [
+            (Self::SelfDestructFix, ForkCondition::Block(0)),
+            (Self::CoreWriter, ForkCondition::Block(0)),
+            (Self::WarmPrecompiles, ForkCondition::Block(0)),
+        ]
+    }
+}
+
+impl<const N: usize> From<[(HyperliquidHardfork, ForkCondition); N]> for ChainHardforks {
+    fn from(list: [(HyperliquidHardfork, ForkCondition); N]) -> Self {
+        Self::new(
+            list.into_iter()
+                .map(|(fork, cond)| (Box::new(fork) as Box<dyn Hardfork>, cond))
+                .collect(),
+        )
+    }
+}
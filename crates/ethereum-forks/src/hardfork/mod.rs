@@ -3,6 +3,9 @@ mod macros;
 mod ethereum;
 pub use ethereum::EthereumHardfork;
 
+mod hyperliquid;
+pub use hyperliquid::HyperliquidHardfork;
+
 mod dev;
 pub use dev::DEV_HARDFORKS;
 
@@ -101,6 +104,19 @@ mod tests {
         assert_eq!(hardforks, expected_hardforks);
     }
 
+    #[test]
+    fn check_hyperliquid_hardfork_from_str() {
+        assert_eq!(
+            HyperliquidHardfork::from_str("selfdestructfix").unwrap(),
+            HyperliquidHardfork::SelfDestructFix
+        );
+        assert_eq!(
+            HyperliquidHardfork::from_str("CoreWriter").unwrap(),
+            HyperliquidHardfork::CoreWriter
+        );
+        assert_eq!(HyperliquidHardfork::WarmPrecompiles.name(), "WarmPrecompiles");
+    }
+
     #[test]
     fn check_nonexistent_hardfork_from_str() {
         assert!(EthereumHardfork::from_str("not a hardfork").is_err());
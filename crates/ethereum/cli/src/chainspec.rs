@@ -2,7 +2,10 @@ extern crate alloc;
 
 use alloy_primitives::{b256, Address, Bytes, B256, B64, U256};
 use once_cell::sync::Lazy;
-use reth_chainspec::{ChainSpec, DEV, DEV_HARDFORKS, HOLESKY, SEPOLIA};
+use reth_chainspec::{
+    ChainHardforks, ChainSpec, ForkCondition, HyperliquidHardfork, DEV, DEV_HARDFORKS, HOLESKY,
+    SEPOLIA,
+};
 use reth_cli::chainspec::{parse_genesis, ChainSpecParser};
 use reth_primitives::{Header, SealedHeader};
 use std::sync::Arc;
@@ -79,20 +82,35 @@ pub static HL_MAINNET: Lazy<alloc::sync::Arc<ChainSpec>> = Lazy::new(|| {
                 GENESIS_HASH,
             ),
             paris_block_and_final_difficulty: Some((0, U256::from(0))),
-            hardforks: DEV_HARDFORKS.clone(),
+            hardforks: hl_hardforks(HyperliquidHardfork::mainnet()),
             prune_delete_limit: 10000,
             ..Default::default()
         }.into()
 });
 
+/// Returns the hardforks of a Hyperliquid chain: all Ethereum hardforks up to Cancun are active
+/// at genesis, and the Hyperliquid upgrades are activated as given.
+pub(crate) fn hl_hardforks<const N: usize>(
+    upgrades: [(HyperliquidHardfork, ForkCondition); N],
+) -> ChainHardforks {
+    let mut hardforks = DEV_HARDFORKS.clone();
+    for (fork, condition) in upgrades {
+        hardforks.insert(fork, condition);
+    }
+    hardforks
+}
+
 /// Clap value parser for [`ChainSpec`]s.
 ///
 /// The value parser matches either a known chain, the path
 /// to a json file, or a json formatted string in-memory. The json needs to be a Genesis struct.
+///
+/// The genesis header of `testnet` is read from the file at `HL_TESTNET_GENESIS_HEADER`, and
+/// verified against the hash of the block the testnet state starts from.
 pub fn chain_value_parser(s: &str) -> eyre::Result<Arc<ChainSpec>, eyre::Error> {
     Ok(match s {
         "mainnet" => HL_MAINNET.clone(),
-        "testnet" => Arc::new(super::hl_testnet::load_hl_testnet()),
+        "testnet" => Arc::new(super::hl_testnet::load_hl_testnet()?),
         "sepolia" => SEPOLIA.clone(),
         "holesky" => HOLESKY.clone(),
         "dev" => DEV.clone(),
@@ -118,11 +136,13 @@ impl ChainSpecParser for EthereumChainSpecParser {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use reth_chainspec::EthereumHardforks;
+    use reth_chainspec::{EthereumHardforks, Hardforks};
 
     #[test]
     fn parse_known_chain_spec() {
-        for &chain in EthereumChainSpecParser::SUPPORTED_CHAINS {
+        // The testnet genesis header is read from a file, which is not available here
+        for &chain in EthereumChainSpecParser::SUPPORTED_CHAINS.iter().filter(|c| **c != "testnet")
+        {
             assert!(<EthereumChainSpecParser as ChainSpecParser>::parse(chain).is_ok());
         }
     }
@@ -181,4 +201,14 @@ mod tests {
         assert!(spec.is_prague_active_at_timestamp(0));
         assert!(spec.is_osaka_active_at_timestamp(0));
     }
+
+    #[test]
+    fn hl_mainnet_hyperliquid_hardforks() {
+        assert!(HL_MAINNET.is_cancun_active_at_timestamp(0));
+        assert!(HL_MAINNET.fork(HyperliquidHardfork::CoreWriter).transitions_at_block(7_578_300));
+        assert!(
+            !HL_MAINNET.is_fork_active_at_block(HyperliquidHardfork::SelfDestructFix, 1_531_366)
+        );
+        assert!(HL_MAINNET.is_fork_active_at_block(HyperliquidHardfork::WarmPrecompiles, 8_197_684));
+    }
 }
@@ -1,57 +1,54 @@
 use alloy_consensus::Header;
 use alloy_genesis::{ChainConfig, Genesis};
-use alloy_primitives::U256;
+use alloy_primitives::{b256, B256, U256};
 use alloy_rlp::Decodable;
-use reqwest::blocking::get;
-use reth_chainspec::{ChainSpec, DEV_HARDFORKS};
+use eyre::{bail, ensure, WrapErr};
+use reth_chainspec::{ChainSpec, HyperliquidHardfork};
 use reth_primitives::SealedHeader;
-use std::collections::BTreeMap;
-use std::fs::File;
-use std::io::{Read, Write};
+use std::{collections::BTreeMap, path::Path};
 
-pub(crate) fn load_hl_testnet() -> ChainSpec {
-    const TESTNET_GENESIS_URL: &str = "https://raw.githubusercontent.com/sprites0/hl-testnet-genesis/main/21304281.rlp";
+/// Environment variable with the path of the testnet genesis header.
+const TESTNET_GENESIS_HEADER_ENV: &str = "HL_TESTNET_GENESIS_HEADER";
 
-    fn download_testnet_genesis() -> Result<&'static str, Box<dyn std::error::Error>> {
-        let path = "/tmp/hl_testnet.rmp.lz4";
-        println!("Downloading testnet genesis");
-        let mut response = get(TESTNET_GENESIS_URL)?;
-        if let Some(length) = response.content_length() {
-            // Check if the file exists
-            if let Ok(metadata) = std::fs::metadata(path) {
-                if metadata.len() == length {
-                    println!("Already downloaded");
-                    return Ok(path);
-                }
-            }
-        }
-        let mut file = File::create(path)?;
-        let mut downloaded = 0;
-        let total_size = response.content_length().unwrap_or(0);
-        let mut buffer = vec![0; 0x100000];
+/// Number of the block the testnet state starts from.
+const TESTNET_GENESIS_NUMBER: u64 = 21_304_281;
 
-        loop {
-            let size = response.read(buffer.as_mut_slice())?;
-            if size == 0 {
-                break;
-            }
-            file.write_all(&buffer[..size])?;
-            downloaded += size as u64;
-            println!(
-                "Downloaded {} of {} bytes ({}%)",
-                downloaded,
-                total_size,
-                (downloaded as f64 / total_size as f64 * 100.0).round()
-            );
-        }
-        Ok(path)
-    }
+/// Hash of the testnet block [`TESTNET_GENESIS_NUMBER`], which the genesis header is verified
+/// against.
+const TESTNET_GENESIS_HEADER_HASH: B256 =
+    b256!("5b10856d2b1ad241c9bd6136bcc60ef7e8553560ca53995a590db65f809269b4");
 
-    let path = download_testnet_genesis().expect("Failed to download testnet genesis");
-    let mut file = File::open(path).expect("Failed to open testnet genesis");
-    let mut buffer = Vec::new();
-    file.read_to_end(&mut buffer).expect("Failed to read testnet genesis");
-    let mut header = Header::decode(&mut &buffer[..]).expect("Failed to decode testnet genesis");
+/// Returns the testnet chain spec, with the genesis header read from the file at
+/// [`TESTNET_GENESIS_HEADER_ENV`].
+pub(crate) fn load_hl_testnet() -> eyre::Result<ChainSpec> {
+    let Some(path) = std::env::var_os(TESTNET_GENESIS_HEADER_ENV) else {
+        bail!(
+            "the testnet genesis header is required for `--chain testnet`: download \
+             {TESTNET_GENESIS_NUMBER}.rlp of https://github.com/sprites0/hl-testnet-genesis and \
+             set {TESTNET_GENESIS_HEADER_ENV} to its path"
+        )
+    };
+    load_hl_testnet_from(Path::new(&path))
+}
+
+/// Returns the testnet chain spec, with the genesis header read from the RLP encoded header of
+/// block [`TESTNET_GENESIS_NUMBER`] at `path`.
+pub(crate) fn load_hl_testnet_from(path: &Path) -> eyre::Result<ChainSpec> {
+    let buffer = std::fs::read(path).wrap_err_with(|| {
+        format!(
+            "failed to read the testnet genesis header {}, set by {TESTNET_GENESIS_HEADER_ENV}",
+            path.display()
+        )
+    })?;
+    let mut header = Header::decode(&mut &buffer[..])
+        .wrap_err_with(|| format!("invalid testnet genesis header {}", path.display()))?;
+    let hash = header.hash_slow();
+    ensure!(
+        hash == TESTNET_GENESIS_HEADER_HASH,
+        "testnet genesis header {} has hash {hash}, expected {TESTNET_GENESIS_HEADER_HASH} of \
+         block {TESTNET_GENESIS_NUMBER}",
+        path.display()
+    );
 
     let config = ChainConfig {
         chain_id: 998,
@@ -102,12 +99,38 @@ pub(crate) fn load_hl_testnet() -> ChainSpec {
         number: None,
     };
 
-    ChainSpec {
+    Ok(ChainSpec {
         chain: alloy_chains::Chain::from_id(998),
         genesis: genesis.into(),
         genesis_header,
-        hardforks: DEV_HARDFORKS.clone(),
+        hardforks: crate::chainspec::hl_hardforks(HyperliquidHardfork::testnet()),
         prune_delete_limit: 10000,
         ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rlp::Encodable;
+    use std::io::Write;
+
+    #[test]
+    fn rejects_unexpected_genesis_header() {
+        let header = Header { number: TESTNET_GENESIS_NUMBER, ..Default::default() };
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        let mut buffer = Vec::new();
+        header.encode(&mut buffer);
+        file.write_all(&buffer).unwrap();
+
+        let err = load_hl_testnet_from(file.path()).unwrap_err();
+        assert!(err.to_string().contains(&TESTNET_GENESIS_HEADER_HASH.to_string()));
+    }
+
+    #[test]
+    fn missing_genesis_header_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let err = load_hl_testnet_from(&dir.path().join("21304281.rlp")).unwrap_err();
+        assert!(err.to_string().contains(TESTNET_GENESIS_HEADER_ENV));
     }
 }
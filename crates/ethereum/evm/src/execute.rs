@@ -9,7 +9,9 @@ use alloy_consensus::{Header, Transaction};
 use alloy_eips::{eip4895::Withdrawals, eip6110, eip7685::Requests};
 use alloy_evm::FromRecoveredTx;
 use alloy_primitives::{address, hex, Address, B256};
-use reth_chainspec::{ChainSpec, EthereumHardfork, EthereumHardforks, MAINNET};
+use reth_chainspec::{
+    ChainSpec, EthereumHardfork, EthereumHardforks, Hardforks, HyperliquidHardfork, MAINNET,
+};
 use reth_evm::{
     execute::{
         balance_increment_state, BasicBlockExecutorProvider, BlockExecutionError,
@@ -23,7 +25,7 @@ use reth_execution_types::BlockExecutionResult;
 use reth_primitives::{
     EthPrimitives, Receipt, Recovered, RecoveredBlock, SealedBlock, TransactionSigned,
 };
-use reth_primitives_traits::{transaction::signed::is_impersonated_tx, NodePrimitives};
+use reth_primitives_traits::{NodePrimitives, SignedTransaction};
 use reth_revm::{
     context_interface::result::ResultAndState, db::State, state::Bytecode, DatabaseCommit,
 };
@@ -161,12 +163,12 @@ where
     }
 
     fn deploy_corewriter_contract(&mut self, block_number: u64) -> Result<(), BlockExecutionError> {
-        const COREWRITER_ENABLED_BLOCK_NUMBER: u64 = 7578300;
         const COREWRITER_CONTRACT_ADDRESS: Address =
             address!("0x3333333333333333333333333333333333333333");
         const COREWRITER_BYTECODE: &[u8] = &hex!("608060405234801561000f575f5ffd5b5060043610610029575f3560e01c806317938e131461002d575b5f5ffd5b61004760048036038101906100429190610123565b610049565b005b5f5f90505b61019081101561006557808060010191505061004e565b503373ffffffffffffffffffffffffffffffffffffffff167f8c7f585fb295f7eb1e6aeb8fba61b23a4fe60beda405f0045073b185c74412e383836040516100ae9291906101c8565b60405180910390a25050565b5f5ffd5b5f5ffd5b5f5ffd5b5f5ffd5b5f5ffd5b5f5f83601f8401126100e3576100e26100c2565b5b8235905067ffffffffffffffff811115610100576100ff6100c6565b5b60208301915083600182028301111561011c5761011b6100ca565b5b9250929050565b5f5f60208385031215610139576101386100ba565b5b5f83013567ffffffffffffffff811115610156576101556100be565b5b610162858286016100ce565b92509250509250929050565b5f82825260208201905092915050565b828183375f83830152505050565b5f601f19601f8301169050919050565b5f6101a7838561016e565b93506101b483858461017e565b6101bd8361018c565b840190509392505050565b5f6020820190508181035f8301526101e181848661019c565b9050939250505056fea2646970667358221220f01517e1fbaff8af4bd72cb063cccecbacbb00b07354eea7dd52265d355474fb64736f6c634300081c0033");
 
-        if block_number != COREWRITER_ENABLED_BLOCK_NUMBER {
+        let core_writer = self.chain_spec.fork(HyperliquidHardfork::CoreWriter);
+        if !core_writer.transitions_at_block(block_number) {
             return Ok(());
         }
 
@@ -226,7 +228,7 @@ where
         }
 
         let hash = tx.hash();
-        let is_system_transaction = is_impersonated_tx(tx.signature(), tx.gas_price()).is_some();
+        let is_system_transaction = tx.system_tx_sender().is_some();
 
         // Execute transaction.
         let result_and_state =
@@ -234,7 +236,12 @@ where
         self.system_caller
             .on_state(StateChangeSource::Transaction(self.receipts.len()), &result_and_state.state);
         let ResultAndState { result, mut state } = result_and_state;
-        crate::fix::fix_state_diff(self.input.number, self.receipts.len(), &mut state);
+        crate::fix::fix_state_diff(
+            self.chain_spec,
+            self.input.number,
+            self.receipts.len(),
+            &mut state,
+        );
         self.evm.db_mut().commit(state);
 
         let gas_used = result.gas_used();
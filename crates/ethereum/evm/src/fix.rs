@@ -1,7 +1,14 @@
 use alloy_primitives::{address, map::HashMap, Address};
+use reth_chainspec::{ChainSpec, ForkCondition, Hardforks, HyperliquidHardfork};
 use reth_revm::state::Account;
 
+/// Drops the changes of the self-destructs that hl-node did not apply before
+/// [`HyperliquidHardfork::SelfDestructFix`].
+///
+/// The improper self-destructs are only known on mainnet, chains that do not schedule the fix have
+/// none.
 pub(crate) fn fix_state_diff(
+    chain_spec: &ChainSpec,
     block_number: u64,
     tx_index: usize,
     changes: &mut HashMap<Address, Account>,
@@ -27,7 +34,9 @@ pub(crate) fn fix_state_diff(
         (1531211, 3, address!("0xdc67c2b8349ca20f58760e08371fc9271e82b5a4")),
         (1531366, 1, address!("0x9a90a517d27a9e60e454c96fefbbe94ff244ed6f")),
     ];
-    if block_number < 1467569 || block_number > 1531366 {
+    let self_destruct_fix = chain_spec.fork(HyperliquidHardfork::SelfDestructFix);
+    if self_destruct_fix == ForkCondition::Never || self_destruct_fix.active_at_block(block_number)
+    {
         return;
     }
     for (block_num, idx, address) in TX_LIST {
@@ -24,13 +24,14 @@ pub use alloy_evm::EthEvm;
 use alloy_primitives::{address, Address, U160, U256};
 use core::{convert::Infallible, fmt::Debug};
 use parking_lot::RwLock;
-use reth_chainspec::{ChainSpec, EthChainSpec, MAINNET};
+use reth_chainspec::{ChainSpec, EthChainSpec, ForkCondition, Hardforks, MAINNET};
 use reth_evm::Database;
 use reth_evm::{ConfigureEvm, ConfigureEvmEnv, EvmEnv, EvmFactory, NextBlockEnvAttributes};
-use reth_hyperliquid_types::PrecompileData;
-use reth_hyperliquid_types::{PrecompilesCache, ReadPrecompileInput, ReadPrecompileResult};
+use reth_hyperliquid_types::{
+    BlockSource, BlockSourceBoxed, PrecompileData, PrecompileDataUnavailable, PrecompilesCache,
+    S3BlockSource,
+};
 use reth_node_builder::HyperliquidSharedState;
-use reth_primitives::SealedBlock;
 use reth_primitives::TransactionSigned;
 use reth_revm::context::result::{EVMError, HaltReason};
 use reth_revm::handler::EthPrecompiles;
@@ -43,7 +44,6 @@ use reth_revm::{
     specification::hardfork::SpecId,
 };
 use reth_revm::{Context, Inspector, MainContext};
-use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
@@ -51,7 +51,7 @@ mod config;
 mod fix;
 use alloy_eips::eip1559::INITIAL_BASE_FEE;
 pub use config::{revm_spec, revm_spec_by_timestamp_and_block_number};
-use reth_ethereum_forks::EthereumHardfork;
+use reth_ethereum_forks::{EthereumHardfork, HyperliquidHardfork};
 
 pub mod execute;
 
@@ -67,19 +67,35 @@ pub mod eip6110;
 pub struct EthEvmConfig {
     chain_spec: Arc<ChainSpec>,
     evm_factory: HyperliquidEvmFactory,
-    ingest_dir: Option<PathBuf>,
     shared_state: Option<HyperliquidSharedState>,
 }
 
 impl EthEvmConfig {
     /// Creates a new Ethereum EVM configuration with the given chain spec.
     pub fn new(chain_spec: Arc<ChainSpec>) -> Self {
-        Self { chain_spec, ingest_dir: None, evm_factory: Default::default(), shared_state: None }
+        let evm_factory = HyperliquidEvmFactory {
+            warm_precompiles: chain_spec.fork(HyperliquidHardfork::WarmPrecompiles),
+            ..Default::default()
+        };
+        Self { chain_spec, evm_factory, shared_state: None }
+    }
+
+    pub fn with_ingest_dir(self, ingest_dir: PathBuf) -> Self {
+        self.with_block_source(Arc::new(S3BlockSource::new(ingest_dir)))
+    }
+
+    /// Sets the source used to look up the read precompile calls of a block.
+    pub fn with_block_source(mut self, block_source: BlockSourceBoxed) -> Self {
+        self.evm_factory.block_source = Some(block_source);
+        self
     }
 
-    pub fn with_ingest_dir(mut self, ingest_dir: PathBuf) -> Self {
-        self.ingest_dir = Some(ingest_dir.clone());
-        self.evm_factory.ingest_dir = Some(ingest_dir);
+    /// Tracks the replay of the recorded read precompile calls of executed blocks, handling calls
+    /// that were not recorded according to `mode`.
+    ///
+    /// Only set for block execution, since calls of the RPC are not recorded by hl-node.
+    pub fn with_replay_mode(mut self, mode: ReplayMode) -> Self {
+        self.evm_factory.replay_mode = Some(mode);
         self
     }
 
@@ -195,34 +211,15 @@ impl ConfigureEvmEnv for EthEvmConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct BlockAndReceipts {
-    #[serde(default)]
-    pub read_precompile_calls: Vec<(Address, Vec<(ReadPrecompileInput, ReadPrecompileResult)>)>,
-    pub highest_precompile_address: Option<Address>,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) enum EvmBlock {
-    Reth115(SealedBlock),
-}
-
 /// Custom EVM configuration.
 #[derive(Debug, Clone, Default)]
 #[non_exhaustive]
 pub struct HyperliquidEvmFactory {
-    ingest_dir: Option<PathBuf>,
+    block_source: Option<BlockSourceBoxed>,
     shared_state: Option<HyperliquidSharedState>,
-}
-
-pub(crate) fn collect_s3_block(ingest_path: PathBuf, height: u64) -> Option<BlockAndReceipts> {
-    let f = ((height - 1) / 1_000_000) * 1_000_000;
-    let s = ((height - 1) / 1_000) * 1_000;
-    let path = format!("{}/{f}/{s}/{height}.rmp.lz4", ingest_path.to_string_lossy());
-    let file = std::fs::read(path).ok()?;
-    let mut decoder = lz4_flex::frame::FrameDecoder::new(&file[..]);
-    let blocks: Vec<BlockAndReceipts> = rmp_serde::from_read(&mut decoder).unwrap();
-    Some(blocks[0].clone())
+    replay_mode: Option<ReplayMode>,
+    /// Activation of [`HyperliquidHardfork::WarmPrecompiles`] on the chain.
+    warm_precompiles: ForkCondition,
 }
 
 pub(crate) fn get_locally_sourced_precompiles_for_height(
@@ -234,27 +231,43 @@ pub(crate) fn get_locally_sourced_precompiles_for_height(
 }
 
 pub(crate) fn collect_block(
-    ingest_path: PathBuf,
+    block_source: &dyn BlockSource,
     shared_state: Option<HyperliquidSharedState>,
     height: u64,
-) -> Option<BlockAndReceipts> {
+) -> Option<PrecompileData> {
     // Attempt to source precompile from the cache that is shared the binary level with the block
     // ingestor.
     if let Some(shared_state) = shared_state {
         if let Some(calls) =
             get_locally_sourced_precompiles_for_height(shared_state.precompiles_cache, height)
         {
-            return Some(BlockAndReceipts {
-                read_precompile_calls: calls.precompiles,
-                highest_precompile_address: calls.highest_precompile_address,
-            });
+            return Some(calls);
+        }
+        // Then from the database, which has the calls of all imported blocks
+        if let Some(store) = shared_state.precompile_store {
+            match store.precompile_data(height) {
+                Ok(Some(calls)) => return Some(calls),
+                Ok(None) => {}
+                Err(err) => {
+                    tracing::warn!(target: "evm", height, %err, "Failed to read precompile calls");
+                }
+            }
+        }
+    }
+    // Fallback to the configured block source
+    match block_source.collect_block(height) {
+        Ok(block) => block.map(|block| block.precompile_data()),
+        Err(err) => {
+            tracing::warn!(target: "evm", height, %err, "Failed to collect block");
+            None
         }
     }
-    // Fallback to s3 always
-    collect_s3_block(ingest_path, height)
 }
 
-const WARM_PRECOMPILES_BLOCK_NUMBER: u64 = 8_197_684;
+/// Highest read precompile for blocks that do not record theirs, i.e. the highest one when
+/// [`HyperliquidHardfork::WarmPrecompiles`] activated on mainnet.
+const DEFAULT_HIGHEST_PRECOMPILE_ADDRESS: Address =
+    address!("0x000000000000000000000000000000000000080d");
 
 impl EvmFactory<EvmEnv> for HyperliquidEvmFactory {
     type Evm<DB: Database, I: Inspector<EthEvmContext<DB>, EthInterpreter>> =
@@ -265,22 +278,36 @@ impl EvmFactory<EvmEnv> for HyperliquidEvmFactory {
     type Context<DB: Database> = EthEvmContext<DB>;
 
     fn create_evm<DB: Database>(&self, db: DB, input: EvmEnv) -> Self::Evm<DB, NoOpInspector> {
+        let height = input.block_env.number;
         let block = collect_block(
-            self.ingest_dir.clone().unwrap(),
+            self.block_source.as_deref().expect("block source not set"),
             self.shared_state.clone(),
-            input.block_env.number,
-        )
-        .expect("Failed to collect a submitted block. If sourcing locally, make sure your local hl-node is producing blocks.");
+            height,
+        );
+        // Calls against blocks hl-node has not produced yet, e.g. `pending`, have no read
+        // precompile calls. They only fail once they reach a read precompile, which reports the
+        // error to this thread, so drop any error a previous EVM left unhandled.
+        PrecompileDataUnavailable::take_reported();
+        let unavailable = block.is_none().then(|| {
+            tracing::debug!(target: "evm", height, "Read precompile calls unavailable");
+            let policy = self
+                .shared_state
+                .as_ref()
+                .map(|shared_state| shared_state.missing_precompile_data)
+                .unwrap_or_default();
+            (PrecompileDataUnavailable { height }, policy)
+        });
+        let block = block.unwrap_or_default();
         let mut cache: HashMap<_, _> = block
-            .read_precompile_calls
+            .precompiles
             .into_iter()
             .map(|(address, calls)| (address, HashMap::from_iter(calls.into_iter())))
             .collect();
+        let recorded_calls = cache.values().map(HashMap::len).sum();
 
-        if input.block_env.number >= WARM_PRECOMPILES_BLOCK_NUMBER {
-            let highest_precompile_address = block
-                .highest_precompile_address
-                .unwrap_or(address!("0x000000000000000000000000000000000000080d"));
+        if self.warm_precompiles.active_at_block(height) {
+            let highest_precompile_address =
+                block.highest_precompile_address.unwrap_or(DEFAULT_HIGHEST_PRECOMPILE_ADDRESS);
             for i in 0x800.. {
                 let address = Address::from(U160::from(i));
                 if address > highest_precompile_address {
@@ -290,15 +317,20 @@ impl EvmFactory<EvmEnv> for HyperliquidEvmFactory {
             }
         }
 
+        let mut precompiles =
+            ReplayPrecompile::new(EthPrecompiles::default(), Arc::new(RwLock::new(cache)));
+        if let Some((error, policy)) = unavailable {
+            precompiles = precompiles.with_unavailable_data(error, policy);
+        }
+        if let Some(mode) = self.replay_mode {
+            precompiles = precompiles.with_replay_mode(height, mode, recorded_calls);
+        }
         let evm = Context::mainnet()
             .with_db(db)
             .with_cfg(input.cfg_env)
             .with_block(input.block_env)
             .build_mainnet_with_inspector(NoOpInspector {})
-            .with_precompiles(ReplayPrecompile::new(
-                EthPrecompiles::default(),
-                Arc::new(RwLock::new(cache)),
-            ));
+            .with_precompiles(precompiles);
 
         EthEvm::new(evm, false)
     }
@@ -496,6 +528,8 @@ mod tests {
     }
 }
 
+mod precompile_calls;
 mod precompile_replay;
 
-pub use precompile_replay::ReplayPrecompile;
+pub use precompile_calls::{ReadPrecompileCall, ReadPrecompileCallCollector};
+pub use precompile_replay::{ReplayMode, ReplayPrecompile, ReplaySummary};
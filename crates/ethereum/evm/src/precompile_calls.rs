@@ -0,0 +1,47 @@
+//! Collection of the read precompile calls made by a transaction.
+
+use crate::precompile_replay::is_read_precompile;
+use alloc::vec::Vec;
+use alloy_primitives::Address;
+use reth_hyperliquid_types::ReadPrecompileInput;
+use reth_revm::{
+    interpreter::{CallInputs, CallOutcome},
+    Inspector,
+};
+
+/// A call to a read precompile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadPrecompileCall {
+    /// Address of the read precompile.
+    pub address: Address,
+    /// Input and gas limit of the call, the key of the recorded result.
+    pub input: ReadPrecompileInput,
+}
+
+/// [`Inspector`] that collects the calls to the read precompiles, in the order they are made.
+#[derive(Debug, Clone, Default)]
+pub struct ReadPrecompileCallCollector {
+    calls: Vec<ReadPrecompileCall>,
+}
+
+impl ReadPrecompileCallCollector {
+    /// Returns the collected calls.
+    pub fn into_calls(self) -> Vec<ReadPrecompileCall> {
+        self.calls
+    }
+}
+
+impl<CTX> Inspector<CTX> for ReadPrecompileCallCollector {
+    fn call(&mut self, _context: &mut CTX, inputs: &mut CallInputs) -> Option<CallOutcome> {
+        if is_read_precompile(&inputs.bytecode_address) {
+            self.calls.push(ReadPrecompileCall {
+                address: inputs.bytecode_address,
+                input: ReadPrecompileInput {
+                    input: inputs.input.clone(),
+                    gas_limit: inputs.gas_limit,
+                },
+            });
+        }
+        None
+    }
+}
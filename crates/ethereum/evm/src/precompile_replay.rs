@@ -1,19 +1,214 @@
-use alloy_primitives::{Address, Bytes};
-use parking_lot::RwLock;
-use reth_hyperliquid_types::{ReadPrecompileInput, ReadPrecompileResult};
+use alloy_primitives::{Address, Bytes, U160};
+use parking_lot::{Mutex, RwLock};
+use reth_hyperliquid_types::{
+    MissingPrecompileDataPolicy, PrecompileDataUnavailable, ReadPrecompileInput,
+    ReadPrecompileResult,
+};
+use reth_metrics::{metrics::Counter, Metrics};
 use reth_revm::{
     context::{Cfg, ContextTr},
     handler::{EthPrecompiles, PrecompileProvider},
     interpreter::{Gas, InstructionResult, InterpreterResult},
     precompile::PrecompileErrors,
 };
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tracing::{debug, error, warn};
+
+/// How calls missing from the recorded read precompile calls are handled during block execution.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Missing calls fail the precompile call, and are recorded in metrics and the debug log.
+    #[default]
+    Lenient,
+    /// Missing calls fail block execution, since they almost always mean a consensus
+    /// divergence.
+    Strict,
+}
+
+/// Metrics of the replay of read precompile calls during block execution.
+#[derive(Metrics)]
+#[metrics(scope = "hl.precompile_replay")]
+struct ReplayMetrics {
+    /// Number of read precompile calls replayed from the recorded calls.
+    hits: Counter,
+    /// Number of read precompile calls missing from the recorded calls.
+    misses: Counter,
+    /// Number of recorded calls that failed with an unexpected error on hl-node.
+    unexpected_errors: Counter,
+    /// Number of recorded calls that were not made during block execution.
+    unused_calls: Counter,
+}
+
+/// Metrics of the read precompile calls missing from the recorded calls, per precompile.
+#[derive(Metrics)]
+#[metrics(scope = "hl.precompile_replay")]
+struct ReplayMissMetrics {
+    /// Number of read precompile calls missing from the recorded calls.
+    address_misses: Counter,
+}
+
+/// Hits and misses of the recorded read precompile calls of a block.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplaySummary {
+    /// Number of calls replayed from the recorded calls.
+    pub hits: u64,
+    /// Number of calls missing from the recorded calls.
+    pub misses: u64,
+    /// Number of recorded calls that failed with an unexpected error on hl-node.
+    pub unexpected_errors: u64,
+    /// Number of recorded calls that were not made.
+    pub unused_calls: u64,
+}
+
+#[derive(Debug, Default)]
+struct ReplayStats {
+    hits: u64,
+    misses: u64,
+    unexpected_errors: u64,
+    /// Distinct recorded calls that were made.
+    used: HashSet<(Address, ReadPrecompileInput)>,
+}
+
+/// Tracks the replay of the recorded read precompile calls of a block.
+///
+/// It is shared by all clones of the [`ReplayPrecompile`] of the block, and reports the
+/// [`ReplaySummary`] of the block once the last of them is dropped.
+#[derive(Debug)]
+struct ReplayTracker {
+    height: u64,
+    mode: ReplayMode,
+    recorded_calls: usize,
+    stats: Mutex<ReplayStats>,
+}
+
+impl ReplayTracker {
+    fn new(height: u64, mode: ReplayMode, recorded_calls: usize) -> Self {
+        Self { height, mode, recorded_calls, stats: Default::default() }
+    }
+
+    fn record_hit(&self, address: &Address, input: &ReadPrecompileInput) {
+        let mut stats = self.stats.lock();
+        stats.hits += 1;
+        stats.used.insert((*address, input.clone()));
+    }
+
+    /// Records a call missing from the recorded calls. Fails in [`ReplayMode::Strict`].
+    fn record_miss(
+        &self,
+        address: &Address,
+        input: &ReadPrecompileInput,
+    ) -> Result<(), PrecompileErrors> {
+        self.stats.lock().misses += 1;
+        ReplayMissMetrics::new_with_labels(&[("address", address.to_string())])
+            .address_misses
+            .increment(1);
+        debug!(
+            target: "evm::replay",
+            height = self.height,
+            %address,
+            input = %input.input,
+            gas_limit = input.gas_limit,
+            "Read precompile call not recorded"
+        );
+        if self.mode == ReplayMode::Strict {
+            return Err(PrecompileErrors::Fatal {
+                msg: format!(
+                    "read precompile call to {address} with input {} and gas limit {} is not \
+                     recorded for block {}",
+                    input.input, input.gas_limit, self.height
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Records a recorded call that failed with an unexpected error on hl-node. Fails in
+    /// [`ReplayMode::Strict`].
+    fn record_unexpected_error(
+        &self,
+        address: &Address,
+        input: &ReadPrecompileInput,
+    ) -> Result<(), PrecompileErrors> {
+        {
+            let mut stats = self.stats.lock();
+            stats.unexpected_errors += 1;
+            stats.used.insert((*address, input.clone()));
+        }
+        error!(
+            target: "evm::replay",
+            height = self.height,
+            %address,
+            input = %input.input,
+            gas_limit = input.gas_limit,
+            "Read precompile call failed with an unexpected error on hl-node"
+        );
+        if self.mode == ReplayMode::Strict {
+            return Err(PrecompileErrors::Fatal {
+                msg: format!(
+                    "read precompile call to {address} failed with an unexpected error in block {}",
+                    self.height
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    fn summary(&self) -> ReplaySummary {
+        let stats = self.stats.lock();
+        ReplaySummary {
+            hits: stats.hits,
+            misses: stats.misses,
+            unexpected_errors: stats.unexpected_errors,
+            unused_calls: self.recorded_calls.saturating_sub(stats.used.len()) as u64,
+        }
+    }
+}
+
+impl Drop for ReplayTracker {
+    fn drop(&mut self) {
+        let summary = self.summary();
+        let metrics = ReplayMetrics::default();
+        metrics.hits.increment(summary.hits);
+        metrics.misses.increment(summary.misses);
+        metrics.unexpected_errors.increment(summary.unexpected_errors);
+        metrics.unused_calls.increment(summary.unused_calls);
+
+        let ReplaySummary { hits, misses, unexpected_errors, unused_calls } = summary;
+        if misses > 0 || unexpected_errors > 0 || unused_calls > 0 {
+            warn!(
+                target: "evm::replay",
+                height = self.height,
+                hits,
+                misses,
+                unexpected_errors,
+                unused_calls,
+                "Read precompile calls of the block diverge from the recorded calls"
+            );
+        } else {
+            let height = self.height;
+            debug!(target: "evm::replay", height, hits, "Replayed read precompile calls");
+        }
+    }
+}
 
 /// Precompile that replays cached results.
 #[derive(Clone)]
 pub struct ReplayPrecompile<CTX: ContextTr> {
     precompiles: EthPrecompiles<CTX>,
     cache: Arc<RwLock<HashMap<Address, HashMap<ReadPrecompileInput, ReadPrecompileResult>>>>,
+    /// Set if the read precompile calls of the block are unavailable.
+    unavailable: Option<(PrecompileDataUnavailable, MissingPrecompileDataPolicy)>,
+    /// Set if the block is executed, rather than e.g. a call of the RPC.
+    tracker: Option<Arc<ReplayTracker>>,
+}
+
+/// Returns `true` if `address` is in the range of the read precompiles, `0x800..0x900`.
+pub(crate) fn is_read_precompile(address: &Address) -> bool {
+    let address = U160::from_be_bytes(address.0 .0);
+    (U160::from(0x800)..U160::from(0x900)).contains(&address)
 }
 
 impl<CTX: ContextTr> std::fmt::Debug for ReplayPrecompile<CTX> {
@@ -28,7 +223,30 @@ impl<CTX: ContextTr> ReplayPrecompile<CTX> {
         precompiles: EthPrecompiles<CTX>,
         cache: Arc<RwLock<HashMap<Address, HashMap<ReadPrecompileInput, ReadPrecompileResult>>>>,
     ) -> Self {
-        Self { precompiles, cache }
+        Self { precompiles, cache, unavailable: None, tracker: None }
+    }
+
+    /// Marks the read precompile calls of the block as unavailable, so that calls to the read
+    /// precompiles are handled according to `policy`.
+    pub fn with_unavailable_data(
+        mut self,
+        error: PrecompileDataUnavailable,
+        policy: MissingPrecompileDataPolicy,
+    ) -> Self {
+        self.unavailable = Some((error, policy));
+        self
+    }
+
+    /// Tracks the replay of the `recorded_calls` read precompile calls of the block at `height`,
+    /// handling calls that were not recorded according to `mode`.
+    pub fn with_replay_mode(
+        mut self,
+        height: u64,
+        mode: ReplayMode,
+        recorded_calls: usize,
+    ) -> Self {
+        self.tracker = Some(Arc::new(ReplayTracker::new(height, mode, recorded_calls)));
+        self
     }
 }
 
@@ -47,6 +265,22 @@ impl<CTX: ContextTr> PrecompileProvider for ReplayPrecompile<CTX> {
         bytes: &Bytes,
         gas_limit: u64,
     ) -> Result<Option<Self::Output>, PrecompileErrors> {
+        if let Some((error, policy)) = &self.unavailable {
+            if is_read_precompile(address) {
+                if *policy != MissingPrecompileDataPolicy::Revert {
+                    error.report();
+                    return Err(PrecompileErrors::Fatal { msg: error.to_string() })
+                }
+                let mut gas = Gas::new(gas_limit);
+                gas.spend_all();
+                return Ok(Some(InterpreterResult {
+                    result: InstructionResult::PrecompileError,
+                    gas,
+                    output: Bytes::new(),
+                }));
+            }
+        }
+
         let cache = self.cache.read();
         if let Some(precompile_calls) = cache.get(address) {
             let input = ReadPrecompileInput { input: bytes.clone(), gas_limit };
@@ -57,11 +291,19 @@ impl<CTX: ContextTr> PrecompileProvider for ReplayPrecompile<CTX> {
             };
 
             let Some(get) = precompile_calls.get(&input) else {
+                if let Some(tracker) = &self.tracker {
+                    tracker.record_miss(address, &input)?;
+                }
                 result.gas.spend_all();
                 result.result = InstructionResult::PrecompileError;
-                return Ok(Some(result));
+                return Ok(Some(result))
             };
 
+            if let Some(tracker) = &self.tracker {
+                if !matches!(get, ReadPrecompileResult::UnexpectedError) {
+                    tracker.record_hit(address, &input);
+                }
+            }
             return match *get {
                 ReadPrecompileResult::Ok { gas_used, ref bytes } => {
                     let underflow = result.gas.record_cost(gas_used);
@@ -80,7 +322,14 @@ impl<CTX: ContextTr> PrecompileProvider for ReplayPrecompile<CTX> {
                     result.result = InstructionResult::PrecompileError;
                     Ok(Some(result))
                 }
-                ReadPrecompileResult::UnexpectedError => panic!("unexpected precompile error"),
+                ReadPrecompileResult::UnexpectedError => {
+                    if let Some(tracker) = &self.tracker {
+                        tracker.record_unexpected_error(address, &input)?;
+                    }
+                    result.gas.spend_all();
+                    result.result = InstructionResult::PrecompileError;
+                    Ok(Some(result))
+                }
             };
         }
 
@@ -98,3 +347,38 @@ impl<CTX: ContextTr> PrecompileProvider for ReplayPrecompile<CTX> {
         Box::new(addresses.into_iter())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input(byte: u8) -> ReadPrecompileInput {
+        ReadPrecompileInput { input: Bytes::from(vec![byte]), gas_limit: 30_000 }
+    }
+
+    #[test]
+    fn summarizes_replay() {
+        let address = Address::with_last_byte(0x01);
+        let tracker = ReplayTracker::new(1, ReplayMode::Lenient, 3);
+        tracker.record_hit(&address, &input(1));
+        tracker.record_hit(&address, &input(1));
+        tracker.record_miss(&address, &input(2)).unwrap();
+
+        assert_eq!(
+            tracker.summary(),
+            ReplaySummary { hits: 2, misses: 1, unexpected_errors: 0, unused_calls: 2 }
+        );
+    }
+
+    #[test]
+    fn strict_mode_fails_on_miss() {
+        let address = Address::with_last_byte(0x01);
+        let tracker = ReplayTracker::new(1, ReplayMode::Strict, 0);
+        assert!(matches!(
+            tracker.record_miss(&address, &input(1)),
+            Err(PrecompileErrors::Fatal { .. })
+        ));
+        assert!(tracker.record_unexpected_error(&address, &input(2)).is_err());
+        assert_eq!(tracker.summary().misses, 1);
+    }
+}
@@ -11,7 +11,7 @@ use reth_ethereum_engine_primitives::{
 };
 use reth_ethereum_primitives::{EthPrimitives, PooledTransaction};
 use reth_evm::{execute::BasicBlockExecutorProvider, ConfigureEvm};
-use reth_evm_ethereum::execute::EthExecutionStrategyFactory;
+use reth_evm_ethereum::{execute::EthExecutionStrategyFactory, ReplayMode};
 use reth_network::{EthNetworkPrimitives, NetworkHandle, PeersInfo};
 use reth_node_api::{AddOnsContext, FullNodeComponents, NodeAddOns, TxTy};
 use reth_node_builder::{
@@ -251,7 +251,16 @@ where
         let evm_config = EthEvmConfig::new(ctx.chain_spec())
             .with_ingest_dir(ctx.ingest_dir())
             .with_shared_state(ctx.shared_state());
-        let strategy_factory = EthExecutionStrategyFactory::new(chain_spec, evm_config.clone());
+        let replay_mode = if ctx.shared_state().is_some_and(|state| state.strict_precompile_replay)
+        {
+            ReplayMode::Strict
+        } else {
+            ReplayMode::Lenient
+        };
+        let strategy_factory = EthExecutionStrategyFactory::new(
+            chain_spec,
+            evm_config.clone().with_replay_mode(replay_mode),
+        );
         let executor = BasicBlockExecutorProvider::new(strategy_factory);
 
         Ok((evm_config, executor))
@@ -1,8 +1,8 @@
 //! Common conversions from alloy types.
 
-use crate::{Transaction, TransactionSigned};
+use crate::{Transaction, TransactionSigned, TxSystem, SYSTEM_TX_TYPE_ID};
 use alloc::string::ToString;
-use alloy_consensus::TxEnvelope;
+use alloy_consensus::{TxEnvelope, TxLegacy};
 use alloy_network::{AnyRpcTransaction, AnyTxEnvelope};
 use alloy_rpc_types_eth::Transaction as AlloyRpcTransaction;
 use alloy_serde::WithOtherFields;
@@ -14,6 +14,7 @@ impl TryFrom<AnyRpcTransaction> for TransactionSigned {
         use alloy_rpc_types_eth::ConversionError;
 
         let WithOtherFields { inner: tx, other: _ } = tx;
+        let from = tx.from;
 
         let (transaction, signature, hash) = match tx.inner {
             AnyTxEnvelope::Ethereum(TxEnvelope::Legacy(tx)) => {
@@ -36,6 +37,17 @@ impl TryFrom<AnyRpcTransaction> for TransactionSigned {
                 let (tx, signature, hash) = tx.into_parts();
                 (Transaction::Eip7702(tx), signature, hash)
             }
+            // System transactions are returned with their own type and their sender
+            AnyTxEnvelope::Unknown(unknown) if unknown.inner.ty.0 == SYSTEM_TX_TYPE_ID => {
+                let tx = unknown
+                    .inner
+                    .fields
+                    .deserialize_into::<TxLegacy>()
+                    .map_err(|err| ConversionError::Custom(err.to_string()))?;
+                let tx = TxSystem { from, tx };
+                let signature = tx.signature();
+                (Transaction::System(tx), signature, unknown.hash)
+            }
             _ => return Err(ConversionError::Custom("unknown transaction type".to_string())),
         };
 
@@ -16,6 +16,9 @@ pub use receipt::*;
 mod transaction;
 pub use transaction::*;
 
+mod system_tx;
+pub use system_tx::{TxSystem, SYSTEM_TX_TYPE_ID};
+
 #[cfg(feature = "alloy-compat")]
 mod alloy_compat;
 
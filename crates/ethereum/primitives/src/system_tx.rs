@@ -0,0 +1,58 @@
+//! Hyperliquid system transactions.
+
+use alloy_consensus::TxLegacy;
+use alloy_primitives::{Address, PrimitiveSignature as Signature, U256};
+use reth_primitives_traits::transaction::signed::NATIVE_TOKEN_SYSTEM_ADDRESS;
+use serde::{Deserialize, Serialize};
+
+/// Type of system transactions in the database and in RPC responses.
+///
+/// It is never used on the wire, where system transactions are legacy transactions.
+pub const SYSTEM_TX_TYPE_ID: u8 = 0x7f;
+
+/// A Hyperliquid system transaction, moving HYPE or a spot token from HyperCore to the EVM.
+///
+/// System transactions are unsigned and executed on behalf of a system address: `0x2222…2222`
+/// for HYPE, or the system address of the spot token, `0x20…<token index>`, for the calls of its
+/// ERC20 contract.
+///
+/// On the wire, a system transaction is the legacy transaction `tx` with the
+/// [signature](Self::signature) derived from `from`, which is what hl-node hashes as well. In the
+/// database, it is stored with its own type, [`SYSTEM_TX_TYPE_ID`], and its sender.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxSystem {
+    /// System address the transaction is executed on behalf of.
+    pub from: Address,
+    /// The executed transaction, with a zero gas price.
+    pub tx: TxLegacy,
+}
+
+impl TxSystem {
+    /// Returns the signature encoding the sender of the transaction.
+    pub fn signature(&self) -> Signature {
+        let s = if self.from == NATIVE_TOKEN_SYSTEM_ADDRESS {
+            U256::from(1)
+        } else {
+            U256::from_be_slice(self.from.as_slice())
+        };
+        Signature::new(U256::from(1), s, true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+    use reth_primitives_traits::transaction::signed::is_impersonated_tx;
+
+    #[test]
+    fn signature_roundtrip() {
+        for from in
+            [NATIVE_TOKEN_SYSTEM_ADDRESS, address!("2000000000000000000000000000000000000097")]
+        {
+            let tx = TxSystem { from, tx: TxLegacy::default() };
+            assert_eq!(is_impersonated_tx(&tx.signature(), Some(0)), Some(from));
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::TxSystem;
 use alloc::vec::Vec;
 pub use alloy_consensus::{transaction::PooledTransaction, TxType};
 use alloy_consensus::{
@@ -19,10 +20,7 @@ use core::hash::{Hash, Hasher};
 use reth_primitives_traits::{
     crypto::secp256k1::{recover_signer, recover_signer_unchecked},
     sync::OnceLock,
-    transaction::{
-        error::TransactionConversionError,
-        signed::{is_impersonated_tx, RecoveryError},
-    },
+    transaction::{error::TransactionConversionError, signed::RecoveryError},
     InMemorySize, SignedTransaction,
 };
 use revm_context::TxEnv;
@@ -36,6 +34,7 @@ macro_rules! delegate {
             Transaction::Eip1559($tx) => $tx.$method($($arg),*),
             Transaction::Eip4844($tx) => $tx.$method($($arg),*),
             Transaction::Eip7702($tx) => $tx.$method($($arg),*),
+            Transaction::System(TxSystem { tx: $tx, .. }) => $tx.$method($($arg),*),
         }
     };
 }
@@ -57,7 +56,6 @@ macro_rules! impl_from_signed {
 ///
 /// Transaction types were introduced in [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718).
 #[derive(Debug, Clone, PartialEq, Eq, Hash, derive_more::From, Serialize, Deserialize)]
-#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
 pub enum Transaction {
     /// Legacy transaction (type `0x0`).
@@ -105,13 +103,18 @@ pub enum Transaction {
     /// EOA for a single transaction. This allows for temporarily adding smart contract
     /// functionality to the EOA.
     Eip7702(TxEip7702),
+    /// Hyperliquid system transaction, see [`TxSystem`].
+    ///
+    /// It is encoded as a legacy transaction (type `0x0`), with a signature derived from its
+    /// sender, and stored with the type [`SYSTEM_TX_TYPE_ID`](crate::SYSTEM_TX_TYPE_ID).
+    System(TxSystem),
 }
 
 impl Transaction {
     /// Returns [`TxType`] of the transaction.
     pub const fn tx_type(&self) -> TxType {
         match self {
-            Self::Legacy(_) => TxType::Legacy,
+            Self::Legacy(_) | Self::System(_) => TxType::Legacy,
             Self::Eip2930(_) => TxType::Eip2930,
             Self::Eip1559(_) => TxType::Eip1559,
             Self::Eip4844(_) => TxType::Eip4844,
@@ -127,10 +130,34 @@ impl Transaction {
             Self::Eip1559(tx) => tx.nonce = nonce,
             Self::Eip4844(tx) => tx.nonce = nonce,
             Self::Eip7702(tx) => tx.nonce = nonce,
+            Self::System(TxSystem { tx, .. }) => tx.nonce = nonce,
+        }
+    }
+
+    /// Returns the [`TxSystem`] if the transaction is a system transaction.
+    pub const fn as_system(&self) -> Option<&TxSystem> {
+        match self {
+            Self::System(tx) => Some(tx),
+            _ => None,
         }
     }
 }
 
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for Transaction {
+    // System transactions are legacy transactions on the wire, so they do not roundtrip through
+    // the network encoding and are left out.
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=4)? {
+            0 => Self::Legacy(u.arbitrary()?),
+            1 => Self::Eip2930(u.arbitrary()?),
+            2 => Self::Eip1559(u.arbitrary()?),
+            3 => Self::Eip4844(u.arbitrary()?),
+            _ => Self::Eip7702(u.arbitrary()?),
+        })
+    }
+}
+
 impl Typed2718 for Transaction {
     fn ty(&self) -> u8 {
         delegate!(self => tx.ty())
@@ -236,10 +263,20 @@ impl InMemorySize for Transaction {
 impl reth_codecs::Compact for Transaction {
     // Serializes the TxType to the buffer if necessary, returning 2 bits of the type as an
     // identifier instead of the length.
+    //
+    // System transactions are written with the extended identifier, followed by
+    // [`SYSTEM_TX_TYPE_ID`](crate::SYSTEM_TX_TYPE_ID) and their sender.
     fn to_compact<B>(&self, buf: &mut B) -> usize
     where
         B: alloy_rlp::bytes::BufMut + AsMut<[u8]>,
     {
+        if let Self::System(TxSystem { from, tx }) = self {
+            buf.put_u8(crate::SYSTEM_TX_TYPE_ID);
+            from.to_compact(buf);
+            tx.to_compact(buf);
+            return reth_codecs::txtype::COMPACT_EXTENDED_IDENTIFIER_FLAG
+        }
+
         let identifier = self.tx_type().to_compact(buf);
         delegate!(self => tx.to_compact(buf));
         identifier
@@ -254,6 +291,14 @@ impl reth_codecs::Compact for Transaction {
     // A panic will be triggered if an identifier larger than 3 is passed from the database. For
     // optimism a identifier with value [`DEPOSIT_TX_TYPE_ID`] is allowed.
     fn from_compact(buf: &[u8], identifier: usize) -> (Self, &[u8]) {
+        if identifier == reth_codecs::txtype::COMPACT_EXTENDED_IDENTIFIER_FLAG &&
+            buf.first() == Some(&crate::SYSTEM_TX_TYPE_ID)
+        {
+            let (from, buf) = Address::from_compact(&buf[1..], 20);
+            let (tx, buf) = TxLegacy::from_compact(buf, buf.len());
+            return (Self::System(TxSystem { from, tx }), buf)
+        }
+
         let (tx_type, buf) = TxType::from_compact(buf, identifier);
 
         match tx_type {
@@ -413,6 +458,11 @@ impl TransactionSigned {
         }
     }
 
+    /// Returns the [`TxSystem`] if the transaction is a system transaction.
+    pub const fn as_system(&self) -> Option<&TxSystem> {
+        self.transaction.as_system()
+    }
+
     /// Provides mutable access to the transaction.
     #[cfg(feature = "test-utils")]
     pub fn transaction_mut(&mut self) -> &mut Transaction {
@@ -518,6 +568,13 @@ impl From<Signed<TxEip4844WithSidecar>> for TransactionSigned {
     }
 }
 
+impl From<TxSystem> for TransactionSigned {
+    fn from(tx: TxSystem) -> Self {
+        let signature = tx.signature();
+        Self::new_unhashed(tx.into(), signature)
+    }
+}
+
 impl From<TxEip4844Variant> for Transaction {
     fn from(variant: TxEip4844Variant) -> Self {
         match variant {
@@ -557,6 +614,9 @@ impl From<TransactionSigned> for TxEnvelope {
             Transaction::Eip1559(tx) => Signed::new_unchecked(tx, signature, hash).into(),
             Transaction::Eip4844(tx) => Signed::new_unchecked(tx, signature, hash).into(),
             Transaction::Eip7702(tx) => Signed::new_unchecked(tx, signature, hash).into(),
+            Transaction::System(TxSystem { tx, .. }) => {
+                Signed::new_unchecked(tx, signature, hash).into()
+            }
         }
     }
 }
@@ -734,14 +794,14 @@ impl reth_codecs::Compact for TransactionSigned {
             Transaction::from_compact(buf, transaction_type)
         };
 
-        (Self { signature, transaction, hash: Default::default() }, buf)
+        (Self::new_unhashed(transaction, signature), buf)
     }
 }
 
 impl FromRecoveredTx<TransactionSigned> for TxEnv {
     fn from_recovered_tx(tx: &TransactionSigned, sender: Address) -> Self {
         match tx.as_ref() {
-            Transaction::Legacy(tx) => Self {
+            Transaction::Legacy(tx) | Transaction::System(TxSystem { tx, .. }) => Self {
                 gas_limit: tx.gas_limit,
                 gas_price: tx.gas_price,
                 gas_priority_fee: None,
@@ -834,9 +894,12 @@ impl SignedTransaction for TransactionSigned {
         &self.signature
     }
 
+    fn system_tx_sender(&self) -> Option<Address> {
+        self.as_system().map(|tx| tx.from)
+    }
+
     fn recover_signer(&self) -> Result<Address, RecoveryError> {
-        let signature = self.signature();
-        if let Some(address) = is_impersonated_tx(signature, self.gas_price()) {
+        if let Some(address) = self.system_tx_sender() {
             return Ok(address);
         }
         let signature_hash = self.signature_hash();
@@ -847,6 +910,9 @@ impl SignedTransaction for TransactionSigned {
         &self,
         buf: &mut Vec<u8>,
     ) -> Result<Address, RecoveryError> {
+        if let Some(address) = self.system_tx_sender() {
+            return Ok(address);
+        }
         self.encode_for_signing(buf);
         let signature_hash = keccak256(buf);
         recover_signer_unchecked(&self.signature, signature_hash)
@@ -875,6 +941,10 @@ impl TryFrom<TransactionSigned> for PooledTransaction {
             TransactionSigned { transaction: Transaction::Eip4844(_), .. } => {
                 Err(TransactionConversionError::UnsupportedForP2P)
             }
+            // System transactions are only included by HyperCore
+            TransactionSigned { transaction: Transaction::System(_), .. } => {
+                Err(TransactionConversionError::UnsupportedForP2P)
+            }
         }
     }
 }
@@ -902,7 +972,7 @@ pub mod serde_bincode_compat {
         transaction::serde_bincode_compat::{TxEip1559, TxEip2930, TxEip7702, TxLegacy},
         TxEip4844,
     };
-    use alloy_primitives::{PrimitiveSignature as Signature, TxHash};
+    use alloy_primitives::{Address, PrimitiveSignature as Signature, TxHash};
     use reth_primitives_traits::{serde_bincode_compat::SerdeBincodeCompat, SignedTransaction};
     use serde::{Deserialize, Serialize};
 
@@ -915,6 +985,7 @@ pub mod serde_bincode_compat {
         Eip1559(TxEip1559<'a>),
         Eip4844(Cow<'a, TxEip4844>),
         Eip7702(TxEip7702<'a>),
+        System { from: Address, tx: TxLegacy<'a> },
     }
 
     impl<'a> From<&'a super::Transaction> for Transaction<'a> {
@@ -925,6 +996,9 @@ pub mod serde_bincode_compat {
                 super::Transaction::Eip1559(tx) => Self::Eip1559(TxEip1559::from(tx)),
                 super::Transaction::Eip4844(tx) => Self::Eip4844(Cow::Borrowed(tx)),
                 super::Transaction::Eip7702(tx) => Self::Eip7702(TxEip7702::from(tx)),
+                super::Transaction::System(tx) => {
+                    Self::System { from: tx.from, tx: TxLegacy::from(&tx.tx) }
+                }
             }
         }
     }
@@ -937,6 +1011,9 @@ pub mod serde_bincode_compat {
                 Transaction::Eip1559(tx) => Self::Eip1559(tx.into()),
                 Transaction::Eip4844(tx) => Self::Eip4844(tx.into_owned()),
                 Transaction::Eip7702(tx) => Self::Eip7702(tx.into()),
+                Transaction::System { from, tx } => {
+                    Self::System(super::TxSystem { from, tx: tx.into() })
+                }
             }
         }
     }
@@ -1041,7 +1118,9 @@ mod tests {
     };
     use alloy_rlp::{Decodable, Encodable, Error as RlpError};
     use reth_codecs::Compact;
-    use reth_primitives_traits::SignedTransaction;
+    use reth_primitives_traits::{
+        transaction::signed::NATIVE_TOKEN_SYSTEM_ADDRESS, SignedTransaction,
+    };
     use std::str::FromStr;
 
     #[test]
@@ -1429,6 +1508,52 @@ mod tests {
         assert_eq!(tx, decoded);
     }
 
+    #[test]
+    fn system_tx_encoded_as_legacy() {
+        let from = address!("2000000000000000000000000000000000000097");
+        let system_tx = TxSystem {
+            from,
+            tx: TxLegacy {
+                to: TxKind::Call(address!("d3e8763675e4c425df46cc3b5c0f6cbdac396046")),
+                input: bytes!("a9059cbb"),
+                ..Default::default()
+            },
+        };
+        let tx = TransactionSigned::from(system_tx.clone());
+        assert_eq!(tx.as_system(), Some(&system_tx));
+        assert_eq!(tx.ty(), LEGACY_TX_TYPE_ID);
+        assert_eq!(tx.recover_signer().unwrap(), from);
+
+        let legacy = TxEnvelope::Legacy(system_tx.tx.clone().into_signed(system_tx.signature()));
+        assert_eq!(tx.encoded_2718(), legacy.encoded_2718());
+        assert_eq!(tx.tx_hash(), legacy.tx_hash());
+
+        // The sender is not recovered from the signature of the legacy encoding
+        let decoded = TransactionSigned::decode_2718(&mut tx.encoded_2718().as_slice()).unwrap();
+        assert_eq!(decoded.as_system(), None);
+    }
+
+    #[test]
+    fn system_tx_compact_roundtrip() {
+        for input in [vec![], vec![1u8; 64]] {
+            for from in
+                [NATIVE_TOKEN_SYSTEM_ADDRESS, address!("2000000000000000000000000000000000000097")]
+            {
+                let system_tx =
+                    TxSystem { from, tx: TxLegacy { input: input.into(), ..Default::default() } };
+
+                let mut buf = Vec::new();
+                let identifier = Transaction::System(system_tx.clone()).to_compact(&mut buf);
+                assert_eq!(buf[0], crate::SYSTEM_TX_TYPE_ID);
+                let (decoded, rest) = Transaction::from_compact(&buf, identifier);
+                assert_eq!(decoded.as_system(), Some(&system_tx));
+                assert!(rest.is_empty());
+
+                test_transaction_signed_to_from_compact(system_tx.into());
+            }
+        }
+    }
+
     #[test]
     fn create_txs_disallowed_for_eip4844() {
         let data =
@@ -0,0 +1,329 @@
+use std::{
+    fmt::Debug,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tracing::{debug, warn};
+
+use crate::{error::IngestError, serialized::BlockAndReceipts};
+
+/// A source of Hyperliquid EVM blocks.
+///
+/// Implementations are queried by height and must not block for long periods of time, since
+/// they are also consulted when constructing an EVM for re-execution.
+pub trait BlockSource: Send + Sync + Debug {
+    /// Human readable name of the source, used in logs.
+    fn name(&self) -> &str;
+
+    /// Returns the block at the given height, if this source has it.
+    fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError>;
+
+    /// Returns the highest block height currently available from this source.
+    fn find_latest_block_number(&self) -> Option<u64>;
+}
+
+pub type BlockSourceBoxed = Arc<dyn BlockSource>;
+
+/// Returns the object key of the block at `height` in the S3 bucket layout
+/// (`{f}/{s}/{height}.rmp.lz4`).
+pub fn s3_block_key(height: u64) -> String {
+    let f = ((height - 1) / 1_000_000) * 1_000_000;
+    let s = ((height - 1) / 1_000) * 1_000;
+    format!("{f}/{s}/{height}.rmp.lz4")
+}
+
+/// Returns the path of the block file for `height` below `root`.
+pub fn s3_block_path(root: &Path, height: u64) -> PathBuf {
+    root.join(s3_block_key(height))
+}
+
+/// Decodes the contents of the `.rmp.lz4` block file of `height`.
+pub fn decode_block_file(height: u64, bytes: &[u8]) -> Result<BlockAndReceipts, IngestError> {
+    let corrupt = |reason: String| IngestError::CorruptBlock { height, reason, quarantined: None };
+
+    let mut decoder = lz4_flex::frame::FrameDecoder::new(bytes);
+    let blocks: Vec<BlockAndReceipts> =
+        rmp_serde::from_read(&mut decoder).map_err(|err| corrupt(err.to_string()))?;
+    let block = blocks.into_iter().next().ok_or_else(|| corrupt("empty block file".into()))?;
+    if block.number() != height {
+        return Err(corrupt(format!("unexpected block {}", block.number())))
+    }
+    Ok(block)
+}
+
+/// Encodes `block` as the contents of its `.rmp.lz4` block file, the inverse of
+/// [`decode_block_file`].
+pub fn encode_block_file(block: &BlockAndReceipts) -> std::io::Result<Vec<u8>> {
+    let bytes =
+        rmp_serde::to_vec_named(std::slice::from_ref(block)).map_err(std::io::Error::other)?;
+    let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+    encoder.write_all(&bytes)?;
+    encoder.finish().map_err(std::io::Error::other)
+}
+
+/// Writes the block file of `block` below `root`, using the same layout as the bucket.
+///
+/// The file is written to a temporary file first, so that readers of `root` never observe a
+/// partially written block. Returns the path of the block file.
+pub fn write_block_file(root: &Path, block: &BlockAndReceipts) -> std::io::Result<PathBuf> {
+    let path = s3_block_path(root, block.number());
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = path.with_extension("lz4.tmp");
+    std::fs::write(&tmp_path, encode_block_file(block)?)?;
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(path)
+}
+
+/// Moves the undecodable block file of `height` at `path` into `quarantine_dir`, using the same
+/// layout as the bucket.
+///
+/// The file is copied instead if it cannot be moved, e.g. because the ingest directory is a
+/// read-only mount. Returns the path of the quarantined file.
+pub fn quarantine_block_file(
+    path: &Path,
+    quarantine_dir: &Path,
+    height: u64,
+) -> std::io::Result<PathBuf> {
+    let target = s3_block_path(quarantine_dir, height);
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if std::fs::rename(path, &target).is_err() {
+        std::fs::copy(path, &target)?;
+    }
+    Ok(target)
+}
+
+/// Block source reading from a directory laid out like the `hl-mainnet-evm-blocks` bucket.
+#[derive(Debug, Clone)]
+pub struct S3BlockSource {
+    dir: PathBuf,
+    /// Directory undecodable block files are moved to, if any.
+    quarantine_dir: Option<PathBuf>,
+}
+
+impl S3BlockSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into(), quarantine_dir: None }
+    }
+
+    /// Sets the directory undecodable block files are moved to.
+    pub fn with_quarantine_dir(mut self, quarantine_dir: impl Into<PathBuf>) -> Self {
+        self.quarantine_dir = Some(quarantine_dir.into());
+        self
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn quarantine_dir(&self) -> Option<&Path> {
+        self.quarantine_dir.as_deref()
+    }
+}
+
+/// Returns the entry of `dir` with the highest numeric name, after stripping `suffix`.
+fn max_numeric_entry(dir: &Path, suffix: &str) -> Option<(u64, PathBuf)> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name();
+            let number = name.to_str()?.strip_suffix(suffix)?.parse::<u64>().ok()?;
+            Some((number, entry.path()))
+        })
+        .max_by_key(|(number, _)| *number)
+}
+
+impl BlockSource for S3BlockSource {
+    fn name(&self) -> &str {
+        "s3"
+    }
+
+    fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError> {
+        let path = s3_block_path(&self.dir, height);
+        let file = match std::fs::read(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(source) => return Err(IngestError::Io { height, path, source }),
+        };
+
+        match decode_block_file(height, &file) {
+            Ok(block) => {
+                debug!(target: "reth::hl", height, "Collected block from s3 directory");
+                Ok(Some(block))
+            }
+            Err(IngestError::CorruptBlock { reason, .. }) => {
+                let quarantined = self.quarantine_dir.as_ref().and_then(|quarantine_dir| {
+                    quarantine_block_file(&path, quarantine_dir, height)
+                        .inspect_err(|err| {
+                            warn!(
+                                target: "reth::hl",
+                                height,
+                                ?path,
+                                %err,
+                                "Failed to quarantine block file"
+                            )
+                        })
+                        .ok()
+                });
+                Err(IngestError::CorruptBlock { height, reason, quarantined })
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    fn find_latest_block_number(&self) -> Option<u64> {
+        let (_, f_dir) = max_numeric_entry(&self.dir, "")?;
+        let (_, s_dir) = max_numeric_entry(&f_dir, "")?;
+        let (height, _) = max_numeric_entry(&s_dir, ".rmp.lz4")?;
+        Some(height)
+    }
+}
+
+/// Block source that queries a list of sources in priority order and returns the first hit.
+#[derive(Debug, Clone, Default)]
+pub struct FallbackBlockSource {
+    sources: Vec<BlockSourceBoxed>,
+}
+
+impl FallbackBlockSource {
+    pub fn new(sources: Vec<BlockSourceBoxed>) -> Self {
+        Self { sources }
+    }
+
+    /// Appends a source with the lowest priority so far.
+    pub fn with_source(mut self, source: BlockSourceBoxed) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    pub fn sources(&self) -> &[BlockSourceBoxed] {
+        &self.sources
+    }
+
+    /// Returns the block from the first source that has it, together with that source.
+    ///
+    /// Sources failing to provide the block are skipped. Their first error is returned if no
+    /// other source has the block.
+    pub fn collect_block_with_source(
+        &self,
+        height: u64,
+    ) -> Result<Option<(BlockAndReceipts, &BlockSourceBoxed)>, IngestError> {
+        let mut first_error = None;
+        for source in &self.sources {
+            match source.collect_block(height) {
+                Ok(Some(block)) => {
+                    debug!(target: "reth::hl", height, source = source.name(), "Collected block");
+                    return Ok(Some((block, source)))
+                }
+                Ok(None) => {}
+                Err(err) => {
+                    warn!(
+                        target: "reth::hl",
+                        height,
+                        source = source.name(),
+                        %err,
+                        "Failed to collect block"
+                    );
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+        first_error.map_or(Ok(None), Err)
+    }
+}
+
+impl BlockSource for FallbackBlockSource {
+    fn name(&self) -> &str {
+        "fallback"
+    }
+
+    fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError> {
+        Ok(self.collect_block_with_source(height)?.map(|(block, _)| block))
+    }
+
+    fn find_latest_block_number(&self) -> Option<u64> {
+        self.sources.iter().filter_map(|source| source.find_latest_block_number()).max()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils;
+    use alloy_primitives::Address;
+
+    #[test]
+    fn s3_block_path_layout() {
+        let root = Path::new("/evm-blocks");
+        assert_eq!(s3_block_path(root, 1), root.join("0/0/1.rmp.lz4"));
+        assert_eq!(s3_block_path(root, 1_000), root.join("0/0/1000.rmp.lz4"));
+        assert_eq!(s3_block_path(root, 1_001), root.join("0/1000/1001.rmp.lz4"));
+        assert_eq!(s3_block_path(root, 7_578_300), root.join("7000000/7578000/7578300.rmp.lz4"));
+    }
+
+    #[test]
+    fn s3_latest_block_number() {
+        let dir = tempfile::tempdir().unwrap();
+        for height in [999, 1_000, 1_001, 1_002] {
+            let path = s3_block_path(dir.path(), height);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, []).unwrap();
+        }
+
+        let source = S3BlockSource::new(dir.path());
+        assert_eq!(source.find_latest_block_number(), Some(1_002));
+        // Undecodable files are not returned
+        assert!(matches!(
+            source.collect_block(1_002),
+            Err(IngestError::CorruptBlock { height: 1_002, quarantined: None, .. })
+        ));
+        assert!(matches!(source.collect_block(1_003), Ok(None)));
+    }
+
+    #[test]
+    fn s3_quarantine_corrupt_block() {
+        let dir = tempfile::tempdir().unwrap();
+        let quarantine_dir = tempfile::tempdir().unwrap();
+        let path = s3_block_path(dir.path(), 1_001);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, b"truncated").unwrap();
+
+        let source = S3BlockSource::new(dir.path()).with_quarantine_dir(quarantine_dir.path());
+        let Err(IngestError::CorruptBlock { quarantined: Some(quarantined), .. }) =
+            source.collect_block(1_001)
+        else {
+            panic!("expected the block file to be quarantined")
+        };
+        assert_eq!(quarantined, s3_block_path(quarantine_dir.path(), 1_001));
+        assert_eq!(std::fs::read(quarantined).unwrap(), b"truncated");
+        assert!(!path.exists());
+        // The block is missing until an intact copy shows up
+        assert!(matches!(source.collect_block(1_001), Ok(None)));
+    }
+
+    #[test]
+    fn written_block_files_are_collected() {
+        let dir = tempfile::tempdir().unwrap();
+        let block = BlockAndReceipts {
+            read_precompile_calls: vec![(Address::with_last_byte(0x80), vec![])],
+            highest_precompile_address: Some(Address::with_last_byte(0x80)),
+            ..test_utils::block(1_001)
+        };
+
+        let path = write_block_file(dir.path(), &block).unwrap();
+        assert_eq!(path, s3_block_path(dir.path(), 1_001));
+
+        let source = S3BlockSource::new(dir.path());
+        assert_eq!(source.find_latest_block_number(), Some(1_001));
+        let collected = source.collect_block(1_001).unwrap().unwrap();
+        assert_eq!(collected.number(), 1_001);
+        assert_eq!(collected.read_precompile_calls.len(), 1);
+        assert_eq!(collected.highest_precompile_address, block.highest_precompile_address);
+    }
+}
@@ -0,0 +1,266 @@
+//! Actions sent from the EVM to HyperCore through the CoreWriter contract.
+//!
+//! Contracts call `sendRawAction(bytes)` on CoreWriter, which emits
+//! `RawAction(address indexed user, bytes data)`. The action is encoded as a version byte, a
+//! 3-byte big-endian action id and the ABI-encoded parameters of the action, as documented in
+//! the Hyperliquid docs.
+
+use alloy_consensus::TxReceipt;
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{address, b256, Address, Bytes, Log, B256};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::precompile_abi::{named_fields, tuple_type, AbiFields};
+
+/// Address of the CoreWriter contract.
+pub const CORE_WRITER_ADDRESS: Address = address!("3333333333333333333333333333333333333333");
+
+/// Topic of the `RawAction(address,bytes)` event of the CoreWriter contract.
+pub const RAW_ACTION_TOPIC: B256 =
+    b256!("8c7f585fb295f7eb1e6aeb8fba61b23a4fe60beda405f0045073b185c74412e3");
+
+/// Only known version of the action encoding.
+pub const CORE_WRITER_ACTION_VERSION: u8 = 1;
+
+/// ABI of a CoreWriter action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoreWriterActionAbi {
+    pub id: u32,
+    /// Name of the action in the Hyperliquid docs.
+    pub name: &'static str,
+    pub params: AbiFields,
+}
+
+/// ABIs of the CoreWriter actions of version [`CORE_WRITER_ACTION_VERSION`].
+pub const CORE_WRITER_ACTIONS: &[CoreWriterActionAbi] = &[
+    CoreWriterActionAbi {
+        id: 1,
+        name: "limitOrder",
+        params: &[
+            ("asset", "uint32"),
+            ("isBuy", "bool"),
+            ("limitPx", "uint64"),
+            ("sz", "uint64"),
+            ("reduceOnly", "bool"),
+            ("encodedTif", "uint8"),
+            ("cloid", "uint128"),
+        ],
+    },
+    CoreWriterActionAbi {
+        id: 2,
+        name: "vaultTransfer",
+        params: &[("vault", "address"), ("isDeposit", "bool"), ("usd", "uint64")],
+    },
+    CoreWriterActionAbi {
+        id: 3,
+        name: "tokenDelegate",
+        params: &[("validator", "address"), ("wei", "uint64"), ("isUndelegate", "bool")],
+    },
+    CoreWriterActionAbi { id: 4, name: "stakingDeposit", params: &[("wei", "uint64")] },
+    CoreWriterActionAbi { id: 5, name: "stakingWithdraw", params: &[("wei", "uint64")] },
+    CoreWriterActionAbi {
+        id: 6,
+        name: "spotSend",
+        params: &[("destination", "address"), ("token", "uint64"), ("wei", "uint64")],
+    },
+    CoreWriterActionAbi {
+        id: 7,
+        name: "usdClassTransfer",
+        params: &[("ntl", "uint64"), ("toPerp", "bool")],
+    },
+    CoreWriterActionAbi {
+        id: 8,
+        name: "finalizeEvmContract",
+        params: &[
+            ("token", "uint64"),
+            ("encodedFinalizeEvmContractVariant", "uint8"),
+            ("createNonce", "uint64"),
+        ],
+    },
+    CoreWriterActionAbi {
+        id: 9,
+        name: "addApiWallet",
+        params: &[("apiWalletAddress", "address"), ("apiWalletName", "string")],
+    },
+    CoreWriterActionAbi {
+        id: 10,
+        name: "cancelOrderByOid",
+        params: &[("asset", "uint32"), ("oid", "uint64")],
+    },
+    CoreWriterActionAbi {
+        id: 11,
+        name: "cancelOrderByCloid",
+        params: &[("asset", "uint32"), ("cloid", "uint128")],
+    },
+];
+
+/// Returns the ABI of the action with the given id.
+pub fn core_writer_action_abi(id: u32) -> Option<&'static CoreWriterActionAbi> {
+    CORE_WRITER_ACTIONS.iter().find(|abi| abi.id == id)
+}
+
+/// An action emitted by the CoreWriter contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawAction {
+    /// Address that called CoreWriter.
+    pub sender: Address,
+    /// Id of the action.
+    pub action_id: u32,
+    /// Encoded action, including the version and the action id.
+    pub data: Bytes,
+}
+
+/// Returns the action emitted by `log` if it is a `RawAction` event of the CoreWriter contract
+/// carrying at least a version and an action id.
+pub fn raw_action(log: &Log) -> Option<RawAction> {
+    if log.address != CORE_WRITER_ADDRESS {
+        return None
+    }
+    let [topic, sender] = log.topics() else { return None };
+    if *topic != RAW_ACTION_TOPIC {
+        return None
+    }
+
+    let DynSolValue::Bytes(data) = DynSolType::Bytes.abi_decode(&log.data.data).ok()? else {
+        return None
+    };
+    let [_, id @ ..] = <[u8; 4]>::try_from(data.get(..4)?).ok()?;
+    Some(RawAction {
+        sender: Address::from_word(*sender),
+        action_id: u32::from_be_bytes([0, id[0], id[1], id[2]]),
+        data: data.into(),
+    })
+}
+
+/// Returns the actions emitted in a block with the index of their transaction and of their log in
+/// the block, given the receipts of the block.
+pub fn block_raw_actions<'a, R: TxReceipt<Log = Log> + 'a>(
+    receipts: impl IntoIterator<Item = &'a R>,
+) -> impl Iterator<Item = (u64, u64, RawAction)> {
+    receipts
+        .into_iter()
+        .enumerate()
+        .flat_map(|(tx_index, receipt)| receipt.logs().iter().map(move |log| (tx_index, log)))
+        .enumerate()
+        .filter_map(|(log_index, (tx_index, log))| {
+            Some((tx_index as u64, log_index as u64, raw_action(log)?))
+        })
+}
+
+/// A CoreWriter action decoded with the ABI of the action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedCoreWriterAction {
+    pub version: u8,
+    pub action_id: u32,
+    /// Name of the action, if the action id is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Parameters by name, if the version and action id are known and the payload matches the
+    /// ABI of the action.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub params: Option<Value>,
+}
+
+/// Decodes an action sent to CoreWriter.
+///
+/// Returns `None` if `data` is too short to hold a version and an action id.
+pub fn decode_core_writer_action(data: &[u8]) -> Option<DecodedCoreWriterAction> {
+    let [version, id @ ..] = <[u8; 4]>::try_from(data.get(..4)?).ok()?;
+    let action_id = u32::from_be_bytes([0, id[0], id[1], id[2]]);
+    let abi = core_writer_action_abi(action_id).filter(|_| version == CORE_WRITER_ACTION_VERSION);
+
+    let params = abi.and_then(|abi| {
+        let DynSolValue::Tuple(values) =
+            tuple_type(abi.params)?.abi_decode_params(&data[4..]).ok()?
+        else {
+            return None
+        };
+        Some(named_fields(abi.params, values))
+    });
+    Some(DecodedCoreWriterAction {
+        version,
+        action_id,
+        name: abi.map(|abi| abi.name.to_string()),
+        params,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Receipt;
+    use alloy_primitives::{LogData, U256};
+    use serde_json::json;
+
+    fn action(version: u8, id: u32, params: Vec<DynSolValue>) -> Vec<u8> {
+        let mut data = vec![version];
+        data.extend_from_slice(&id.to_be_bytes()[1..]);
+        data.extend_from_slice(&DynSolValue::Tuple(params).abi_encode_params());
+        data
+    }
+
+    #[test]
+    fn decodes_raw_actions() {
+        let sender = Address::with_last_byte(0x11);
+        let vault = Address::with_last_byte(0x22);
+        let data = action(
+            1,
+            2,
+            vec![
+                DynSolValue::Address(vault),
+                DynSolValue::Bool(true),
+                DynSolValue::Uint(U256::from(1_000_000), 64),
+            ],
+        );
+        let log = Log {
+            address: CORE_WRITER_ADDRESS,
+            data: LogData::new_unchecked(
+                vec![RAW_ACTION_TOPIC, sender.into_word()],
+                DynSolValue::Bytes(data.clone()).abi_encode(),
+            ),
+        };
+
+        let action = raw_action(&log).unwrap();
+        assert_eq!(action, RawAction { sender, action_id: 2, data: data.into() });
+        assert_eq!(
+            decode_core_writer_action(&action.data),
+            Some(DecodedCoreWriterAction {
+                version: 1,
+                action_id: 2,
+                name: Some("vaultTransfer".to_string()),
+                params: Some(json!({
+                    "vault": vault.to_string(),
+                    "isDeposit": true,
+                    "usd": 1_000_000
+                })),
+            })
+        );
+
+        let other = Log { address: Address::with_last_byte(0x33), data: log.data.clone() };
+        assert_eq!(raw_action(&other), None);
+
+        let receipts = [
+            Receipt { logs: vec![log.clone()], ..Default::default() },
+            Receipt { logs: vec![other, log], ..Default::default() },
+        ];
+        assert_eq!(
+            block_raw_actions(&receipts)
+                .map(|(tx_index, log_index, _)| (tx_index, log_index))
+                .collect::<Vec<_>>(),
+            vec![(0, 0), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn keeps_unknown_actions() {
+        let unknown = decode_core_writer_action(&action(1, 0x01_0000, vec![])).unwrap();
+        assert_eq!((unknown.action_id, unknown.name, unknown.params), (0x01_0000, None, None));
+
+        let version = decode_core_writer_action(&action(2, 4, vec![])).unwrap();
+        assert_eq!((version.version, version.name, version.params), (2, None, None));
+
+        assert_eq!(decode_core_writer_action(&[1, 0, 0]), None);
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use crate::serialized::SystemTxError;
+
+/// Errors when ingesting Hyperliquid EVM blocks.
+///
+/// Transient errors are retried with a backoff, see [`IngestError::is_transient`]. Any other error
+/// halts block ingestion at the affected height.
+#[derive(Debug, thiserror::Error)]
+pub enum IngestError {
+    /// Reading the block file failed.
+    #[error("failed to read block {height} from {path:?}: {source}")]
+    Io {
+        height: u64,
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    /// The block file could not be decoded.
+    #[error("failed to decode block {height}: {reason}")]
+    CorruptBlock {
+        height: u64,
+        reason: String,
+        /// Path the undecodable file was moved to, if it was quarantined.
+        quarantined: Option<PathBuf>,
+    },
+    /// Fetching the block from a remote source failed.
+    #[error("failed to fetch block {height}: {reason}")]
+    Fetch { height: u64, reason: String },
+    /// The system transactions of the block could not be converted.
+    #[error("invalid system transaction in block {height}: {source}")]
+    SystemTx {
+        height: u64,
+        #[source]
+        source: SystemTxError,
+    },
+    /// The engine API could not be reached.
+    #[error("engine API request for block {height} failed: {reason}")]
+    Engine { height: u64, reason: String },
+    /// The engine API did not accept the block.
+    #[error("block {height} was not accepted by the engine API: {status}")]
+    InvalidPayload { height: u64, status: String },
+    /// The executed receipts of the block diverge from the ingested ones.
+    #[error("receipts of block {height} diverge from the ingested receipts")]
+    ReceiptDivergence { height: u64 },
+    /// Reading from the database failed.
+    #[error("database error at block {height}: {reason}")]
+    Database { height: u64, reason: String },
+}
+
+impl IngestError {
+    /// Returns the height of the block the error occurred at.
+    pub const fn height(&self) -> u64 {
+        match self {
+            Self::Io { height, .. } |
+            Self::CorruptBlock { height, .. } |
+            Self::Fetch { height, .. } |
+            Self::SystemTx { height, .. } |
+            Self::Engine { height, .. } |
+            Self::InvalidPayload { height, .. } |
+            Self::ReceiptDivergence { height } |
+            Self::Database { height, .. } => *height,
+        }
+    }
+
+    /// Returns `true` if the error may resolve itself, so the operation should be retried.
+    ///
+    /// Corrupt block files are retried as well, since another source or a re-synced file may
+    /// provide an intact copy of the block.
+    pub const fn is_transient(&self) -> bool {
+        match self {
+            Self::Io { .. } |
+            Self::CorruptBlock { .. } |
+            Self::Fetch { .. } |
+            Self::Engine { .. } => true,
+            Self::SystemTx { .. } |
+            Self::InvalidPayload { .. } |
+            Self::ReceiptDivergence { .. } |
+            Self::Database { .. } => false,
+        }
+    }
+}
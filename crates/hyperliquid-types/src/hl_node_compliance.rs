@@ -0,0 +1,135 @@
+//! Whether RPC responses follow hl-node, i.e. hide the system transactions and their logs.
+//!
+//! The mode is selected per request: requests served within [`with_hl_node_compliance`] use the
+//! given mode, all other requests use the node default set by
+//! [`set_default_hl_node_compliance`].
+//!
+//! System transactions precede the user transactions of a block. In hl-node compliant mode they
+//! are hidden, and the transaction and log indices of the user transactions are renumbered as if
+//! the block had no system transactions.
+
+use std::{
+    future::Future,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use alloy_consensus::TxReceipt;
+use reth_primitives_traits::SignedTransaction;
+use tokio::task::futures::TaskLocalFuture;
+
+tokio::task_local! {
+    static HL_NODE_COMPLIANT: bool;
+}
+
+static DEFAULT_HL_NODE_COMPLIANT: AtomicBool = AtomicBool::new(false);
+
+/// Sets the mode of requests that are not served within [`with_hl_node_compliance`].
+pub fn set_default_hl_node_compliance(enabled: bool) {
+    DEFAULT_HL_NODE_COMPLIANT.store(enabled, Ordering::Relaxed);
+}
+
+/// Returns `true` if the current request is served in hl-node compliant mode.
+pub fn is_hl_node_compliant() -> bool {
+    HL_NODE_COMPLIANT
+        .try_with(|enabled| *enabled)
+        .unwrap_or_else(|_| DEFAULT_HL_NODE_COMPLIANT.load(Ordering::Relaxed))
+}
+
+/// Runs `fut` with hl-node compliant mode `enabled`.
+///
+/// The mode does not carry over to tasks spawned by `fut`.
+pub fn with_hl_node_compliance<F: Future>(enabled: bool, fut: F) -> TaskLocalFuture<bool, F> {
+    HL_NODE_COMPLIANT.scope(enabled, fut)
+}
+
+/// Returns `true` if `tx` is a system transaction.
+pub fn is_system_tx<T: SignedTransaction>(tx: &T) -> bool {
+    tx.system_tx_sender().is_some()
+}
+
+/// Returns the number of system transactions at the start of a block.
+pub fn system_tx_count<'a, T: SignedTransaction + 'a>(
+    transactions: impl IntoIterator<Item = &'a T>,
+) -> usize {
+    transactions.into_iter().take_while(|tx| is_system_tx(*tx)).count()
+}
+
+/// Returns the number of receipts of system transactions at the start of a block.
+///
+/// System transactions use no gas, unlike any user transaction.
+pub fn system_receipt_count<'a, R: TxReceipt + 'a>(
+    receipts: impl IntoIterator<Item = &'a R>,
+) -> usize {
+    receipts.into_iter().take_while(|receipt| receipt.cumulative_gas_used() == 0).count()
+}
+
+/// Returns `true` if `tx` is hidden from the current request.
+pub fn is_hidden_tx<T: SignedTransaction>(tx: &T) -> bool {
+    is_hl_node_compliant() && is_system_tx(tx)
+}
+
+/// Returns the number of transactions at the start of a block that are hidden from the current
+/// request.
+pub fn hidden_tx_count<'a, T: SignedTransaction + 'a>(
+    transactions: impl IntoIterator<Item = &'a T>,
+) -> usize {
+    if is_hl_node_compliant() {
+        system_tx_count(transactions)
+    } else {
+        0
+    }
+}
+
+/// Returns the number of receipts at the start of a block whose transactions are hidden from the
+/// current request.
+pub fn hidden_receipt_count<'a, R: TxReceipt + 'a>(
+    receipts: impl IntoIterator<Item = &'a R>,
+) -> usize {
+    if is_hl_node_compliant() {
+        system_receipt_count(receipts)
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::{Receipt, TxLegacy};
+    use alloy_primitives::{Log, PrimitiveSignature};
+    use reth_primitives::{TransactionSigned, TxSystem};
+
+    #[tokio::test]
+    async fn scoped_mode_overrides_default() {
+        assert!(!is_hl_node_compliant());
+        assert!(with_hl_node_compliance(true, async { is_hl_node_compliant() }).await);
+        assert!(!is_hl_node_compliant());
+    }
+
+    #[tokio::test]
+    async fn counts_hidden_system_transactions() {
+        let user_tx = TransactionSigned::new_unhashed(
+            TxLegacy::default().into(),
+            PrimitiveSignature::test_signature(),
+        );
+        let txs =
+            [TransactionSigned::from(TxSystem::default()), user_tx, TxSystem::default().into()];
+        let receipts = [
+            Receipt::<Log>::default(),
+            Receipt { cumulative_gas_used: 21_000, ..Default::default() },
+            Receipt { cumulative_gas_used: 21_000, ..Default::default() },
+        ];
+        assert_eq!(system_tx_count(&txs), 1);
+        assert_eq!(system_receipt_count(&receipts), 1);
+        assert_eq!(hidden_tx_count(&txs), 0);
+        assert_eq!(hidden_receipt_count(&receipts), 0);
+
+        with_hl_node_compliance(true, async {
+            assert_eq!(hidden_tx_count(&txs), 1);
+            assert_eq!(hidden_receipt_count(&receipts), 1);
+            assert!(is_hidden_tx(&txs[0]));
+            assert!(!is_hidden_tx(&txs[1]));
+        })
+        .await;
+    }
+}
@@ -4,6 +4,42 @@ use alloy_primitives::{Address, Bytes};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
 
+pub mod block_source;
+pub mod core_writer;
+pub mod error;
+pub mod hl_node_compliance;
+pub mod precompile_abi;
+pub mod precompile_store;
+pub mod precompile_unavailable;
+pub mod serialized;
+pub mod spot_meta;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+
+pub use block_source::{BlockSource, BlockSourceBoxed, FallbackBlockSource, S3BlockSource};
+pub use core_writer::{
+    block_raw_actions, core_writer_action_abi, decode_core_writer_action, raw_action,
+    DecodedCoreWriterAction, RawAction, CORE_WRITER_ADDRESS,
+};
+pub use error::IngestError;
+pub use hl_node_compliance::{
+    hidden_receipt_count, hidden_tx_count, is_hidden_tx, is_hl_node_compliant, is_system_tx,
+    set_default_hl_node_compliance, system_receipt_count, system_tx_count,
+    with_hl_node_compliance,
+};
+pub use precompile_abi::{decode_precompile_call, precompile_abi, DecodedPrecompileCall};
+pub use precompile_store::{PrecompileDataStore, PrecompileDataStoreBoxed};
+pub use precompile_unavailable::{
+    MissingPrecompileDataPolicy, PrecompileDataUnavailable, PRECOMPILE_DATA_UNAVAILABLE_CODE,
+};
+pub use serialized::{
+    BlockAndReceipts, EvmBlock, LegacyReceipt, LegacyTxType, SystemTx, SystemTxError,
+};
+pub use spot_meta::{
+    read_spot_meta_snapshot, seal_block_with_system_txs, SpotId, SpotTokenStore,
+    SpotTokenStoreBoxed, SpotTokens,
+};
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash)]
 pub struct ReadPrecompileInput {
     pub input: Bytes,
@@ -18,7 +54,7 @@ pub enum ReadPrecompileResult {
     UnexpectedError,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PrecompileData {
     pub precompiles: Vec<(Address, Vec<(ReadPrecompileInput, ReadPrecompileResult)>)>,
     pub highest_precompile_address: Option<Address>,
@@ -0,0 +1,404 @@
+//! ABIs of the read precompiles, used to decode their calls in traces.
+//!
+//! The read precompiles take the ABI-encoded arguments without a function selector and return
+//! the ABI-encoded result, as declared in `L1Read.sol` of the Hyperliquid docs.
+
+use alloy_dyn_abi::{DynSolType, DynSolValue};
+use alloy_primitives::{address, hex, Address};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Parameters or struct fields as `(name, type)` pairs.
+pub type AbiFields = &'static [(&'static str, &'static str)];
+
+/// Result type of a read precompile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrecompileOutput {
+    /// A single value of the given type.
+    Value(&'static str),
+    /// A struct with the given fields.
+    Struct(AbiFields),
+    /// An array of structs with the given fields.
+    StructArray(AbiFields),
+}
+
+/// ABI of a read precompile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrecompileAbi {
+    /// Name of the function in `L1Read.sol`.
+    pub name: &'static str,
+    pub inputs: AbiFields,
+    pub output: PrecompileOutput,
+}
+
+/// ABI of a read precompile from the block it activates at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrecompileAbiVersion {
+    pub address: Address,
+    pub activation_block: u64,
+    pub abi: PrecompileAbi,
+}
+
+const fn version(
+    address: Address,
+    activation_block: u64,
+    name: &'static str,
+    inputs: AbiFields,
+    output: PrecompileOutput,
+) -> PrecompileAbiVersion {
+    PrecompileAbiVersion { address, activation_block, abi: PrecompileAbi { name, inputs, output } }
+}
+
+/// ABIs of the read precompiles.
+///
+/// A precompile whose ABI changes gets another entry activating at the block of the change.
+pub const PRECOMPILE_ABIS: &[PrecompileAbiVersion] = &[
+    version(
+        address!("0000000000000000000000000000000000000800"),
+        0,
+        "position",
+        &[("user", "address"), ("perp", "uint16")],
+        PrecompileOutput::Struct(&[
+            ("szi", "int64"),
+            ("entryNtl", "uint64"),
+            ("isolatedRawUsd", "int64"),
+            ("leverage", "uint32"),
+            ("isIsolated", "bool"),
+        ]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000801"),
+        0,
+        "spotBalance",
+        &[("user", "address"), ("token", "uint64")],
+        PrecompileOutput::Struct(&[
+            ("total", "uint64"),
+            ("hold", "uint64"),
+            ("entryNtl", "uint64"),
+        ]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000802"),
+        0,
+        "userVaultEquity",
+        &[("user", "address"), ("vault", "address")],
+        PrecompileOutput::Struct(&[("equity", "uint64"), ("lockedUntilTimestamp", "uint64")]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000803"),
+        0,
+        "withdrawable",
+        &[("user", "address")],
+        PrecompileOutput::Struct(&[("withdrawable", "uint64")]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000804"),
+        0,
+        "delegations",
+        &[("user", "address")],
+        PrecompileOutput::StructArray(&[
+            ("validator", "address"),
+            ("amount", "uint64"),
+            ("lockedUntilTimestamp", "uint64"),
+        ]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000805"),
+        0,
+        "delegatorSummary",
+        &[("user", "address")],
+        PrecompileOutput::Struct(&[
+            ("delegated", "uint64"),
+            ("undelegated", "uint64"),
+            ("totalPendingWithdrawal", "uint64"),
+            ("nPendingWithdrawals", "uint64"),
+        ]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000806"),
+        0,
+        "markPx",
+        &[("index", "uint32")],
+        PrecompileOutput::Value("uint64"),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000807"),
+        0,
+        "oraclePx",
+        &[("index", "uint32")],
+        PrecompileOutput::Value("uint64"),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000808"),
+        0,
+        "spotPx",
+        &[("index", "uint32")],
+        PrecompileOutput::Value("uint64"),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000809"),
+        0,
+        "l1BlockNumber",
+        &[],
+        PrecompileOutput::Value("uint64"),
+    ),
+    version(
+        address!("000000000000000000000000000000000000080a"),
+        0,
+        "perpAssetInfo",
+        &[("perp", "uint32")],
+        PrecompileOutput::Struct(&[
+            ("coin", "string"),
+            ("marginTableId", "uint32"),
+            ("szDecimals", "uint8"),
+            ("maxLeverage", "uint8"),
+            ("onlyIsolated", "bool"),
+        ]),
+    ),
+    version(
+        address!("000000000000000000000000000000000000080b"),
+        0,
+        "spotInfo",
+        &[("spot", "uint32")],
+        PrecompileOutput::Struct(&[("name", "string"), ("tokens", "uint64[2]")]),
+    ),
+    version(
+        address!("000000000000000000000000000000000000080c"),
+        0,
+        "tokenInfo",
+        &[("token", "uint32")],
+        PrecompileOutput::Struct(&[
+            ("name", "string"),
+            ("spots", "uint64[]"),
+            ("deployerTradingFeeShare", "uint64"),
+            ("deployer", "address"),
+            ("evmContract", "address"),
+            ("szDecimals", "uint8"),
+            ("weiDecimals", "uint8"),
+            ("evmExtraWeiDecimals", "int8"),
+        ]),
+    ),
+    version(
+        address!("000000000000000000000000000000000000080d"),
+        0,
+        "tokenSupply",
+        &[("token", "uint32")],
+        PrecompileOutput::Struct(&[
+            ("maxSupply", "uint64"),
+            ("totalSupply", "uint64"),
+            ("circulatingSupply", "uint64"),
+            ("futureEmissions", "uint64"),
+            ("nonCirculatingUserBalances", "(address,uint64)[]"),
+        ]),
+    ),
+    version(
+        address!("000000000000000000000000000000000000080e"),
+        0,
+        "bbo",
+        &[("asset", "uint32")],
+        PrecompileOutput::Struct(&[("bid", "uint64"), ("ask", "uint64")]),
+    ),
+    version(
+        address!("000000000000000000000000000000000000080f"),
+        0,
+        "accountMarginSummary",
+        &[("perpDexIndex", "uint32"), ("user", "address")],
+        PrecompileOutput::Struct(&[
+            ("accountValue", "int64"),
+            ("marginUsed", "uint64"),
+            ("ntlPos", "uint64"),
+            ("rawUsd", "int64"),
+        ]),
+    ),
+    version(
+        address!("0000000000000000000000000000000000000810"),
+        0,
+        "coreUserExists",
+        &[("user", "address")],
+        PrecompileOutput::Struct(&[("exists", "bool")]),
+    ),
+];
+
+/// Returns the ABI of the read precompile at `address` that is active at `block`.
+pub fn precompile_abi(address: Address, block: u64) -> Option<&'static PrecompileAbi> {
+    find_abi(PRECOMPILE_ABIS, address, block)
+}
+
+fn find_abi(
+    table: &[PrecompileAbiVersion],
+    address: Address,
+    block: u64,
+) -> Option<&PrecompileAbi> {
+    table
+        .iter()
+        .filter(|version| version.address == address && version.activation_block <= block)
+        .max_by_key(|version| version.activation_block)
+        .map(|version| &version.abi)
+}
+
+/// A read precompile call decoded with the ABI of the precompile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DecodedPrecompileCall {
+    /// Name of the function in `L1Read.sol`.
+    pub name: String,
+    /// Arguments by name, if the input matches the ABI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input: Option<Value>,
+    /// Result, if the call succeeded and the output matches the ABI.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Value>,
+}
+
+/// Decodes a call to the read precompile at `address` made in `block`.
+///
+/// Returns `None` if `address` is not a read precompile with a known ABI at `block`.
+pub fn decode_precompile_call(
+    address: Address,
+    block: u64,
+    input: &[u8],
+    output: Option<&[u8]>,
+) -> Option<DecodedPrecompileCall> {
+    let abi = precompile_abi(address, block)?;
+    Some(DecodedPrecompileCall {
+        name: abi.name.to_string(),
+        input: abi.decode_input(input),
+        output: output.and_then(|output| abi.decode_output(output)),
+    })
+}
+
+impl PrecompileAbi {
+    /// Decodes the arguments of a call into an object keyed by parameter name.
+    pub fn decode_input(&self, input: &[u8]) -> Option<Value> {
+        let DynSolValue::Tuple(values) = tuple_type(self.inputs)?.abi_decode_params(input).ok()?
+        else {
+            return None
+        };
+        Some(named_fields(self.inputs, values))
+    }
+
+    /// Decodes the result of a call.
+    pub fn decode_output(&self, output: &[u8]) -> Option<Value> {
+        match self.output {
+            PrecompileOutput::Value(ty) => {
+                let value = DynSolType::parse(ty).ok()?.abi_decode(output).ok()?;
+                Some(to_json(value))
+            }
+            PrecompileOutput::Struct(fields) => {
+                let DynSolValue::Tuple(values) = tuple_type(fields)?.abi_decode(output).ok()?
+                else {
+                    return None
+                };
+                Some(named_fields(fields, values))
+            }
+            PrecompileOutput::StructArray(fields) => {
+                let ty = DynSolType::Array(Box::new(tuple_type(fields)?));
+                let DynSolValue::Array(items) = ty.abi_decode(output).ok()? else { return None };
+                let items = items
+                    .into_iter()
+                    .map(|item| match item {
+                        DynSolValue::Tuple(values) => named_fields(fields, values),
+                        item => to_json(item),
+                    })
+                    .collect();
+                Some(Value::Array(items))
+            }
+        }
+    }
+}
+
+pub(crate) fn tuple_type(fields: AbiFields) -> Option<DynSolType> {
+    let types = fields.iter().map(|(_, ty)| DynSolType::parse(ty).ok()).collect::<Option<_>>()?;
+    Some(DynSolType::Tuple(types))
+}
+
+pub(crate) fn named_fields(fields: AbiFields, values: Vec<DynSolValue>) -> Value {
+    let object: Map<_, _> = fields
+        .iter()
+        .zip(values)
+        .map(|((name, _), value)| (name.to_string(), to_json(value)))
+        .collect();
+    Value::Object(object)
+}
+
+/// Converts a decoded value to JSON. Integers that fit into 64 bits are numbers, larger ones are
+/// decimal strings.
+fn to_json(value: DynSolValue) -> Value {
+    match value {
+        DynSolValue::Bool(value) => Value::Bool(value),
+        DynSolValue::Int(value, _) => match i64::try_from(value) {
+            Ok(value) => value.into(),
+            Err(_) => value.to_string().into(),
+        },
+        DynSolValue::Uint(value, _) => match u64::try_from(value) {
+            Ok(value) => value.into(),
+            Err(_) => value.to_string().into(),
+        },
+        DynSolValue::Address(address) => address.to_string().into(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..size]).into(),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes).into(),
+        DynSolValue::String(value) => value.into(),
+        DynSolValue::Array(values)
+        | DynSolValue::FixedArray(values)
+        | DynSolValue::Tuple(values) => values.into_iter().map(to_json).collect(),
+        value => hex::encode_prefixed(value.abi_encode()).into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::U256;
+    use serde_json::json;
+
+    #[test]
+    fn decodes_calls() {
+        let user = Address::with_last_byte(0x11);
+        let input = DynSolValue::Tuple(vec![
+            DynSolValue::Address(user),
+            DynSolValue::Uint(U256::from(3), 16),
+        ])
+        .abi_encode_params();
+        let output = DynSolValue::Tuple(vec![
+            DynSolValue::String("BTC".to_string()),
+            DynSolValue::Uint(U256::from(1), 32),
+            DynSolValue::Uint(U256::from(5), 8),
+            DynSolValue::Uint(U256::from(40), 8),
+            DynSolValue::Bool(false),
+        ])
+        .abi_encode();
+
+        let position = address!("0000000000000000000000000000000000000800");
+        let decoded = decode_precompile_call(position, 1, &input, None).unwrap();
+        assert_eq!(decoded.name, "position");
+        assert_eq!(decoded.input, Some(json!({ "user": user.to_string(), "perp": 3 })));
+
+        let perp_asset_info = address!("000000000000000000000000000000000000080a");
+        let abi = precompile_abi(perp_asset_info, 1).unwrap();
+        assert_eq!(
+            abi.decode_output(&output),
+            Some(json!({
+                "coin": "BTC",
+                "marginTableId": 1,
+                "szDecimals": 5,
+                "maxLeverage": 40,
+                "onlyIsolated": false
+            }))
+        );
+        assert_eq!(abi.decode_output(&[1, 2, 3]), None);
+
+        assert!(decode_precompile_call(Address::with_last_byte(0x01), 1, &input, None).is_none());
+    }
+
+    #[test]
+    fn selects_active_version() {
+        let address = address!("0000000000000000000000000000000000000806");
+        let table = [
+            version(address, 0, "v1", &[], PrecompileOutput::Value("uint64")),
+            version(address, 100, "v2", &[], PrecompileOutput::Value("uint64")),
+        ];
+        assert_eq!(find_abi(&table, address, 99).unwrap().name, "v1");
+        assert_eq!(find_abi(&table, address, 100).unwrap().name, "v2");
+        assert!(find_abi(&table, Address::ZERO, 100).is_none());
+    }
+}
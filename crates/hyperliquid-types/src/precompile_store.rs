@@ -0,0 +1,56 @@
+use std::{fmt::Debug, sync::Arc};
+
+use crate::PrecompileData;
+
+/// Persistent store of the read precompile calls of imported blocks.
+///
+/// Blocks can only be executed with the read precompile calls hl-node recorded for them, so they
+/// are stored along with the blocks to re-execute them later on, e.g. for tracing.
+pub trait PrecompileDataStore: Send + Sync + Debug {
+    /// Returns the read precompile calls of the block at `height`, if stored.
+    fn precompile_data(&self, height: u64) -> eyre::Result<Option<PrecompileData>>;
+
+    /// Stores the read precompile calls of the given blocks, replacing existing entries.
+    fn insert_precompile_data(&self, blocks: &[(u64, PrecompileData)]) -> eyre::Result<()>;
+}
+
+/// Shared [`PrecompileDataStore`].
+pub type PrecompileDataStoreBoxed = Arc<dyn PrecompileDataStore>;
+
+impl PrecompileData {
+    /// Encodes the read precompile calls as MessagePack, the format they are stored in.
+    pub fn encode(&self) -> Vec<u8> {
+        rmp_serde::to_vec(self).expect("read precompile calls are serializable")
+    }
+
+    /// Decodes read precompile calls encoded with [`PrecompileData::encode`].
+    pub fn decode(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ReadPrecompileInput, ReadPrecompileResult};
+    use alloy_primitives::{Address, Bytes};
+
+    #[test]
+    fn precompile_data_roundtrip() {
+        let input = ReadPrecompileInput { input: Bytes::from_static(&[1, 2]), gas_limit: 30_000 };
+        let result = ReadPrecompileResult::Ok { gas_used: 100, bytes: Bytes::from_static(&[3]) };
+        let data = PrecompileData {
+            precompiles: vec![(Address::with_last_byte(0x01), vec![(input.clone(), result)])],
+            highest_precompile_address: Some(Address::with_last_byte(0x0d)),
+        };
+
+        let decoded = PrecompileData::decode(&data.encode()).unwrap();
+        assert_eq!(decoded.highest_precompile_address, data.highest_precompile_address);
+        let [(address, calls)] = decoded.precompiles.as_slice() else {
+            panic!("expected one precompile")
+        };
+        assert_eq!(*address, Address::with_last_byte(0x01));
+        assert_eq!(calls[0].0, input);
+        assert!(matches!(calls[0].1, ReadPrecompileResult::Ok { gas_used: 100, .. }));
+    }
+}
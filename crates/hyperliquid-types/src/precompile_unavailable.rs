@@ -0,0 +1,102 @@
+use std::{cell::Cell, fmt, str::FromStr};
+
+/// JSON-RPC error code of [`PrecompileDataUnavailable`], the "resource unavailable" code of
+/// EIP-1474.
+pub const PRECOMPILE_DATA_UNAVAILABLE_CODE: i32 = -32002;
+
+/// The read precompile calls of a block are not available, so calls to the read precompiles
+/// cannot be replayed.
+///
+/// This is the case for blocks hl-node has not produced yet, e.g. when calling against `pending`
+/// or against the latest block before its file is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("precompile data unavailable for block {height}")]
+pub struct PrecompileDataUnavailable {
+    pub height: u64,
+}
+
+thread_local! {
+    /// The error last reported by a read precompile of this thread, until it is taken.
+    static REPORTED: Cell<Option<PrecompileDataUnavailable>> = const { Cell::new(None) };
+}
+
+impl PrecompileDataUnavailable {
+    /// Reports the error to the caller of the EVM running on this thread.
+    ///
+    /// The EVM only passes precompile errors on as strings, so the read precompile reports the
+    /// error here before failing, and the caller takes it back with [`Self::take_reported`] once
+    /// the call returned.
+    pub fn report(self) {
+        REPORTED.with(|reported| reported.set(Some(self)));
+    }
+
+    /// Takes the error last reported on this thread, if any.
+    pub fn take_reported() -> Option<Self> {
+        REPORTED.with(Cell::take)
+    }
+}
+
+/// What to do when a call reaches a read precompile of a block whose read precompile calls are
+/// not available.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MissingPrecompileDataPolicy {
+    /// Fail the call with [`PrecompileDataUnavailable`].
+    #[default]
+    Error,
+    /// Fail the read precompile call, as hl-node does for inputs it has no result for, so that
+    /// the call usually reverts.
+    Revert,
+    /// Fail the call with [`PrecompileDataUnavailable`] in the EVM, and forward `eth_call` and
+    /// `eth_estimateGas` to the upstream RPC instead.
+    Forward,
+}
+
+impl fmt::Display for MissingPrecompileDataPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => f.write_str("error"),
+            Self::Revert => f.write_str("revert"),
+            Self::Forward => f.write_str("forward"),
+        }
+    }
+}
+
+impl FromStr for MissingPrecompileDataPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Self::Error),
+            "revert" => Ok(Self::Revert),
+            "forward" => Ok(Self::Forward),
+            _ => Err(format!("unknown policy {s:?}, expected one of error, revert, forward")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn takes_reported_error_once() {
+        assert_eq!(PrecompileDataUnavailable::take_reported(), None);
+
+        let err = PrecompileDataUnavailable { height: 42 };
+        err.report();
+        assert_eq!(PrecompileDataUnavailable::take_reported(), Some(err));
+        assert_eq!(PrecompileDataUnavailable::take_reported(), None);
+    }
+
+    #[test]
+    fn reported_error_is_per_thread() {
+        PrecompileDataUnavailable { height: 42 }.report();
+        std::thread::spawn(|| assert_eq!(PrecompileDataUnavailable::take_reported(), None))
+            .join()
+            .unwrap();
+        assert_eq!(
+            PrecompileDataUnavailable::take_reported(),
+            Some(PrecompileDataUnavailable { height: 42 })
+        );
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use alloy_consensus::{BlockBody, BlockHeader};
+use alloy_primitives::{b256, Address, Log, TxKind, B256};
+use reth_primitives::{Receipt, SealedBlock, Transaction, TransactionSigned, TxSystem, TxType};
+use reth_primitives_traits::transaction::signed::NATIVE_TOKEN_SYSTEM_ADDRESS;
+use serde::{Deserialize, Serialize};
+
+use crate::{spot_meta::SpotId, PrecompileData, ReadPrecompileInput, ReadPrecompileResult};
+
+/// Topic of the ERC20 `Transfer(address,address,uint256)` event.
+pub(crate) const TRANSFER_EVENT_SIGNATURE: B256 =
+    b256!("ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockAndReceipts {
+    pub block: EvmBlock,
+    pub receipts: Vec<LegacyReceipt>,
+    #[serde(default)]
+    pub system_txs: Vec<SystemTx>,
+    #[serde(default)]
+    pub read_precompile_calls: Vec<(Address, Vec<(ReadPrecompileInput, ReadPrecompileResult)>)>,
+    pub highest_precompile_address: Option<Address>,
+}
+
+impl BlockAndReceipts {
+    /// Returns the number of the wrapped block.
+    pub fn number(&self) -> u64 {
+        match &self.block {
+            EvmBlock::Reth115(b) => b.header().number(),
+        }
+    }
+
+    /// Returns the read precompile calls recorded for this block.
+    pub fn precompile_data(&self) -> PrecompileData {
+        PrecompileData {
+            precompiles: self.read_precompile_calls.clone(),
+            highest_precompile_address: self.highest_precompile_address,
+        }
+    }
+
+    /// Returns the system transactions of this block, with their system senders.
+    ///
+    /// Transfers of HYPE are sent by [`NATIVE_TOKEN_SYSTEM_ADDRESS`], and calls of an ERC20
+    /// contract by the system address of its spot token, looked up in `spot_map`.
+    pub fn system_transactions(
+        &self,
+        spot_map: &BTreeMap<Address, SpotId>,
+    ) -> Result<Vec<TxSystem>, SystemTxError> {
+        self.system_txs
+            .iter()
+            .map(|transaction| {
+                let Transaction::Legacy(tx) = &transaction.tx else {
+                    return Err(SystemTxError::UnexpectedTxType(transaction.tx.tx_type()))
+                };
+                let TxKind::Call(to) = tx.to else {
+                    return Err(SystemTxError::ContractCreation)
+                };
+                let from = if tx.input.is_empty() {
+                    NATIVE_TOKEN_SYSTEM_ADDRESS
+                } else {
+                    spot_map.get(&to).ok_or(SystemTxError::UnknownSpotToken(to))?.system_address()
+                };
+                Ok(TxSystem { from, tx: tx.clone() })
+            })
+            .collect()
+    }
+
+    /// Returns the spot tokens of the ERC20 contracts called by the system transactions of this
+    /// block, derived from their receipts.
+    ///
+    /// Spot tokens are sent from their system address, which is the sender of the `Transfer`
+    /// event the ERC20 contract emits.
+    pub fn spot_tokens(&self) -> Vec<(Address, SpotId)> {
+        self.system_txs
+            .iter()
+            .filter_map(|system_tx| {
+                let Transaction::Legacy(tx) = &system_tx.tx else { return None };
+                let TxKind::Call(contract) = tx.to else { return None };
+                system_tx.receipt.as_ref()?.logs.iter().find_map(|log| {
+                    let [topic, from, _] = log.topics() else { return None };
+                    if log.address != contract || *topic != TRANSFER_EVENT_SIGNATURE {
+                        return None
+                    }
+                    let spot = SpotId::from_system_address(Address::from_word(*from))?;
+                    Some((contract, spot))
+                })
+            })
+            .collect()
+    }
+
+    /// Converts this into the block imported by the node, with the system transactions prepended
+    /// to the block transactions.
+    pub fn into_sealed_block(
+        self,
+        spot_map: &BTreeMap<Address, SpotId>,
+    ) -> Result<SealedBlock, SystemTxError> {
+        let system_txs = self.system_transactions(spot_map)?;
+        let EvmBlock::Reth115(block) = self.block;
+        let (header, BlockBody { transactions, ommers, withdrawals }) =
+            block.split_sealed_header_body();
+
+        let mut txs = system_txs.into_iter().map(TransactionSigned::from).collect::<Vec<_>>();
+        txs.extend(transactions);
+
+        Ok(SealedBlock::from_sealed_parts(
+            header,
+            BlockBody { transactions: txs, ommers, withdrawals },
+        ))
+    }
+}
+
+/// Errors when converting the system transactions of a [`BlockAndReceipts`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SystemTxError {
+    /// System transactions are expected to be legacy transactions.
+    #[error("unexpected system transaction type {0}")]
+    UnexpectedTxType(TxType),
+    /// System transactions are expected to call an existing contract.
+    #[error("unexpected contract creation in system transaction")]
+    ContractCreation,
+    /// The called ERC20 contract is not in the spot token mapping, and its spot token could not
+    /// be derived from the receipts of the system transactions.
+    #[error("contract {0} not found in spot token mapping")]
+    UnknownSpotToken(Address),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EvmBlock {
+    Reth115(SealedBlock),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegacyReceipt {
+    pub tx_type: LegacyTxType,
+    pub success: bool,
+    pub cumulative_gas_used: u64,
+    pub logs: Vec<Log>,
+}
+
+impl From<&Receipt> for LegacyReceipt {
+    fn from(receipt: &Receipt) -> Self {
+        Self {
+            tx_type: receipt.tx_type.into(),
+            success: receipt.success,
+            cumulative_gas_used: receipt.cumulative_gas_used,
+            logs: receipt.logs.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LegacyTxType {
+    Legacy = 0,
+    Eip2930 = 1,
+    Eip1559 = 2,
+    Eip4844 = 3,
+    Eip7702 = 4,
+}
+
+impl From<TxType> for LegacyTxType {
+    fn from(tx_type: TxType) -> Self {
+        match tx_type {
+            TxType::Legacy => Self::Legacy,
+            TxType::Eip2930 => Self::Eip2930,
+            TxType::Eip1559 => Self::Eip1559,
+            TxType::Eip4844 => Self::Eip4844,
+            TxType::Eip7702 => Self::Eip7702,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemTx {
+    pub tx: Transaction,
+    pub receipt: Option<LegacyReceipt>,
+}
@@ -0,0 +1,260 @@
+use alloy_primitives::Address;
+use eyre::Result;
+use reth_primitives::SealedBlock;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fmt::Debug, path::Path, sync::Arc};
+use tracing::info;
+
+use crate::{
+    error::IngestError,
+    serialized::{BlockAndReceipts, SystemTxError},
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EvmContract {
+    address: Address,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SpotToken {
+    index: u64,
+    #[serde(rename = "evmContract")]
+    evm_contract: Option<EvmContract>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SpotMeta {
+    tokens: Vec<SpotToken>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpotId {
+    pub index: u64,
+}
+
+impl SpotId {
+    /// Returns the system address of the spot token, `0x20…<index>`, which sends the system
+    /// transactions calling its ERC20 contract.
+    pub fn system_address(&self) -> Address {
+        let mut addr = [0u8; 20];
+        addr[0] = 0x20;
+        addr[12..20].copy_from_slice(self.index.to_be_bytes().as_ref());
+        Address::from(addr)
+    }
+
+    /// Returns the spot token whose system address is `address`, if it is one.
+    pub fn from_system_address(address: Address) -> Option<Self> {
+        let (prefix, index) = address.as_slice().split_at(12);
+        if prefix[0] != 0x20 || prefix[1..].iter().any(|byte| *byte != 0) {
+            return None
+        }
+        Some(Self { index: u64::from_be_bytes(index.try_into().ok()?) })
+    }
+}
+
+/// Reads the spot token mapping from a snapshot of the `spotMeta` info of the Hyperliquid API,
+/// i.e. a saved response of `POST https://api.hyperliquid.xyz/info` with `{"type": "spotMeta"}`.
+pub fn read_spot_meta_snapshot(path: &Path) -> Result<BTreeMap<Address, SpotId>> {
+    let meta: SpotMeta = serde_json::from_slice(&std::fs::read(path)?)?;
+    Ok(meta
+        .tokens
+        .iter()
+        .filter_map(|token| {
+            let evm_contract = token.evm_contract.as_ref()?;
+            Some((evm_contract.address, SpotId { index: token.index }))
+        })
+        .collect())
+}
+
+/// Persistent store of the spot token mapping.
+///
+/// The mapping only grows, since the ERC20 contract of a spot token cannot be changed once linked.
+pub trait SpotTokenStore: Send + Sync + Debug {
+    /// Returns the stored spot tokens, by ERC20 contract.
+    fn spot_tokens(&self) -> Result<BTreeMap<Address, SpotId>>;
+
+    /// Stores the given spot tokens, replacing existing entries.
+    fn insert_spot_tokens(&self, tokens: &BTreeMap<Address, SpotId>) -> Result<()>;
+}
+
+/// Shared [`SpotTokenStore`].
+pub type SpotTokenStoreBoxed = Arc<dyn SpotTokenStore>;
+
+/// Mapping of ERC20 contracts to their spot tokens, used to derive the senders of system
+/// transactions.
+///
+/// Contracts missing from the mapping are learned from the receipts of their system transactions,
+/// see [`BlockAndReceipts::spot_tokens`], and written to the store, if any.
+#[derive(Debug, Clone, Default)]
+pub struct SpotTokens {
+    tokens: BTreeMap<Address, SpotId>,
+    store: Option<SpotTokenStoreBoxed>,
+}
+
+impl SpotTokens {
+    /// Creates a mapping of the given spot tokens, which is not persisted.
+    pub const fn new(tokens: BTreeMap<Address, SpotId>) -> Self {
+        Self { tokens, store: None }
+    }
+
+    /// Loads the mapping from `store`, which learned spot tokens are written to.
+    pub fn load(store: SpotTokenStoreBoxed) -> Result<Self> {
+        Ok(Self { tokens: store.spot_tokens()?, store: Some(store) })
+    }
+
+    /// Returns the spot tokens, by ERC20 contract.
+    pub const fn tokens(&self) -> &BTreeMap<Address, SpotId> {
+        &self.tokens
+    }
+
+    /// Adds the spot tokens of the system transactions of `block` that are missing from the
+    /// mapping.
+    fn learn(&mut self, block: &BlockAndReceipts) -> Result<()> {
+        let learned = block
+            .spot_tokens()
+            .into_iter()
+            .filter(|(contract, _)| !self.tokens.contains_key(contract))
+            .collect::<BTreeMap<_, _>>();
+        if learned.is_empty() {
+            return Ok(())
+        }
+
+        for (contract, spot) in &learned {
+            info!(height = block.number(), %contract, index = spot.index, "Learned spot token");
+        }
+        if let Some(store) = &self.store {
+            store.insert_spot_tokens(&learned)?;
+        }
+        self.tokens.extend(learned);
+        Ok(())
+    }
+}
+
+/// Converts `block` into the block imported by the node.
+///
+/// Spot tokens missing from `spot_tokens` are learned from the receipts of the system
+/// transactions of the block, so no network access is needed.
+pub fn seal_block_with_system_txs(
+    block: BlockAndReceipts,
+    spot_tokens: &mut SpotTokens,
+) -> Result<SealedBlock, IngestError> {
+    let height = block.number();
+    if let Err(SystemTxError::UnknownSpotToken(_)) = block.system_transactions(&spot_tokens.tokens)
+    {
+        spot_tokens
+            .learn(&block)
+            .map_err(|err| IngestError::Database { height, reason: err.to_string() })?;
+    }
+    block
+        .into_sealed_block(&spot_tokens.tokens)
+        .map_err(|source| IngestError::SystemTx { height, source })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        serialized::{SystemTx, TRANSFER_EVENT_SIGNATURE},
+        test_utils::block,
+        LegacyReceipt, LegacyTxType,
+    };
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{address, Bytes, Log, TxKind};
+    use reth_primitives::{Transaction, TxSystem};
+    use std::io::Write;
+
+    #[test]
+    fn system_address_roundtrip() {
+        let spot = SpotId { index: 0xc5 };
+        assert_eq!(spot.system_address(), address!("20000000000000000000000000000000000000c5"));
+        assert_eq!(SpotId::from_system_address(spot.system_address()), Some(spot));
+        assert_eq!(
+            SpotId::from_system_address(address!("2222222222222222222222222222222222222222")),
+            None
+        );
+    }
+
+    #[test]
+    fn reads_spot_meta_snapshot() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(
+            br#"{"tokens": [
+                {"name": "USDC", "index": 0, "evmContract": null},
+                {
+                    "name": "TOKEN",
+                    "index": 197,
+                    "evmContract": {
+                        "address": "0xd3e8763675e4c425df46cc3b5c0f6cbdac396046",
+                        "evm_extra_wei_decimals": 0
+                    }
+                }
+            ], "universe": []}"#,
+        )
+        .unwrap();
+
+        let tokens = read_spot_meta_snapshot(file.path()).unwrap();
+        assert_eq!(
+            tokens,
+            BTreeMap::from([(
+                address!("d3e8763675e4c425df46cc3b5c0f6cbdac396046"),
+                SpotId { index: 197 }
+            )])
+        );
+    }
+
+    #[derive(Debug, Default)]
+    struct TestStore(parking_lot::Mutex<BTreeMap<Address, SpotId>>);
+
+    impl SpotTokenStore for TestStore {
+        fn spot_tokens(&self) -> Result<BTreeMap<Address, SpotId>> {
+            Ok(self.0.lock().clone())
+        }
+
+        fn insert_spot_tokens(&self, tokens: &BTreeMap<Address, SpotId>) -> Result<()> {
+            self.0.lock().extend(tokens);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn learns_spot_tokens_from_receipts() {
+        let contract = address!("d3e8763675e4c425df46cc3b5c0f6cbdac396046");
+        let recipient = Address::with_last_byte(0x11);
+        let spot = SpotId { index: 197 };
+
+        let mut input = vec![0xa9, 0x05, 0x9c, 0xbb];
+        input.extend_from_slice(recipient.into_word().as_slice());
+        input.extend_from_slice(&[0; 32]);
+        let tx = TxLegacy { to: TxKind::Call(contract), input: input.into(), ..Default::default() };
+        let transfer = Log::new_unchecked(
+            contract,
+            vec![
+                TRANSFER_EVENT_SIGNATURE,
+                spot.system_address().into_word(),
+                recipient.into_word(),
+            ],
+            Bytes::from_static(&[0; 32]),
+        );
+        let block = BlockAndReceipts {
+            system_txs: vec![SystemTx {
+                tx: Transaction::Legacy(tx.clone()),
+                receipt: Some(LegacyReceipt {
+                    tx_type: LegacyTxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: 0,
+                    logs: vec![transfer],
+                }),
+            }],
+            ..block(0)
+        };
+
+        let store = Arc::new(TestStore::default());
+        let mut spot_tokens = SpotTokens::load(store.clone()).unwrap();
+        let sealed = seal_block_with_system_txs(block, &mut spot_tokens).unwrap();
+
+        let expected = TxSystem { from: spot.system_address(), tx };
+        assert_eq!(sealed.body().transactions[0].as_system(), Some(&expected));
+        assert_eq!(spot_tokens.tokens(), &BTreeMap::from([(contract, spot)]));
+        assert_eq!(store.spot_tokens().unwrap(), BTreeMap::from([(contract, spot)]));
+    }
+}
@@ -0,0 +1,23 @@
+//! Test helpers for Hyperliquid blocks.
+
+use alloy_primitives::B256;
+use reth_primitives::{Block, Header, SealedBlock};
+
+use crate::{BlockAndReceipts, EvmBlock};
+
+/// Returns an empty block with the given number.
+pub fn block(number: u64) -> BlockAndReceipts {
+    block_with_parent(number, B256::ZERO)
+}
+
+/// Returns an empty block with the given number on top of the block with hash `parent_hash`.
+pub fn block_with_parent(number: u64, parent_hash: B256) -> BlockAndReceipts {
+    let header = Header { number, parent_hash, ..Default::default() };
+    BlockAndReceipts {
+        block: EvmBlock::Reth115(SealedBlock::seal_slow(Block { header, ..Default::default() })),
+        receipts: vec![],
+        system_txs: vec![],
+        read_precompile_calls: vec![],
+        highest_precompile_address: None,
+    }
+}
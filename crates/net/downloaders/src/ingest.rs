@@ -0,0 +1,779 @@
+use std::{
+    future::Future,
+    ops::RangeInclusive,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{ready, Context, Poll},
+    time::{Duration, Instant},
+};
+
+use alloy_consensus::BlockHeader as _;
+use alloy_primitives::BlockNumber;
+use alloy_rlp::{Decodable, Encodable};
+use futures::Stream;
+use reth_consensus::ConsensusError;
+use reth_hyperliquid_types::{
+    seal_block_with_system_txs, BlockAndReceipts, BlockSourceBoxed, EvmBlock, IngestError,
+    PrecompileDataStoreBoxed, SpotTokenStoreBoxed, SpotTokens,
+};
+use reth_network_p2p::{
+    bodies::{
+        downloader::{BodyDownloader, BodyDownloaderResult},
+        response::BlockResponse,
+    },
+    error::{DownloadError, DownloadResult},
+    headers::{
+        downloader::{HeaderDownloader, SyncTarget},
+        error::{HeadersDownloaderError, HeadersDownloaderResult},
+    },
+};
+use reth_primitives_traits::{Block, BlockHeader, GotExpected, SealedBlock, SealedHeader};
+use reth_storage_api::errors::provider::ProviderError;
+use reth_tasks::{TaskSpawner, TokioTaskExecutor};
+use tokio::task::{JoinError, JoinHandle};
+use tracing::{debug, error, warn};
+
+use crate::{bodies::task::TaskDownloader as BodiesTaskDownloader, headers::task::TaskDownloader};
+
+/// Interval at which a block that is not yet available from the source is queried again.
+const MISSING_BLOCK_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Initial delay before reading a block again after a transient error.
+const INITIAL_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+/// Upper bound of the delay between reads of a block after transient errors.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Number of blocks below the latest block of the source that are searched for the block hash of
+/// a [`SyncTarget::Tip`].
+const MAX_TIP_SEARCH_DEPTH: u64 = 10_000;
+
+/// Error of a [`PendingRead`].
+#[derive(Debug, thiserror::Error)]
+enum ReadError {
+    /// The read was cancelled.
+    #[error("read was cancelled")]
+    Cancelled,
+    /// The blocking task reading the source failed.
+    #[error(transparent)]
+    Join(#[from] JoinError),
+}
+
+/// Error storing the read precompile calls of downloaded blocks.
+#[derive(Debug, thiserror::Error)]
+#[error("failed to store read precompile calls of blocks {range:?}: {reason}")]
+struct PrecompileStoreError {
+    range: RangeInclusive<BlockNumber>,
+    reason: String,
+}
+
+/// A read of the source running on the blocking pool, which is cancelled once dropped.
+///
+/// Aborting a blocking task has no effect once it is running, so the read is handed a flag it
+/// checks while waiting for blocks instead.
+#[derive(Debug)]
+struct PendingRead<T> {
+    handle: JoinHandle<Option<T>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> PendingRead<T> {
+    /// Spawns `read`, which returns `None` once the flag it is given is set.
+    fn spawn(read: impl FnOnce(&AtomicBool) -> Option<T> + Send + 'static) -> Self {
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let flag = cancelled.clone();
+        Self { handle: tokio::task::spawn_blocking(move || read(&flag)), cancelled }
+    }
+}
+
+impl<T> Future for PendingRead<T> {
+    type Output = Result<T, ReadError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let result = ready!(Pin::new(&mut self.get_mut().handle).poll(cx))?;
+        Poll::Ready(result.ok_or(ReadError::Cancelled))
+    }
+}
+
+impl<T> Drop for PendingRead<T> {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Converts an error reading the source into a [`DownloadError`].
+fn read_error(err: impl core::error::Error + Send + Sync + 'static) -> DownloadError {
+    DownloadError::Provider(ProviderError::other(err))
+}
+
+/// Sleeps for `duration`, or until `cancelled` is set.
+fn sleep(duration: Duration, cancelled: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while !cancelled.load(Ordering::Relaxed) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break
+        }
+        std::thread::sleep(remaining.min(MISSING_BLOCK_RETRY_INTERVAL));
+    }
+}
+
+/// Returns the block at `height`, waiting for the source to have it.
+///
+/// Transient errors are retried with an exponential backoff. Corrupt block files are returned as
+/// errors instead, since the ingest directory is the only source of the pipeline: the source
+/// quarantines the file, if configured, and the block is waited for after a restart.
+/// Returns `None` once `cancelled` is set.
+fn read_block(
+    source: &BlockSourceBoxed,
+    height: BlockNumber,
+    cancelled: &AtomicBool,
+) -> Option<Result<BlockAndReceipts, IngestError>> {
+    let mut backoff = INITIAL_RETRY_BACKOFF;
+    while !cancelled.load(Ordering::Relaxed) {
+        match source.collect_block(height) {
+            Ok(Some(block)) => return Some(Ok(block)),
+            Ok(None) => {
+                warn!(
+                    target: "downloaders::ingest",
+                    height,
+                    source = source.name(),
+                    "Block not available yet"
+                );
+                sleep(MISSING_BLOCK_RETRY_INTERVAL, cancelled);
+            }
+            Err(err) if err.is_transient() && !matches!(err, IngestError::CorruptBlock { .. }) => {
+                warn!(
+                    target: "downloaders::ingest",
+                    height,
+                    source = source.name(),
+                    %err,
+                    ?backoff,
+                    "Failed to read block, retrying"
+                );
+                sleep(backoff, cancelled);
+                backoff = (backoff * 2).min(MAX_RETRY_BACKOFF);
+            }
+            Err(err) => {
+                error!(
+                    target: "downloaders::ingest",
+                    height,
+                    source = source.name(),
+                    %err,
+                    "Failed to read block"
+                );
+                return Some(Err(err))
+            }
+        }
+    }
+    None
+}
+
+/// Re-encodes `value` into the type used by the node.
+///
+/// The serialized blocks use the Ethereum primitives, which are RLP compatible with the
+/// primitives of the node.
+fn convert<T: Decodable>(value: &impl Encodable) -> alloy_rlp::Result<T> {
+    T::decode(&mut alloy_rlp::encode(value).as_slice())
+}
+
+/// Returns the number of the highest block to download for the given sync target.
+///
+/// Returns `None` once `cancelled` is set.
+fn resolve_sync_target(
+    source: &BlockSourceBoxed,
+    target: &SyncTarget,
+    cancelled: &AtomicBool,
+) -> Option<BlockNumber> {
+    let hash = match target {
+        SyncTarget::TipNum(number) => return Some(*number),
+        SyncTarget::Gap(gap) => return Some(gap.block.number.saturating_sub(1)),
+        SyncTarget::Tip(hash) => *hash,
+    };
+
+    // Highest block searched so far. Blocks are only searched once, since their hashes do not
+    // change while waiting for the sync target to show up.
+    let mut searched: Option<BlockNumber> = None;
+    while !cancelled.load(Ordering::Relaxed) {
+        if let Some(latest) = source.find_latest_block_number() {
+            let lowest = searched
+                .map_or(latest.saturating_sub(MAX_TIP_SEARCH_DEPTH), |searched| searched + 1);
+            for height in (lowest..=latest).rev() {
+                if cancelled.load(Ordering::Relaxed) {
+                    return None
+                }
+                let Ok(Some(block)) = source.collect_block(height) else { continue };
+                let EvmBlock::Reth115(block) = block.block;
+                if block.hash() == hash {
+                    return Some(height)
+                }
+            }
+            searched = Some(searched.map_or(latest, |searched| searched.max(latest)));
+        }
+        warn!(target: "downloaders::ingest", %hash, source = source.name(), "Sync target not found");
+        sleep(MISSING_BLOCK_RETRY_INTERVAL, cancelled);
+    }
+    None
+}
+
+/// Reads a batch of headers in descending order, starting at `start`, or at the sync target if
+/// `start` is not known yet, and ending at the block after `local_head`.
+///
+/// Returns `None` once `cancelled` is set.
+fn read_headers<H: Decodable>(
+    source: BlockSourceBoxed,
+    target: SyncTarget,
+    start: Option<BlockNumber>,
+    local_head: BlockNumber,
+    batch_size: u64,
+    cancelled: &AtomicBool,
+) -> Option<DownloadResult<Vec<SealedHeader<H>>>> {
+    let start = match start {
+        Some(start) => start,
+        None => resolve_sync_target(&source, &target, cancelled)?,
+    };
+    let end = start.saturating_sub(batch_size.saturating_sub(1)).max(local_head + 1);
+
+    let mut headers = Vec::with_capacity((start + 1 - end) as usize);
+    for height in (end..=start).rev() {
+        let block = match read_block(&source, height, cancelled)? {
+            Ok(block) => block,
+            Err(err) => return Some(Err(read_error(err))),
+        };
+        let EvmBlock::Reth115(block) = block.block;
+        let header = match convert(block.header()) {
+            Ok(header) => header,
+            Err(err) => {
+                return Some(Err(DownloadError::HeaderValidation {
+                    hash: block.hash(),
+                    number: height,
+                    error: Box::new(ConsensusError::Other(err.to_string())),
+                }))
+            }
+        };
+        headers.push(SealedHeader::new(header, block.hash()));
+    }
+    Some(Ok(headers))
+}
+
+/// A [`HeaderDownloader`] that reads headers from a [`BlockSource`](reth_hyperliquid_types::BlockSource),
+/// such as the ingest directory.
+///
+/// Headers are yielded in batches in descending order, from the sync target down to the block
+/// after the local head. Blocks that are not available yet are waited for.
+#[derive(Debug)]
+pub struct IngestHeaderDownloader<H: BlockHeader> {
+    source: BlockSourceBoxed,
+    local_head: Option<SealedHeader<H>>,
+    sync_target: Option<SyncTarget>,
+    /// Number of the next header to yield, resolved from the sync target by the first batch.
+    next: Option<BlockNumber>,
+    batch_size: u64,
+    /// Batch that is currently being read from the source.
+    pending: Option<PendingRead<DownloadResult<Vec<SealedHeader<H>>>>>,
+}
+
+impl<H: BlockHeader + 'static> IngestHeaderDownloader<H> {
+    /// Creates a new downloader reading from the given source.
+    pub const fn new(source: BlockSourceBoxed, batch_size: u64) -> Self {
+        Self { source, local_head: None, sync_target: None, next: None, batch_size, pending: None }
+    }
+
+    /// Convert the downloader into a [`TaskDownloader`] by spawning it.
+    pub fn into_task(self) -> TaskDownloader<H> {
+        self.into_task_with(&TokioTaskExecutor::default())
+    }
+
+    /// Convert the downloader into a [`TaskDownloader`] by spawning it via the given `spawner`.
+    pub fn into_task_with<S>(self, spawner: &S) -> TaskDownloader<H>
+    where
+        S: TaskSpawner,
+    {
+        TaskDownloader::spawn_with(self, spawner)
+    }
+
+    /// Discards the progress made towards the current sync target.
+    fn reset(&mut self) {
+        self.next = None;
+        self.pending = None;
+    }
+}
+
+impl<H: BlockHeader + 'static> HeaderDownloader for IngestHeaderDownloader<H> {
+    type Header = H;
+
+    fn update_local_head(&mut self, head: SealedHeader<H>) {
+        if self.local_head.as_ref().map(|local_head| local_head.num_hash()) != Some(head.num_hash())
+        {
+            self.reset();
+            self.local_head = Some(head);
+        }
+    }
+
+    fn update_sync_target(&mut self, target: SyncTarget) {
+        if self.sync_target.as_ref() != Some(&target) {
+            self.reset();
+            self.sync_target = Some(target);
+        }
+    }
+
+    fn set_batch_size(&mut self, limit: usize) {
+        self.batch_size = limit as u64;
+    }
+}
+
+impl<H: BlockHeader + 'static> Stream for IngestHeaderDownloader<H> {
+    type Item = HeadersDownloaderResult<Vec<SealedHeader<H>>, H>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let (Some(local_head), Some(target)) = (&this.local_head, &this.sync_target) else {
+                return Poll::Pending
+            };
+
+            if let Some(pending) = &mut this.pending {
+                let result = ready!(Pin::new(pending).poll(cx));
+                this.pending = None;
+
+                // The failed batch is read again if the stream is polled again
+                let headers = match result.map_err(read_error).and_then(|headers| headers) {
+                    Ok(headers) => headers,
+                    Err(err) => {
+                        error!(target: "downloaders::ingest", %err, "Failed to read headers");
+                        return Poll::Ready(Some(Err(HeadersDownloaderError::Download(err))))
+                    }
+                };
+                let Some(last) = headers.last() else {
+                    // The sync target is not above the local head
+                    this.next = Some(local_head.number());
+                    continue
+                };
+                this.next = Some(last.number() - 1);
+
+                if last.number() == local_head.number() + 1
+                    && last.parent_hash() != local_head.hash()
+                {
+                    return Poll::Ready(Some(Err(HeadersDownloaderError::DetachedHead {
+                        local_head: Box::new(local_head.clone()),
+                        header: Box::new(last.clone()),
+                        error: Box::new(ConsensusError::ParentHashMismatch(
+                            GotExpected { got: last.parent_hash(), expected: local_head.hash() }
+                                .into(),
+                        )),
+                    })));
+                }
+
+                debug!(target: "downloaders::ingest", first = ?headers.first().map(|h| h.number()), last = last.number(), "Read headers");
+                return Poll::Ready(Some(Ok(headers)))
+            }
+
+            if this.next.is_some_and(|next| next <= local_head.number()) {
+                return Poll::Pending
+            }
+
+            let source = this.source.clone();
+            let target = target.clone();
+            let (start, local_head, batch_size) = (this.next, local_head.number(), this.batch_size);
+            this.pending = Some(PendingRead::spawn(move |cancelled| {
+                read_headers(source, target, start, local_head, batch_size, cancelled)
+            }));
+        }
+    }
+}
+
+/// Reads the blocks of `range` and converts them into the blocks imported by the node.
+///
+/// The read precompile calls of the blocks are written to `precompile_store`, if set, so that the
+/// blocks can be executed again without the source, and the batch fails if they cannot be
+/// stored. Returns `None` once `cancelled` is set.
+fn read_bodies<B: Block>(
+    source: BlockSourceBoxed,
+    precompile_store: Option<PrecompileDataStoreBoxed>,
+    mut spot_tokens: SpotTokens,
+    range: RangeInclusive<BlockNumber>,
+    cancelled: &AtomicBool,
+) -> Option<(SpotTokens, BodyDownloaderResult<B>)> {
+    let blocks = match range
+        .clone()
+        .map(|height| read_block(&source, height, cancelled))
+        .collect::<Option<Result<Vec<_>, _>>>()?
+    {
+        Ok(blocks) => blocks,
+        Err(err) => return Some((spot_tokens, Err(read_error(err)))),
+    };
+    if let Some(store) = precompile_store {
+        let precompile_data = blocks
+            .iter()
+            .map(|block| (block.number(), block.precompile_data()))
+            .collect::<Vec<_>>();
+        // The blocks are not handed to the pipeline without their read precompile calls, since
+        // they could not be executed again otherwise
+        if let Err(err) = store.insert_precompile_data(&precompile_data) {
+            let err = PrecompileStoreError { range, reason: err.to_string() };
+            error!(target: "downloaders::ingest", %err, "Failed to store read precompile calls");
+            return Some((spot_tokens, Err(read_error(err))))
+        }
+    }
+
+    let result = seal_blocks(blocks, &mut spot_tokens);
+    Some((spot_tokens, result))
+}
+
+/// Converts `blocks` into the blocks imported by the node.
+fn seal_blocks<B: Block>(
+    blocks: Vec<BlockAndReceipts>,
+    spot_tokens: &mut SpotTokens,
+) -> BodyDownloaderResult<B> {
+    let mut responses = Vec::with_capacity(blocks.len());
+    for block in blocks {
+        let number = block.number();
+        let EvmBlock::Reth115(original) = &block.block;
+        let hash = original.hash();
+        let body_error = |error: ConsensusError| DownloadError::BodyValidation {
+            hash,
+            number,
+            error: Box::new(error),
+        };
+
+        let block = seal_block_with_system_txs(block, spot_tokens)
+            .map_err(|err| body_error(ConsensusError::Other(err.to_string())))?;
+        block
+            .ensure_transaction_root_valid()
+            .map_err(|err| body_error(ConsensusError::BodyTransactionRootDiff(err.into())))?;
+        let block = convert::<B>(&block.into_block())
+            .map_err(|err| body_error(ConsensusError::Other(err.to_string())))?;
+        responses.push(BlockResponse::Full(SealedBlock::new_unchecked(block, hash)));
+    }
+
+    Ok(responses)
+}
+
+/// Task reading a batch of blocks, which hands back the spot token mapping once done.
+type PendingBodies<B> = PendingRead<(SpotTokens, BodyDownloaderResult<B>)>;
+
+/// A [`BodyDownloader`] that reads blocks from a [`BlockSource`](reth_hyperliquid_types::BlockSource),
+/// such as the ingest directory.
+///
+/// The system transactions of each block are prepended to its transactions, as is done when
+/// importing the block through the engine API.
+#[derive(Debug)]
+pub struct IngestBodyDownloader<B: Block> {
+    source: BlockSourceBoxed,
+    /// Store the read precompile calls of the downloaded blocks are written to.
+    precompile_store: Option<PrecompileDataStoreBoxed>,
+    /// Store the spot token mapping is loaded from and learned spot tokens are written to.
+    spot_token_store: Option<SpotTokenStoreBoxed>,
+    /// Spot token mapping used to derive the senders of system transactions, lent to the pending
+    /// batch. Loaded from the store when the first batch is read, or after a batch failed.
+    spot_tokens: Option<SpotTokens>,
+    batch_size: u64,
+    /// Number of the next block to yield.
+    next: BlockNumber,
+    /// Last block of the download range.
+    end: BlockNumber,
+    /// Batch that is currently being read from the source.
+    pending: Option<(RangeInclusive<BlockNumber>, PendingBodies<B>)>,
+}
+
+impl<B: Block + 'static> IngestBodyDownloader<B> {
+    /// Creates a new downloader reading from the given source.
+    pub const fn new(source: BlockSourceBoxed, batch_size: u64) -> Self {
+        Self {
+            source,
+            precompile_store: None,
+            spot_token_store: None,
+            spot_tokens: None,
+            batch_size,
+            next: 1,
+            end: 0,
+            pending: None,
+        }
+    }
+
+    /// Sets the store the read precompile calls of the downloaded blocks are written to.
+    pub fn with_precompile_store(mut self, store: PrecompileDataStoreBoxed) -> Self {
+        self.precompile_store = Some(store);
+        self
+    }
+
+    /// Sets the store the spot token mapping is loaded from and learned spot tokens are written
+    /// to.
+    pub fn with_spot_token_store(mut self, store: SpotTokenStoreBoxed) -> Self {
+        self.spot_token_store = Some(store);
+        self
+    }
+
+    /// Returns the spot token mapping, loading it from the store if not loaded yet.
+    fn take_spot_tokens(&mut self) -> SpotTokens {
+        if let Some(spot_tokens) = self.spot_tokens.take() {
+            return spot_tokens
+        }
+        let Some(store) = &self.spot_token_store else { return SpotTokens::default() };
+        SpotTokens::load(store.clone()).unwrap_or_else(|err| {
+            warn!(target: "downloaders::ingest", %err, "Failed to load spot token mapping");
+            SpotTokens::default()
+        })
+    }
+
+    /// Convert the downloader into a [`BodiesTaskDownloader`] by spawning it.
+    pub fn into_task(self) -> BodiesTaskDownloader<B> {
+        self.into_task_with(&TokioTaskExecutor::default())
+    }
+
+    /// Convert the downloader into a [`BodiesTaskDownloader`] by spawning it via the given
+    /// `spawner`.
+    pub fn into_task_with<S>(self, spawner: &S) -> BodiesTaskDownloader<B>
+    where
+        S: TaskSpawner,
+    {
+        BodiesTaskDownloader::spawn_with(self, spawner)
+    }
+}
+
+impl<B: Block + 'static> BodyDownloader for IngestBodyDownloader<B> {
+    type Block = B;
+
+    fn set_download_range(&mut self, range: RangeInclusive<BlockNumber>) -> DownloadResult<()> {
+        if range.is_empty() {
+            return Err(DownloadError::InvalidBodyRange { range })
+        }
+
+        // Keep the pending batch if the new range continues the current one
+        let is_continuation = *range.start() == self.next
+            && self.pending.as_ref().is_none_or(|(pending, _)| pending.end() <= range.end());
+        if !is_continuation {
+            // Dropping the pending batch cancels it
+            self.pending = None;
+            self.next = *range.start();
+        }
+        self.end = *range.end();
+
+        Ok(())
+    }
+}
+
+impl<B: Block + 'static> Stream for IngestBodyDownloader<B> {
+    type Item = BodyDownloaderResult<B>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some((range, pending)) = &mut this.pending {
+                let end = *range.end();
+                let result = match ready!(Pin::new(pending).poll(cx)) {
+                    Ok((spot_tokens, result)) => {
+                        this.spot_tokens = Some(spot_tokens);
+                        result
+                    }
+                    Err(err) => {
+                        error!(target: "downloaders::ingest", %err, "Failed to read bodies");
+                        Err(read_error(err))
+                    }
+                };
+                this.pending = None;
+                if result.is_ok() {
+                    this.next = end + 1;
+                }
+                return Poll::Ready(Some(result))
+            }
+
+            if this.next > this.end {
+                return Poll::Pending
+            }
+
+            let range = this.next..=this.end.min(this.next + this.batch_size.saturating_sub(1));
+            let (source, precompile_store) = (this.source.clone(), this.precompile_store.clone());
+            let spot_tokens = this.take_spot_tokens();
+            let batch = range.clone();
+            let pending = PendingRead::spawn(move |cancelled| {
+                read_bodies(source, precompile_store, spot_tokens, batch, cancelled)
+            });
+            this.pending = Some((range, pending));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::Header;
+    use alloy_primitives::B256;
+    use futures::StreamExt;
+    use reth_hyperliquid_types::{
+        test_utils::block_with_parent, BlockSource, IngestError, PrecompileData,
+        PrecompileDataStore,
+    };
+    use std::collections::BTreeMap;
+
+    #[derive(Debug)]
+    struct TestBlockSource(BTreeMap<u64, BlockAndReceipts>);
+
+    impl TestBlockSource {
+        /// Creates a chain of empty blocks `1..=tip` on top of `genesis`.
+        fn new(genesis: &SealedHeader, tip: u64) -> Self {
+            let mut blocks = BTreeMap::new();
+            let mut parent_hash = genesis.hash();
+            for number in 1..=tip {
+                let block = block_with_parent(number, parent_hash);
+                let EvmBlock::Reth115(sealed) = &block.block;
+                parent_hash = sealed.hash();
+                blocks.insert(number, block);
+            }
+            Self(blocks)
+        }
+
+        fn hash(&self, number: u64) -> B256 {
+            let EvmBlock::Reth115(block) = &self.0[&number].block;
+            block.hash()
+        }
+    }
+
+    impl BlockSource for TestBlockSource {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError> {
+            Ok(self.0.get(&height).cloned())
+        }
+
+        fn find_latest_block_number(&self) -> Option<u64> {
+            self.0.keys().next_back().copied()
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn headers_are_yielded_in_reverse() {
+        let genesis = SealedHeader::seal_slow(Header::default());
+        let source = Arc::new(TestBlockSource::new(&genesis, 25));
+        let tip = source.hash(25);
+
+        let mut downloader = IngestHeaderDownloader::<Header>::new(source, 10);
+        downloader.update_sync_gap(genesis, SyncTarget::Tip(tip));
+
+        let mut numbers = vec![];
+        for _ in 0..3 {
+            let headers = downloader.next().await.unwrap().unwrap();
+            assert!(headers.len() <= 10);
+            numbers.extend(headers.iter().map(|header| header.number));
+        }
+        assert_eq!(numbers, (1..=25).rev().collect::<Vec<_>>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn headers_detached_from_local_head() {
+        let genesis = SealedHeader::seal_slow(Header::default());
+        let source = Arc::new(TestBlockSource::new(&genesis, 5));
+
+        let mut downloader = IngestHeaderDownloader::<Header>::new(source, 10);
+        let other = SealedHeader::seal_slow(Header { gas_limit: 1, ..Default::default() });
+        downloader.update_sync_gap(other, SyncTarget::TipNum(5));
+
+        assert!(matches!(
+            downloader.next().await,
+            Some(Err(HeadersDownloaderError::DetachedHead { .. }))
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn bodies_are_yielded_in_order() {
+        let genesis = SealedHeader::seal_slow(Header::default());
+        let source = Arc::new(TestBlockSource::new(&genesis, 25));
+
+        let mut downloader =
+            IngestBodyDownloader::<reth_primitives::Block>::new(source.clone(), 10);
+        downloader.set_download_range(3..=20).unwrap();
+
+        let mut numbers = vec![];
+        while numbers.len() < 18 {
+            let responses = downloader.next().await.unwrap().unwrap();
+            for response in responses {
+                let BlockResponse::Full(block) = response else { panic!("expected full block") };
+                assert_eq!(block.hash(), source.hash(block.number));
+                numbers.push(block.number);
+            }
+        }
+        assert_eq!(numbers, (3..=20).collect::<Vec<_>>());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn corrupt_blocks_are_download_errors() {
+        #[derive(Debug)]
+        struct CorruptBlockSource;
+
+        impl BlockSource for CorruptBlockSource {
+            fn name(&self) -> &str {
+                "corrupt"
+            }
+
+            fn collect_block(&self, height: u64) -> Result<Option<BlockAndReceipts>, IngestError> {
+                Err(IngestError::CorruptBlock {
+                    height,
+                    reason: "truncated".into(),
+                    quarantined: None,
+                })
+            }
+
+            fn find_latest_block_number(&self) -> Option<u64> {
+                Some(5)
+            }
+        }
+
+        let genesis = SealedHeader::seal_slow(Header::default());
+        let mut headers = IngestHeaderDownloader::<Header>::new(Arc::new(CorruptBlockSource), 10);
+        headers.update_sync_gap(genesis, SyncTarget::TipNum(5));
+        assert!(matches!(
+            headers.next().await,
+            Some(Err(HeadersDownloaderError::Download(DownloadError::Provider(_))))
+        ));
+
+        let mut bodies =
+            IngestBodyDownloader::<reth_primitives::Block>::new(Arc::new(CorruptBlockSource), 10);
+        bodies.set_download_range(1..=5).unwrap();
+        assert!(matches!(bodies.next().await, Some(Err(DownloadError::Provider(_)))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn bodies_fail_without_stored_precompile_calls() {
+        #[derive(Debug)]
+        struct FailingStore;
+
+        impl PrecompileDataStore for FailingStore {
+            fn precompile_data(&self, _height: u64) -> eyre::Result<Option<PrecompileData>> {
+                Ok(None)
+            }
+
+            fn insert_precompile_data(
+                &self,
+                _blocks: &[(u64, PrecompileData)],
+            ) -> eyre::Result<()> {
+                eyre::bail!("database is read-only")
+            }
+        }
+
+        let genesis = SealedHeader::seal_slow(Header::default());
+        let source = Arc::new(TestBlockSource::new(&genesis, 5));
+        let mut downloader = IngestBodyDownloader::<reth_primitives::Block>::new(source, 10)
+            .with_precompile_store(Arc::new(FailingStore));
+        downloader.set_download_range(1..=5).unwrap();
+        assert!(matches!(downloader.next().await, Some(Err(DownloadError::Provider(_)))));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dropped_reads_are_cancelled() {
+        let genesis = SealedHeader::seal_slow(Header::default());
+        let source: BlockSourceBoxed = Arc::new(TestBlockSource::new(&genesis, 1));
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let read = PendingRead::spawn(move |cancelled| {
+            let block = read_block(&source, 2, cancelled);
+            tx.send(block.is_none()).unwrap();
+            block
+        });
+        drop(read);
+        assert!(rx.recv_timeout(Duration::from_secs(5)).unwrap());
+    }
+}
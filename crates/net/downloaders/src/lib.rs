@@ -38,6 +38,13 @@ pub mod receipt_file_client;
 /// Enables decoding and encoding `Block` types within file contexts.
 pub mod file_codec;
 
+/// Module with downloaders reading blocks from a Hyperliquid block source.
+///
+/// Contains [`IngestHeaderDownloader`](ingest::IngestHeaderDownloader) and
+/// [`IngestBodyDownloader`](ingest::IngestBodyDownloader), which let the pipeline backfill the
+/// blocks of the ingest directory.
+pub mod ingest;
+
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
@@ -1,3 +1,4 @@
+use crate::error::DownloadError;
 use alloy_primitives::Sealable;
 use derive_more::{Display, Error};
 use reth_consensus::ConsensusError;
@@ -7,7 +8,7 @@ use reth_primitives_traits::SealedHeader;
 pub type HeadersDownloaderResult<T, H> = Result<T, HeadersDownloaderError<H>>;
 
 /// Error variants that can happen when sending requests to a session.
-#[derive(Debug, Clone, Eq, PartialEq, Display, Error)]
+#[derive(Debug, Clone, Display, Error)]
 pub enum HeadersDownloaderError<H: Sealable> {
     /// The downloaded header cannot be attached to the local head,
     /// but is valid otherwise.
@@ -21,4 +22,7 @@ pub enum HeadersDownloaderError<H: Sealable> {
         #[error(source)]
         error: Box<ConsensusError>,
     },
+    /// The headers could not be read from the downloader's source.
+    #[display("failed to download headers: {_0}")]
+    Download(DownloadError),
 }
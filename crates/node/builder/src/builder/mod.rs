@@ -10,13 +10,14 @@ use crate::{
     BlockReaderFor, EngineNodeLauncher, LaunchNode, Node,
 };
 use alloy_eips::eip4844::env_settings::EnvKzgSettings;
+use alloy_primitives::Address;
 use futures::Future;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
 use reth_cli_util::get_secret_key;
 use reth_db_api::{database::Database, database_metrics::DatabaseMetrics};
 use reth_engine_tree::tree::TreeConfig;
 use reth_exex::ExExContext;
-use reth_hyperliquid_types::PrecompilesCache;
+use reth_hyperliquid_types::{MissingPrecompileDataPolicy, PrecompilesCache, SpotId};
 use reth_network::{
     transactions::TransactionsManagerConfig, NetworkBuilder, NetworkConfig, NetworkConfigBuilder,
     NetworkHandle, NetworkManager, NetworkPrimitives,
@@ -38,7 +39,7 @@ use reth_provider::{
 use reth_tasks::TaskExecutor;
 use reth_transaction_pool::{PoolConfig, PoolTransaction, TransactionPool};
 use secp256k1::SecretKey;
-use std::{path::PathBuf, sync::Arc};
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc};
 use tracing::{info, trace, warn};
 
 pub mod add_ons;
@@ -483,6 +484,30 @@ where
         }
     }
 
+    /// Sets what calls do when the read precompile calls of their block are unavailable.
+    pub fn missing_precompile_data_policy(self, policy: MissingPrecompileDataPolicy) -> Self {
+        Self {
+            builder: self.builder.missing_precompile_data_policy(policy),
+            task_executor: self.task_executor,
+        }
+    }
+
+    /// Sets whether block execution fails on read precompile calls that were not recorded.
+    pub fn strict_precompile_replay(self, strict: bool) -> Self {
+        Self {
+            builder: self.builder.strict_precompile_replay(strict),
+            task_executor: self.task_executor,
+        }
+    }
+
+    /// Sets the spot tokens written to the spot token store when the node is launched.
+    pub fn spot_token_snapshot(self, tokens: BTreeMap<Address, SpotId>) -> Self {
+        Self {
+            builder: self.builder.spot_token_snapshot(tokens),
+            task_executor: self.task_executor,
+        }
+    }
+
     /// Sets the hook that is run to configure the rpc modules.
     pub fn extend_rpc_modules<F>(self, hook: F) -> Self
     where
@@ -12,12 +12,15 @@ use crate::{
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     AddOns, FullNode,
 };
+use alloy_primitives::Address;
 use reth_exex::ExExContext;
-use reth_hyperliquid_types::PrecompilesCache;
+use reth_hyperliquid_types::{
+    MissingPrecompileDataPolicy, PrecompileDataStoreBoxed, PrecompilesCache, SpotId,
+};
 use reth_node_api::{FullNodeComponents, FullNodeTypes, NodeAddOns, NodeTypes};
 use reth_node_core::node_config::NodeConfig;
 use reth_tasks::TaskExecutor;
-use std::{fmt, future::Future};
+use std::{collections::BTreeMap, fmt, future::Future};
 
 /// A node builder that also has the configured types.
 pub struct NodeBuilderWithTypes<T: FullNodeTypes> {
@@ -148,9 +151,18 @@ impl<T: FullNodeTypes, C: NodeComponents<T>> Clone for NodeAdapter<T, C> {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct HyperliquidSharedState {
     pub precompiles_cache: PrecompilesCache,
+    /// Database store of the read precompile calls, set when the node is launched.
+    pub precompile_store: Option<PrecompileDataStoreBoxed>,
+    /// What calls do when the read precompile calls of their block are unavailable.
+    pub missing_precompile_data: MissingPrecompileDataPolicy,
+    /// Whether block execution fails on read precompile calls that were not recorded.
+    pub strict_precompile_replay: bool,
+    /// Spot tokens written to the spot token store when the node is launched, e.g. read from a
+    /// snapshot.
+    pub spot_token_snapshot: BTreeMap<Address, SpotId>,
 }
 
 /// A fully type configured node builder.
@@ -307,7 +319,26 @@ where
 
     /// Add state
     pub fn add_precompiles_cache(mut self, precompiles_cache: PrecompilesCache) -> Self {
-        self.shared_state = Some(HyperliquidSharedState { precompiles_cache });
+        self.shared_state.get_or_insert_with(Default::default).precompiles_cache =
+            precompiles_cache;
+        self
+    }
+
+    /// Sets what calls do when the read precompile calls of their block are unavailable.
+    pub fn missing_precompile_data_policy(mut self, policy: MissingPrecompileDataPolicy) -> Self {
+        self.shared_state.get_or_insert_with(Default::default).missing_precompile_data = policy;
+        self
+    }
+
+    /// Sets whether block execution fails on read precompile calls that were not recorded.
+    pub fn strict_precompile_replay(mut self, strict: bool) -> Self {
+        self.shared_state.get_or_insert_with(Default::default).strict_precompile_replay = strict;
+        self
+    }
+
+    /// Sets the spot tokens written to the spot token store when the node is launched.
+    pub fn spot_token_snapshot(mut self, tokens: BTreeMap<Address, SpotId>) -> Self {
+        self.shared_state.get_or_insert_with(Default::default).spot_token_snapshot = tokens;
         self
     }
 }
@@ -20,6 +20,7 @@ use reth_engine_local::MiningMode;
 use reth_engine_tree::tree::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 use reth_evm::noop::NoopBlockExecutorProvider;
 use reth_fs_util as fs;
+use reth_hyperliquid_types::{BlockSource, EvmBlock, S3BlockSource};
 use reth_invalid_block_hooks::InvalidBlockWitnessHook;
 use reth_network_p2p::headers::client::HeadersClient;
 use reth_node_api::{FullNodeTypes, NodeTypes, NodeTypesWithDB, NodeTypesWithDBAdapter};
@@ -60,6 +61,10 @@ use tokio::sync::{
     oneshot, watch,
 };
 
+/// Minimum number of blocks the ingest directory has to be ahead of the local head for the
+/// initial sync to be done by the pipeline.
+const MIN_BLOCKS_FOR_INGEST_BACKFILL: u64 = 10_000;
+
 /// Reusable setup for launching a node.
 ///
 /// This provides commonly used boilerplate for launching a node.
@@ -776,9 +781,39 @@ where
             initial_target = self.check_pipeline_consistency()?;
         }
 
+        if initial_target.is_none() {
+            initial_target = self.ingest_backfill_target()?;
+        }
+
         Ok(initial_target)
     }
 
+    /// Returns the hash of the latest block of the ingest directory, if the local head is far
+    /// enough behind it that the blocks should be backfilled by the pipeline rather than imported
+    /// one by one through the engine API.
+    pub fn ingest_backfill_target(&self) -> ProviderResult<Option<B256>> {
+        let Some(ingest_dir) = &self.node_config().ingest_dir else { return Ok(None) };
+        let source = S3BlockSource::new(ingest_dir);
+        let Some(latest) = source.find_latest_block_number() else { return Ok(None) };
+
+        let head = self.blockchain_db().last_block_number()?;
+        if latest < head + MIN_BLOCKS_FOR_INGEST_BACKFILL {
+            return Ok(None)
+        }
+
+        let block = match source.collect_block(latest) {
+            Ok(Some(block)) => block,
+            Ok(None) => return Ok(None),
+            Err(err) => {
+                warn!(target: "reth::cli", %err, "Failed to read the latest block of the ingest directory");
+                return Ok(None)
+            }
+        };
+        let EvmBlock::Reth115(block) = block.block;
+        info!(target: "reth::cli", head, latest, "Backfilling blocks from the ingest directory");
+        Ok(Some(block.hash()))
+    }
+
     /// Returns true if the node should terminate after the initial backfill run.
     ///
     /// This is the case if any of these configs are set:
@@ -13,6 +13,7 @@ use reth_engine_tree::{
 };
 use reth_engine_util::EngineMessageStreamExt;
 use reth_exex::ExExManagerHandle;
+use reth_hyperliquid_types::{PrecompileDataStoreBoxed, S3BlockSource, SpotTokenStoreBoxed};
 use reth_network::{NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
@@ -38,8 +39,9 @@ use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
     rpc::{EngineValidatorAddOn, RethRpcAddOns, RpcHandle},
-    setup::build_networked_pipeline,
-    AddOns, AddOnsContext, ExExLauncher, FullNode, LaunchContext, LaunchNode, NodeAdapter,
+    setup::{build_ingest_pipeline, build_networked_pipeline},
+    AddOns, AddOnsContext, DatabasePrecompileDataStore, DatabaseSpotTokenStore, ExExLauncher,
+    FullNode, HyperliquidSharedState, LaunchContext, LaunchNode, NodeAdapter,
     NodeBuilderWithComponents, NodeComponents, NodeComponentsBuilder, NodeHandle, NodeTypesAdapter,
 };
 
@@ -127,8 +129,25 @@ where
             // later the components.
             .with_blockchain_db::<T, _>(move |provider_factory| {
                 Ok(BlockchainProvider::new(provider_factory)?)
-            })?
-            .with_components(components_builder, on_component_initialized, shared_state.clone()).await?;
+            })?;
+
+        // Read precompile calls are stored along with the blocks, so that they can be re-executed
+        let precompile_store: PrecompileDataStoreBoxed =
+            Arc::new(DatabasePrecompileDataStore::new(ctx.provider_factory().clone()));
+        // The spot token mapping is stored as well, so that system transactions can be converted
+        // without network access
+        let spot_token_store: SpotTokenStoreBoxed =
+            Arc::new(DatabaseSpotTokenStore::new(ctx.provider_factory().clone()));
+        if let Some(shared_state) = &shared_state {
+            spot_token_store.insert_spot_tokens(&shared_state.spot_token_snapshot)?;
+        }
+        let shared_state = HyperliquidSharedState {
+            precompile_store: Some(precompile_store.clone()),
+            ..shared_state.unwrap_or_default()
+        };
+        let ctx = ctx
+            .with_components(components_builder, on_component_initialized, Some(shared_state))
+            .await?;
 
         // spawn exexs
         let exex_manager_handle = ExExLauncher::new(
@@ -171,19 +190,40 @@ where
         // Configure the pipeline
         let pipeline_exex_handle =
             exex_manager_handle.clone().unwrap_or_else(ExExManagerHandle::empty);
-        let pipeline = build_networked_pipeline(
-            &ctx.toml_config().stages,
-            network_client.clone(),
-            consensus.clone(),
-            ctx.provider_factory().clone(),
-            ctx.task_executor(),
-            ctx.sync_metrics_tx(),
-            ctx.prune_config(),
-            max_block,
-            static_file_producer,
-            ctx.components().block_executor().clone(),
-            pipeline_exex_handle,
-        )?;
+        let pipeline = if let Some(ingest_dir) = &ctx.node_config().ingest_dir {
+            // Backfill from the ingest directory instead of the network
+            build_ingest_pipeline(
+                &ctx.toml_config().stages,
+                Arc::new(
+                    S3BlockSource::new(ingest_dir).with_quarantine_dir(ctx.data_dir().quarantine()),
+                ),
+                precompile_store,
+                spot_token_store,
+                consensus.clone(),
+                ctx.provider_factory().clone(),
+                ctx.task_executor(),
+                ctx.sync_metrics_tx(),
+                ctx.prune_config(),
+                max_block,
+                static_file_producer,
+                ctx.components().block_executor().clone(),
+                pipeline_exex_handle,
+            )?
+        } else {
+            build_networked_pipeline(
+                &ctx.toml_config().stages,
+                network_client.clone(),
+                consensus.clone(),
+                ctx.provider_factory().clone(),
+                ctx.task_executor(),
+                ctx.sync_metrics_tx(),
+                ctx.prune_config(),
+                max_block,
+                static_file_producer,
+                ctx.components().block_executor().clone(),
+                pipeline_exex_handle,
+            )?
+        };
 
         // The new engine writes directly to static files. This ensures that they're up to the tip.
         pipeline.move_to_static_files()?;
@@ -320,6 +360,11 @@ where
         // Run consensus engine to completion
         let initial_target = ctx.initial_backfill_target()?;
         let network_handle = ctx.components().network().clone();
+        if initial_target.is_some() {
+            // Mark the node as syncing right away, so that block ingestion waits for the initial
+            // backfill instead of racing it through the engine API.
+            network_handle.update_sync_state(SyncState::Syncing);
+        }
         let mut built_payloads = ctx
             .components()
             .payload_builder_handle()
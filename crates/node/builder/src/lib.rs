@@ -38,6 +38,12 @@ pub mod rpc;
 
 pub mod setup;
 
+pub mod precompile_store;
+pub use precompile_store::DatabasePrecompileDataStore;
+
+pub mod spot_token_store;
+pub use spot_token_store::DatabaseSpotTokenStore;
+
 /// Type aliases for traits that are often used together
 pub mod aliases;
 pub use aliases::*;
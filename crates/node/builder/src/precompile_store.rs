@@ -0,0 +1,55 @@
+//! Storage of the read precompile calls of imported blocks in the database.
+
+use std::fmt;
+
+use reth_db_api::{
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_hyperliquid_types::{PrecompileData, PrecompileDataStore};
+use reth_provider::{DBProvider, DatabaseProviderFactory};
+
+/// [`PrecompileDataStore`] backed by the [`tables::BlockPrecompileCalls`] table.
+#[derive(Clone)]
+pub struct DatabasePrecompileDataStore<F> {
+    factory: F,
+}
+
+impl<F> DatabasePrecompileDataStore<F> {
+    /// Creates a new store writing to the database of `factory`.
+    pub const fn new(factory: F) -> Self {
+        Self { factory }
+    }
+}
+
+impl<F> fmt::Debug for DatabasePrecompileDataStore<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabasePrecompileDataStore").finish_non_exhaustive()
+    }
+}
+
+impl<F> PrecompileDataStore for DatabasePrecompileDataStore<F>
+where
+    F: DatabaseProviderFactory + 'static,
+{
+    fn precompile_data(&self, height: u64) -> eyre::Result<Option<PrecompileData>> {
+        let provider = self.factory.database_provider_ro()?;
+        let Some(bytes) = provider.tx_ref().get::<tables::BlockPrecompileCalls>(height)? else {
+            return Ok(None)
+        };
+        Ok(Some(PrecompileData::decode(&bytes)?))
+    }
+
+    fn insert_precompile_data(&self, blocks: &[(u64, PrecompileData)]) -> eyre::Result<()> {
+        if blocks.is_empty() {
+            return Ok(())
+        }
+
+        let provider = self.factory.database_provider_rw()?;
+        for (height, data) in blocks {
+            provider.tx_ref().put::<tables::BlockPrecompileCalls>(*height, data.encode().into())?;
+        }
+        provider.commit()?;
+        Ok(())
+    }
+}
@@ -9,9 +9,11 @@ use reth_consensus::{ConsensusError, FullConsensus};
 use reth_downloaders::{
     bodies::bodies::BodiesDownloaderBuilder,
     headers::reverse_headers::ReverseHeadersDownloaderBuilder,
+    ingest::{IngestBodyDownloader, IngestHeaderDownloader},
 };
 use reth_evm::execute::BlockExecutorProvider;
 use reth_exex::ExExManagerHandle;
+use reth_hyperliquid_types::{BlockSourceBoxed, PrecompileDataStoreBoxed, SpotTokenStoreBoxed};
 use reth_network_p2p::{
     bodies::downloader::BodyDownloader, headers::downloader::HeaderDownloader, BlockClient,
 };
@@ -69,6 +71,53 @@ where
     Ok(pipeline)
 }
 
+/// Constructs a [Pipeline] that backfills blocks from the given block source, such as the ingest
+/// directory.
+#[allow(clippy::too_many_arguments)]
+pub fn build_ingest_pipeline<N, Executor>(
+    config: &StageConfig,
+    source: BlockSourceBoxed,
+    precompile_store: PrecompileDataStoreBoxed,
+    spot_token_store: SpotTokenStoreBoxed,
+    consensus: Arc<dyn FullConsensus<N::Primitives, Error = ConsensusError>>,
+    provider_factory: ProviderFactory<N>,
+    task_executor: &TaskExecutor,
+    metrics_tx: reth_stages::MetricEventsSender,
+    prune_config: Option<PruneConfig>,
+    max_block: Option<BlockNumber>,
+    static_file_producer: StaticFileProducer<ProviderFactory<N>>,
+    executor: Executor,
+    exex_manager_handle: ExExManagerHandle<N::Primitives>,
+) -> eyre::Result<Pipeline<N>>
+where
+    N: ProviderNodeTypes,
+    Executor: BlockExecutorProvider<Primitives = N::Primitives>,
+{
+    let header_downloader =
+        IngestHeaderDownloader::new(source.clone(), config.headers.downloader_request_limit)
+            .into_task_with(task_executor);
+
+    let batch_size = config.bodies.downloader_stream_batch_size as u64;
+    let body_downloader = IngestBodyDownloader::new(source, batch_size)
+        .with_precompile_store(precompile_store)
+        .with_spot_token_store(spot_token_store)
+        .into_task_with(task_executor);
+
+    build_pipeline(
+        provider_factory,
+        config,
+        header_downloader,
+        body_downloader,
+        consensus,
+        max_block,
+        metrics_tx,
+        prune_config,
+        static_file_producer,
+        executor,
+        exex_manager_handle,
+    )
+}
+
 /// Builds the [Pipeline] with the given [`ProviderFactory`] and downloaders.
 #[allow(clippy::too_many_arguments)]
 pub fn build_pipeline<N, H, B, Executor>(
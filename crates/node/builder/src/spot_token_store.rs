@@ -0,0 +1,62 @@
+//! Storage of the spot token mapping in the database.
+
+use std::{collections::BTreeMap, fmt};
+
+use alloy_primitives::Address;
+use reth_db_api::{
+    cursor::DbCursorRO,
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_hyperliquid_types::{SpotId, SpotTokenStore};
+use reth_provider::{DBProvider, DatabaseProviderFactory};
+
+/// [`SpotTokenStore`] backed by the [`tables::SpotTokens`] table.
+#[derive(Clone)]
+pub struct DatabaseSpotTokenStore<F> {
+    factory: F,
+}
+
+impl<F> DatabaseSpotTokenStore<F> {
+    /// Creates a new store writing to the database of `factory`.
+    pub const fn new(factory: F) -> Self {
+        Self { factory }
+    }
+}
+
+impl<F> fmt::Debug for DatabaseSpotTokenStore<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseSpotTokenStore").finish_non_exhaustive()
+    }
+}
+
+impl<F> SpotTokenStore for DatabaseSpotTokenStore<F>
+where
+    F: DatabaseProviderFactory + 'static,
+{
+    fn spot_tokens(&self) -> eyre::Result<BTreeMap<Address, SpotId>> {
+        let provider = self.factory.database_provider_ro()?;
+        let mut cursor = provider.tx_ref().cursor_read::<tables::SpotTokens>()?;
+        let tokens = cursor
+            .walk(None)?
+            .map(|entry| {
+                let (contract, index) = entry?;
+                Ok((contract, SpotId { index }))
+            })
+            .collect::<eyre::Result<_>>()?;
+        Ok(tokens)
+    }
+
+    fn insert_spot_tokens(&self, tokens: &BTreeMap<Address, SpotId>) -> eyre::Result<()> {
+        if tokens.is_empty() {
+            return Ok(())
+        }
+
+        let provider = self.factory.database_provider_rw()?;
+        for (contract, spot) in tokens {
+            provider.tx_ref().put::<tables::SpotTokens>(*contract, spot.index)?;
+        }
+        provider.commit()?;
+        Ok(())
+    }
+}
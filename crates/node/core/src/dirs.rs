@@ -351,6 +351,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the directory undecodable block files are moved to.
+    ///
+    /// `<DIR>/<CHAIN_ID>/quarantine`
+    pub fn quarantine(&self) -> PathBuf {
+        self.data_dir().join("quarantine")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
@@ -15,7 +15,6 @@ pub mod shanghai;
 use alloy_rpc_types_engine::{ExecutionData, PayloadError};
 use reth_chainspec::EthereumHardforks;
 use reth_primitives::SealedBlock;
-use reth_primitives_traits::transaction::signed::is_impersonated_tx;
 use reth_primitives_traits::{Block, SignedTransaction};
 use std::sync::Arc;
 
@@ -94,7 +93,7 @@ impl<ChainSpec: EthereumHardforks> ExecutionPayloadValidator<ChainSpec> {
         let (normal, system) = transactions.into_iter().partition(|tx| {
             let tx = T::decode_2718(&mut tx.iter().as_slice());
             match tx {
-                Ok(tx) => is_impersonated_tx(tx.signature(), tx.gas_price()).is_none(),
+                Ok(tx) => tx.system_tx_sender().is_none(),
                 Err(_) => true,
             }
         });
@@ -1,7 +1,7 @@
 //! Block body abstraction.
 
 use crate::{
-    transaction::signed::{is_impersonated_tx, RecoveryError},
+    transaction::signed::RecoveryError,
     BlockHeader, FullSignedTx, InMemorySize, MaybeSerde, MaybeSerdeBincodeCompat,
     SignedTransaction,
 };
@@ -85,7 +85,7 @@ pub trait BlockBody:
         let transactions: Vec<Self::Transaction> = self
             .transactions()
             .into_iter()
-            .filter(|&tx| is_impersonated_tx(tx.signature(), tx.gas_price()).is_none())
+            .filter(|&tx| tx.system_tx_sender().is_none())
             .cloned()
             .collect::<Vec<_>>();
         alloy_consensus::proofs::calculate_transaction_root(transactions.as_slice())
@@ -22,8 +22,11 @@ impl<T> FullSignedTx for T where T: SignedTransaction + MaybeCompact + MaybeSerd
 /// Hyperliquid system transaction from address.
 pub const NATIVE_TOKEN_SYSTEM_ADDRESS: Address = address!("2222222222222222222222222222222222222222");
 
-/// Check if the transaction is impersonated.
-/// Signature part is introduced in block_ingest, while the gas_price is trait of hyperliquid system transactions.
+/// Returns the sender of a Hyperliquid system transaction in its legacy encoding.
+///
+/// System transactions are unsigned. They are encoded as legacy transactions with a zero gas price
+/// and the signature `r = 1, v = true`, whose `s` value is the system address of the sender, or
+/// `1` for [`NATIVE_TOKEN_SYSTEM_ADDRESS`].
 pub fn is_impersonated_tx(signature: &Signature, gas_price: Option<u128>) -> Option<Address> {
     if signature.r() == U256::from(1) && signature.v() == true && gas_price == Some(0u128) {
         if signature.s() == U256::from(1) {
@@ -40,6 +43,48 @@ pub fn is_impersonated_tx(signature: &Signature, gas_price: Option<u128>) -> Opt
     }
 }
 
+/// Selector of the ERC20 `transfer(address,uint256)` function.
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// A transfer of HYPE or of a spot token from HyperCore to the EVM, made by a system transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemTransfer {
+    /// Address receiving the transfer.
+    pub recipient: Address,
+    /// ERC20 contract of the spot token, or [`NATIVE_TOKEN_SYSTEM_ADDRESS`] for HYPE.
+    pub token: Address,
+    /// Transferred amount, in the smallest unit of the token.
+    pub amount: U256,
+}
+
+/// Returns the transfer made by `tx` if it is a system transaction moving HYPE or a spot token
+/// from HyperCore to the EVM.
+///
+/// HYPE is sent as value to the recipient, spot tokens are sent by calling `transfer` on their
+/// ERC20 contract.
+pub fn system_transfer<T: SignedTransaction>(tx: &T) -> Option<SystemTransfer> {
+    tx.system_tx_sender()?;
+    let to = tx.to()?;
+    let input = tx.input();
+    if input.is_empty() {
+        return Some(SystemTransfer {
+            recipient: to,
+            token: NATIVE_TOKEN_SYSTEM_ADDRESS,
+            amount: tx.value(),
+        });
+    }
+
+    // `transfer(address,uint256)` call: selector, then the left padded recipient and the amount
+    if input.len() != 68 || input[..4] != ERC20_TRANSFER_SELECTOR {
+        return None;
+    }
+    Some(SystemTransfer {
+        recipient: Address::from_slice(&input[16..36]),
+        token: to,
+        amount: U256::from_be_slice(&input[36..68]),
+    })
+}
+
 /// A signed transaction.
 #[auto_impl::auto_impl(&, Arc)]
 pub trait SignedTransaction:
@@ -65,6 +110,13 @@ pub trait SignedTransaction:
     /// Returns reference to signature.
     fn signature(&self) -> &Signature;
 
+    /// Returns the sender of a Hyperliquid system transaction, or `None` for a user transaction.
+    ///
+    /// System transactions are unsigned, they are executed on behalf of a system address.
+    fn system_tx_sender(&self) -> Option<Address> {
+        None
+    }
+
     /// Returns whether this transaction type can be __broadcasted__ as full transaction over the
     /// network.
     ///
@@ -99,7 +151,7 @@ pub trait SignedTransaction:
     /// Returns `None` if the transaction's signature is invalid, see also
     /// `reth_primitives::transaction::recover_signer_unchecked`.
     fn recover_signer_unchecked(&self) -> Result<Address, RecoveryError> {
-        if let Some(address) = is_impersonated_tx(self.signature(), self.gas_price()) {
+        if let Some(address) = self.system_tx_sender() {
             return Ok(address);
         }
         self.recover_signer_unchecked_with_buf(&mut Vec::new()).map_err(|_| RecoveryError)
@@ -193,9 +245,6 @@ impl SignedTransaction for PooledTransaction {
         buf: &mut Vec<u8>,
     ) -> Result<Address, RecoveryError> {
         let signature = self.signature();
-        if let Some(address) = is_impersonated_tx(signature, self.gas_price()) {
-            return Ok(address);
-        }
         match self {
             Self::Legacy(tx) => tx.tx().encode_for_signing(buf),
             Self::Eip2930(tx) => tx.tx().encode_for_signing(buf),
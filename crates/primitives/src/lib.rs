@@ -47,7 +47,7 @@ pub type RecoveredTx<T> = Recovered<T>;
 
 pub use transaction::{
     util::secp256k1::{public_key_to_address, recover_signer_unchecked, sign_message},
-    InvalidTransactionError, Transaction, TransactionSigned, TxType,
+    InvalidTransactionError, Transaction, TransactionSigned, TxSystem, TxType, SYSTEM_TX_TYPE_ID,
 };
 #[allow(deprecated)]
 pub use transaction::{PooledTransactionsElementEcRecovered, TransactionSignedEcRecovered};
@@ -27,7 +27,7 @@ mod pooled;
 mod tx_type;
 
 /// Signed transaction.
-pub use reth_ethereum_primitives::{Transaction, TransactionSigned};
+pub use reth_ethereum_primitives::{Transaction, TransactionSigned, TxSystem, SYSTEM_TX_TYPE_ID};
 
 /// Type alias kept for backward compatibility.
 #[deprecated(note = "Use `Recovered` instead")]
@@ -6,6 +6,17 @@ use alloy_rpc_types_trace::otterscan::{
     TransactionsWithReceipts,
 };
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_hyperliquid_types::DecodedPrecompileCall;
+use serde::{Deserialize, Serialize};
+
+/// Entry of `ots_traceTransaction`, with the decoded call if it is a call to a read precompile.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OtsTraceEntry {
+    #[serde(flatten)]
+    pub entry: TraceEntry,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decoded: Option<DecodedPrecompileCall>,
+}
 
 /// Otterscan rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "ots"))]
@@ -40,9 +51,9 @@ pub trait Otterscan<T: RpcObject, H: RpcObject> {
     async fn get_transaction_error(&self, tx_hash: TxHash) -> RpcResult<Option<Bytes>>;
 
     /// Extract all variations of calls, contract creation and self-destructs and returns a call
-    /// tree.
+    /// tree. Calls to the read precompiles are decoded.
     #[method(name = "traceTransaction")]
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>>;
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<OtsTraceEntry>>>;
 
     /// Tailor-made and expanded version of eth_getBlockByNumber for block details page in
     /// Otterscan.
@@ -1952,6 +1952,16 @@ impl TransportRpcModules {
         &self.config
     }
 
+    /// Returns the module of the http transport, if configured.
+    pub const fn http_module(&self) -> Option<&RpcModule<()>> {
+        self.http.as_ref()
+    }
+
+    /// Returns the module of the ws transport, if configured.
+    pub const fn ws_module(&self) -> Option<&RpcModule<()>> {
+        self.ws.as_ref()
+    }
+
     /// Merge the given [`Methods`] in all configured transport modules if the given
     /// [`RethRpcModule`] is configured for the transport.
     ///
@@ -10,6 +10,7 @@ use alloy_primitives::Sealable;
 use alloy_rlp::Encodable;
 use alloy_rpc_types_eth::{Block, BlockTransactions, Header, Index};
 use futures::Future;
+use reth_hyperliquid_types::hidden_tx_count;
 use reth_node_api::BlockBody;
 use reth_primitives::{RecoveredBlock, SealedBlock};
 use reth_provider::{
@@ -75,11 +76,11 @@ pub trait EthBlocks: LoadBlock {
         async move {
             if block_id.is_pending() {
                 // Pending block can be fetched directly without need for caching
-                return Ok(self
-                    .provider()
-                    .pending_block()
-                    .map_err(Self::Error::from_eth_err)?
-                    .map(|block| block.body().transactions().len()))
+                let block = self.provider().pending_block().map_err(Self::Error::from_eth_err)?;
+                return Ok(block.map(|block| {
+                    let transactions = block.body().transactions();
+                    transactions.len() - hidden_tx_count(transactions)
+                }))
             }
 
             let block_hash = match self
@@ -96,7 +97,10 @@ pub trait EthBlocks: LoadBlock {
                 .get_sealed_block_with_senders(block_hash)
                 .await
                 .map_err(Self::Error::from_eth_err)?
-                .map(|b| b.body().transaction_count()))
+                .map(|b| {
+                    let transactions = b.body().transactions();
+                    transactions.len() - hidden_tx_count(transactions)
+                }))
         }
     }
 
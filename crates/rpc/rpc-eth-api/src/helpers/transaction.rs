@@ -13,6 +13,9 @@ use alloy_network::TransactionBuilder;
 use alloy_primitives::{Address, Bytes, TxHash, B256};
 use alloy_rpc_types_eth::{transaction::TransactionRequest, BlockNumberOrTag, TransactionInfo};
 use futures::Future;
+use reth_hyperliquid_types::{
+    hidden_tx_count, is_hidden_tx, is_hl_node_compliant, is_system_tx, system_tx_count,
+};
 use reth_node_api::BlockBody;
 use reth_primitives::{transaction::SignedTransaction, RecoveredBlock};
 use reth_provider::{
@@ -65,7 +68,8 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
     ///
     /// Checks the pool and state.
     ///
-    /// Returns `Ok(None)` if no matching transaction was found.
+    /// Returns `Ok(None)` if no matching transaction was found, or if the transaction is hidden
+    /// from the current request. The index is the one of the user-visible block.
     #[expect(clippy::complexity)]
     fn transaction_by_hash(
         &self,
@@ -73,7 +77,36 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
     ) -> impl Future<
         Output = Result<Option<TransactionSource<ProviderTx<Self::Provider>>>, Self::Error>,
     > + Send {
-        LoadTransaction::transaction_by_hash(self, hash)
+        async move {
+            match LoadTransaction::transaction_by_hash(self, hash).await? {
+                Some(TransactionSource::Block {
+                    transaction,
+                    index,
+                    block_hash,
+                    block_number,
+                    base_fee,
+                }) if is_hl_node_compliant() => {
+                    if is_system_tx(&*transaction) {
+                        return Ok(None)
+                    }
+                    let hidden = self
+                        .cache()
+                        .get_sealed_block_with_senders(block_hash)
+                        .await
+                        .map_err(Self::Error::from_eth_err)?
+                        .map(|block| system_tx_count(block.body().transactions()))
+                        .unwrap_or_default();
+                    Ok(Some(TransactionSource::Block {
+                        transaction,
+                        index: index.saturating_sub(hidden as u64),
+                        block_hash,
+                        block_number,
+                        base_fee,
+                    }))
+                }
+                tx => Ok(tx),
+            }
+        }
     }
 
     /// Get all transactions in the block with the given hash.
@@ -142,7 +175,8 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
 
     /// Returns the transaction receipt for the given hash.
     ///
-    /// Returns None if the transaction does not exist or is pending
+    /// Returns None if the transaction does not exist, is pending or is hidden from the current
+    /// request.
     /// Note: The tx receipt is not available for pending transactions.
     fn transaction_receipt(
         &self,
@@ -153,6 +187,7 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
     {
         async move {
             match self.load_transaction_and_receipt(hash).await? {
+                Some((tx, _, _)) if is_hidden_tx(&tx) => Ok(None),
                 Some((tx, meta, receipt)) => {
                     self.build_transaction_receipt(tx, meta, receipt).await.map(Some)
                 }
@@ -196,6 +231,8 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
 
     /// Get transaction by [`BlockId`] and index of transaction within that block.
     ///
+    /// The index is the one of the user-visible block, see [`hidden_tx_count`].
+    ///
     /// Returns `Ok(None)` if the block does not exist, or index is out of range.
     fn transaction_by_block_and_tx_index(
         &self,
@@ -210,7 +247,10 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                 let block_hash = block.hash();
                 let block_number = block.number();
                 let base_fee_per_gas = block.base_fee_per_gas();
-                if let Some((signer, tx)) = block.transactions_with_sender().nth(index) {
+                let hidden = hidden_tx_count(block.body().transactions());
+                if let Some((signer, tx)) =
+                    block.transactions_with_sender().nth(index.saturating_add(hidden))
+                {
                     let tx_info = TransactionInfo {
                         hash: Some(*tx.tx_hash()),
                         block_hash: Some(block_hash),
@@ -284,10 +324,12 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                     let block_hash = block.hash();
                     let block_number = block.number();
                     let base_fee_per_gas = block.base_fee_per_gas();
+                    let hidden = hidden_tx_count(block.body().transactions());
 
                     block
                         .transactions_with_sender()
                         .enumerate()
+                        .skip(hidden)
                         .find(|(_, (signer, tx))| **signer == sender && (*tx).nonce() == nonce)
                         .map(|(index, (signer, tx))| {
                             let tx_info = TransactionInfo {
@@ -295,7 +337,7 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
                                 block_hash: Some(block_hash),
                                 block_number: Some(block_number),
                                 base_fee: base_fee_per_gas,
-                                index: Some(index as u64),
+                                index: Some((index - hidden) as u64),
                             };
                             self.tx_resp_builder().fill(tx.clone().with_signer(*signer), tx_info)
                         })
@@ -307,6 +349,8 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
 
     /// Get transaction, as raw bytes, by [`BlockId`] and index of transaction within that block.
     ///
+    /// The index is the one of the user-visible block, see [`hidden_tx_count`].
+    ///
     /// Returns `Ok(None)` if the block does not exist, or index is out of range.
     fn raw_transaction_by_block_and_tx_index(
         &self,
@@ -318,7 +362,9 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
     {
         async move {
             if let Some(block) = self.block_with_senders(block_id).await? {
-                if let Some(tx) = block.body().transactions().get(index) {
+                let transactions = block.body().transactions();
+                let hidden = hidden_tx_count(transactions);
+                if let Some(tx) = transactions.get(index.saturating_add(hidden)) {
                     return Ok(Some(tx.encoded_2718().into()))
                 }
             }
@@ -9,6 +9,7 @@ use alloy_sol_types::{ContractError, RevertReason};
 pub use api::{AsEthApiError, FromEthApiError, FromEvmError, IntoEthApiError};
 use core::time::Duration;
 use reth_errors::RethError;
+use reth_hyperliquid_types::{PrecompileDataUnavailable, PRECOMPILE_DATA_UNAVAILABLE_CODE};
 use reth_primitives_traits::transaction::signed::RecoveryError;
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
@@ -142,6 +143,10 @@ pub enum EthApiError {
     /// Evm precompile error
     #[error("Revm precompile error: {0}")]
     EvmPrecompile(String),
+    /// The read precompile calls of the block are unavailable, so a call to a read precompile
+    /// cannot be replayed.
+    #[error(transparent)]
+    PrecompileDataUnavailable(#[from] PrecompileDataUnavailable),
     /// Error encountered when converting a transaction type
     #[error("Transaction conversion error")]
     TransactionConversionError,
@@ -230,6 +235,9 @@ impl From<EthApiError> for jsonrpsee_types::error::ErrorObject<'static> {
             err @ EthApiError::TransactionInputError(_) => invalid_params_rpc_err(err.to_string()),
             EthApiError::Other(err) => err.to_rpc_error(),
             EthApiError::MuxTracerError(msg) => internal_rpc_err(msg.to_string()),
+            err @ EthApiError::PrecompileDataUnavailable(_) => {
+                rpc_error_with_code(PRECOMPILE_DATA_UNAVAILABLE_CODE, err.to_string())
+            }
         }
     }
 }
@@ -297,7 +305,11 @@ where
             EVMError::Header(err) => err.into(),
             EVMError::Database(err) => err.into(),
             EVMError::Custom(err) => Self::EvmCustom(err),
-            EVMError::Precompile(err) => Self::EvmPrecompile(err),
+            // The read precompiles report unavailable data on the thread running the EVM.
+            EVMError::Precompile(err) => match PrecompileDataUnavailable::take_reported() {
+                Some(err) => Self::PrecompileDataUnavailable(err),
+                None => Self::EvmPrecompile(err),
+            },
         }
     }
 }
@@ -841,6 +853,26 @@ mod tests {
         assert_eq!(err.message(), "block not found: finalized");
     }
 
+    #[test]
+    fn precompile_data_unavailable_error() {
+        PrecompileDataUnavailable { height: 100 }.report();
+        let err = EthApiError::from(EVMError::<EthApiError>::Precompile(
+            "fatal precompile error".to_string(),
+        ));
+        assert!(matches!(
+            err,
+            EthApiError::PrecompileDataUnavailable(PrecompileDataUnavailable { height: 100 })
+        ));
+        let err: jsonrpsee_types::error::ErrorObject<'static> = err.into();
+        assert_eq!(err.code(), PRECOMPILE_DATA_UNAVAILABLE_CODE);
+        assert_eq!(err.message(), "precompile data unavailable for block 100");
+
+        // Only a reported error is unavailable data, whatever the message of the EVM error.
+        let message = PrecompileDataUnavailable { height: 100 }.to_string();
+        let err = EthApiError::from(EVMError::<EthApiError>::Precompile(message));
+        assert!(matches!(err, EthApiError::EvmPrecompile(_)));
+    }
+
     #[test]
     fn revert_err_display() {
         let revert = Revert::from("test_revert_reason");
@@ -8,6 +8,7 @@ use alloy_primitives::TxHash;
 use alloy_rpc_types_eth::{FilteredParams, Log};
 use reth_chainspec::ChainInfo;
 use reth_errors::ProviderError;
+use reth_hyperliquid_types::hidden_receipt_count;
 use reth_primitives::RecoveredBlock;
 use reth_primitives_traits::{BlockBody, SignedTransaction};
 use reth_storage_api::{BlockReader, ProviderBlock};
@@ -27,14 +28,14 @@ where
     let mut all_logs = Vec::new();
     // Tracks the index of a log in the entire block.
     let mut log_index: u64 = 0;
-    let is_in_hl_node_compliant_mode = is_in_hl_node_compliant_mode();
+    let tx_hashes_and_receipts: Vec<_> = tx_hashes_and_receipts.into_iter().collect();
+    // Hidden transactions are skipped, the remaining ones are numbered from zero.
+    let hidden = hidden_receipt_count(tx_hashes_and_receipts.iter().map(|(_, receipt)| *receipt));
     // Iterate over transaction hashes and receipts and append matching logs.
-    for (receipt_idx, (tx_hash, receipt)) in tx_hashes_and_receipts.into_iter().enumerate() {
+    for (receipt_idx, (tx_hash, receipt)) in
+        tx_hashes_and_receipts.into_iter().skip(hidden).enumerate()
+    {
         for log in receipt.logs() {
-            if is_in_hl_node_compliant_mode && receipt.cumulative_gas_used() == 0 {
-                continue;
-            }
-
             if log_matches_filter(block_num_hash, log, filter) {
                 let log = Log {
                     inner: log.clone(),
@@ -64,10 +65,6 @@ pub enum ProviderOrBlock<'a, P: BlockReader> {
     Block(Arc<RecoveredBlock<ProviderBlock<P>>>),
 }
 
-fn is_in_hl_node_compliant_mode() -> bool {
-    std::env::var("HL_NODE_COMPLIANT").is_ok()
-}
-
 /// Appends all matching logs of a block's receipts.
 /// If the log matches, look up the corresponding transaction hash.
 pub fn append_matching_block_logs<P>(
@@ -90,18 +87,15 @@ where
     // prevents re-querying the block body indices.
     let mut loaded_first_tx_num = None;
 
-    let is_in_hl_node_compliant_mode = is_in_hl_node_compliant_mode();
+    // Hidden transactions are skipped, the remaining ones are numbered from zero.
+    let hidden = hidden_receipt_count(receipts);
 
     // Iterate over receipts and append matching logs.
-    for (receipt_idx, receipt) in receipts.iter().enumerate() {
+    for (receipt_idx, receipt) in receipts.iter().enumerate().skip(hidden) {
         // The transaction hash of the current receipt.
         let mut transaction_hash = None;
 
         for log in receipt.logs() {
-            if is_in_hl_node_compliant_mode && receipt.cumulative_gas_used() == 0 {
-                continue;
-            }
-
             if log_matches_filter(block_num_hash, log, filter) {
                 // if this is the first match in the receipt's logs, look up the transaction hash
                 if transaction_hash.is_none() {
@@ -142,7 +136,7 @@ where
                     block_number: Some(block_num_hash.number),
                     transaction_hash,
                     // The transaction and receipt index is always the same.
-                    transaction_index: Some(receipt_idx as u64),
+                    transaction_index: Some((receipt_idx - hidden) as u64),
                     log_index: Some(log_index),
                     removed,
                     block_timestamp: Some(block_timestamp),
@@ -5,6 +5,7 @@ use alloy_consensus::{transaction::TransactionMeta, ReceiptEnvelope, TxReceipt};
 use alloy_eips::eip7840::BlobParams;
 use alloy_primitives::{Address, TxKind};
 use alloy_rpc_types_eth::{Log, ReceiptWithBloom, TransactionReceipt};
+use reth_hyperliquid_types::hidden_receipt_count;
 use reth_primitives::{Receipt, TransactionSigned, TxType};
 use reth_primitives_traits::SignedTransaction;
 
@@ -43,9 +44,13 @@ where
 
     let logs_bloom = receipt.bloom();
 
+    // hidden transactions are skipped when numbering the transaction and its logs
+    let hidden = hidden_receipt_count(all_receipts);
+    let transaction_index = meta.index.saturating_sub(hidden as u64);
+
     // get number of logs in the block
     let mut num_logs = 0;
-    for prev_receipt in all_receipts.iter().take(meta.index as usize) {
+    for prev_receipt in all_receipts.iter().take(meta.index as usize).skip(hidden) {
         num_logs += prev_receipt.logs().len();
     }
 
@@ -59,7 +64,7 @@ where
             block_number: Some(meta.block_number),
             block_timestamp: Some(meta.timestamp),
             transaction_hash: Some(meta.tx_hash),
-            transaction_index: Some(meta.index),
+            transaction_index: Some(transaction_index),
             log_index: Some((num_logs + tx_log_idx) as u64),
             removed: false,
         })
@@ -79,7 +84,7 @@ where
     Ok(TransactionReceipt {
         inner: build_envelope(ReceiptWithBloom { receipt: rpc_receipt, logs_bloom }),
         transaction_hash: meta.tx_hash,
-        transaction_index: Some(meta.index),
+        transaction_index: Some(transaction_index),
         block_hash: Some(meta.block_hash),
         block_number: Some(meta.block_number),
         from,
@@ -1,3 +1,4 @@
+use crate::precompile_abi::{decode_call_frame, decode_precompiles_enabled};
 use alloy_consensus::BlockHeader;
 use alloy_eips::{eip2718::Encodable2718, BlockId, BlockNumberOrTag};
 use alloy_genesis::ChainConfig;
@@ -20,6 +21,7 @@ use reth_evm::{
     execute::{BlockExecutorProvider, Executor},
     ConfigureEvmEnv, EvmEnv,
 };
+use reth_hyperliquid_types::hidden_tx_count;
 use reth_primitives::{NodePrimitives, ReceiptWithBloom, RecoveredBlock};
 use reth_primitives_traits::{Block as _, BlockBody, SignedTransaction};
 use reth_provider::{
@@ -100,7 +102,8 @@ where
         evm_env: EvmEnv<<Eth::Evm as ConfigureEvmEnv>::Spec>,
         opts: GethDebugTracingOptions,
     ) -> Result<Vec<TraceResult>, Eth::Error> {
-        // replay all transactions of the block
+        // replay all transactions of the block, the results of hidden transactions are dropped
+        let hidden = hidden_tx_count(block.body().transactions());
         let this = self.clone();
         self.eth_api()
             .spawn_with_state_at_block(block.parent_hash().into(), move |state| {
@@ -124,14 +127,16 @@ where
                         Some(TransactionContext {
                             block_hash: Some(block.hash()),
                             tx_hash: Some(tx_hash),
-                            tx_index: Some(index),
+                            tx_index: Some(index.saturating_sub(hidden)),
                         }),
                         &mut inspector,
                     )?;
 
                     inspector = inspector.map(|insp| insp.fused());
 
-                    results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+                    if index >= hidden {
+                        results.push(TraceResult::Success { result, tx_hash: Some(tx_hash) });
+                    }
                     if transactions.peek().is_some() {
                         // need to apply the state changes of this transaction before executing the
                         // next transaction
@@ -298,6 +303,7 @@ where
                         return Ok(FourByteFrame::from(&inspector).into())
                     }
                     GethDebugBuiltInTracerType::CallTracer => {
+                        let decode_precompiles = decode_precompiles_enabled(&tracer_config);
                         let call_config = tracer_config
                             .into_call_config()
                             .map_err(|_| EthApiError::InvalidTracerConfig)?;
@@ -309,12 +315,16 @@ where
                         let frame = self
                             .eth_api()
                             .spawn_with_call_at(call, at, overrides, move |db, evm_env, tx_env| {
+                                let block_number = evm_env.block_env.number;
                                 let (res, (_, tx_env)) =
                                     this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                                 let frame = inspector
                                     .with_transaction_gas_limit(tx_env.gas_limit())
                                     .into_geth_builder()
                                     .geth_call_traces(call_config, res.result.gas_used());
+                                if decode_precompiles {
+                                    return Ok(decode_call_frame(frame, block_number))
+                                }
                                 Ok(frame.into())
                             })
                             .await?;
@@ -727,6 +737,7 @@ where
                             ))
                         });
 
+                        let block_number = evm_env.block_env.number;
                         let (res, (_, tx_env)) =
                             self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
 
@@ -736,6 +747,9 @@ where
                             .geth_builder()
                             .geth_call_traces(call_config, res.result.gas_used());
 
+                        if decode_precompiles_enabled(tracer_config) {
+                            return Ok((decode_call_frame(frame, block_number), res.state))
+                        }
                         return Ok((frame.into(), res.state))
                     }
                     GethDebugBuiltInTracerType::PreStateTracer => {
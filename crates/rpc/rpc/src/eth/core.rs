@@ -3,10 +3,12 @@
 
 use std::sync::Arc;
 
-use crate::{eth::EthTxBuilder, EthApiBuilder};
+use crate::{
+    eth::{EthRpcTypes, EthTxBuilder},
+    EthApiBuilder,
+};
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockNumberOrTag;
-use alloy_network::Ethereum;
 use alloy_primitives::{Bytes, U256};
 use derive_more::Deref;
 use reth_node_api::{FullNodeComponents, FullNodeTypes};
@@ -194,7 +196,7 @@ where
     Provider: BlockReader,
 {
     type Error = EthApiError;
-    type NetworkTypes = Ethereum;
+    type NetworkTypes = EthRpcTypes;
     type TransactionCompat = EthTxBuilder;
 
     fn tx_resp_builder(&self) -> &Self::TransactionCompat {
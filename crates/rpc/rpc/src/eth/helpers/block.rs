@@ -3,6 +3,7 @@
 use alloy_consensus::{transaction::TransactionMeta, BlockHeader};
 use alloy_rpc_types_eth::{BlockId, TransactionReceipt};
 use reth_chainspec::EthChainSpec;
+use reth_hyperliquid_types::hidden_receipt_count;
 use reth_primitives_traits::{BlockBody, SignedTransaction};
 use reth_provider::{BlockReader, ChainSpecProvider};
 use reth_rpc_eth_api::{
@@ -40,6 +41,7 @@ where
             let excess_blob_gas = block.excess_blob_gas();
             let timestamp = block.timestamp();
             let blob_params = self.provider().chain_spec().blob_params_at_timestamp(timestamp);
+            let hidden = hidden_receipt_count(receipts.iter());
 
             return block
                 .body()
@@ -47,6 +49,7 @@ where
                 .iter()
                 .zip(receipts.iter())
                 .enumerate()
+                .skip(hidden)
                 .map(|(idx, (tx, receipt))| {
                     let meta = TransactionMeta {
                         tx_hash: *tx.tx_hash(),
@@ -1,15 +1,21 @@
 //! L1 `eth` API types.
 
-use alloy_consensus::{Transaction as _, TxEip4844Variant, TxEnvelope};
-use alloy_network::{Ethereum, Network};
-use alloy_primitives::PrimitiveSignature as Signature;
+use alloy_consensus::{
+    Transaction as TransactionTrait, TxEip4844Variant, TxEnvelope, TxLegacy, Typed2718,
+};
+use alloy_eips::{eip2930::AccessList, eip7702::SignedAuthorization};
+use alloy_network::{Ethereum, Network, TransactionResponse};
+use alloy_primitives::{
+    Address, BlockHash, Bytes, ChainId, PrimitiveSignature as Signature, TxHash, TxKind, B256, U256,
+};
 use alloy_rpc_types::TransactionRequest;
 use alloy_rpc_types_eth::{Transaction, TransactionInfo};
-use reth_primitives::{Recovered, TransactionSigned};
+use reth_primitives::{Recovered, TransactionSigned, TxSystem, SYSTEM_TX_TYPE_ID};
 use reth_primitives_traits::SignedTransaction;
-use reth_rpc_eth_api::EthApiTypes;
+use reth_rpc_eth_api::{types::RpcTypes, EthApiTypes};
 use reth_rpc_eth_types::EthApiError;
 use reth_rpc_types_compat::TransactionCompat;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 
 /// A standalone [`EthApiTypes`] implementation for Ethereum.
 #[derive(Debug, Clone, Copy, Default)]
@@ -17,7 +23,7 @@ pub struct EthereumEthApiTypes(EthTxBuilder);
 
 impl EthApiTypes for EthereumEthApiTypes {
     type Error = EthApiError;
-    type NetworkTypes = Ethereum;
+    type NetworkTypes = EthRpcTypes;
     type TransactionCompat = EthTxBuilder;
 
     fn tx_resp_builder(&self) -> &Self::TransactionCompat {
@@ -25,6 +31,224 @@ impl EthApiTypes for EthereumEthApiTypes {
     }
 }
 
+/// RPC response types of l1, those of [`Ethereum`] with system transactions, see
+/// [`EthRpcTransaction`].
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct EthRpcTypes;
+
+impl RpcTypes for EthRpcTypes {
+    type Header = <Ethereum as Network>::HeaderResponse;
+    type Receipt = <Ethereum as Network>::ReceiptResponse;
+    type Transaction = EthRpcTransaction;
+}
+
+/// Transaction in the RPC responses of l1.
+///
+/// System transactions are not signed, so they are returned with their sender, and with their
+/// own type, [`SYSTEM_TX_TYPE_ID`], instead of the signature of their legacy encoding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EthRpcTransaction {
+    /// Hyperliquid system transaction.
+    System(RpcSystemTransaction),
+    /// Signed transaction.
+    Ethereum(Transaction),
+}
+
+/// A system transaction in RPC responses, see [`TxSystem`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSystemTransaction {
+    /// Type of the transaction, [`SYSTEM_TX_TYPE_ID`].
+    #[serde(rename = "type")]
+    ty: SystemTxType,
+    /// Hash of the legacy encoding of the transaction.
+    pub hash: TxHash,
+    /// System address the transaction is executed on behalf of.
+    pub from: Address,
+    /// The executed transaction.
+    #[serde(flatten)]
+    pub tx: TxLegacy,
+    /// Hash of the block the transaction is included in, `None` if pending.
+    #[serde(default)]
+    pub block_hash: Option<BlockHash>,
+    /// Number of the block the transaction is included in, `None` if pending.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub block_number: Option<u64>,
+    /// Index of the transaction in its block.
+    #[serde(default, with = "alloy_serde::quantity::opt")]
+    pub transaction_index: Option<u64>,
+}
+
+impl RpcSystemTransaction {
+    /// Returns the RPC representation of the system transaction `tx`, with the hash `hash`.
+    pub fn new(tx: TxSystem, hash: TxHash, tx_info: TransactionInfo) -> Self {
+        let TxSystem { from, tx } = tx;
+        let TransactionInfo { block_hash, block_number, index: transaction_index, .. } = tx_info;
+        Self { ty: SystemTxType, hash, from, tx, block_hash, block_number, transaction_index }
+    }
+}
+
+/// The `type` field of [`RpcSystemTransaction`], which only accepts [`SYSTEM_TX_TYPE_ID`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct SystemTxType;
+
+impl Serialize for SystemTxType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        alloy_serde::quantity::serialize(&SYSTEM_TX_TYPE_ID, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SystemTxType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ty: u8 = alloy_serde::quantity::deserialize(deserializer)?;
+        if ty != SYSTEM_TX_TYPE_ID {
+            return Err(D::Error::custom(format!("unexpected system transaction type {ty}")))
+        }
+        Ok(Self)
+    }
+}
+
+macro_rules! delegate {
+    ($self:expr => $method:ident($($arg:expr),*)) => {
+        match $self {
+            EthRpcTransaction::System(tx) => TransactionTrait::$method(&tx.tx, $($arg),*),
+            EthRpcTransaction::Ethereum(tx) => TransactionTrait::$method(tx, $($arg),*),
+        }
+    };
+}
+
+impl Typed2718 for EthRpcTransaction {
+    fn ty(&self) -> u8 {
+        match self {
+            Self::System(_) => SYSTEM_TX_TYPE_ID,
+            Self::Ethereum(tx) => tx.ty(),
+        }
+    }
+}
+
+impl TransactionTrait for EthRpcTransaction {
+    fn chain_id(&self) -> Option<ChainId> {
+        delegate!(self => chain_id())
+    }
+
+    fn nonce(&self) -> u64 {
+        delegate!(self => nonce())
+    }
+
+    fn gas_limit(&self) -> u64 {
+        delegate!(self => gas_limit())
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        delegate!(self => gas_price())
+    }
+
+    fn max_fee_per_gas(&self) -> u128 {
+        delegate!(self => max_fee_per_gas())
+    }
+
+    fn max_priority_fee_per_gas(&self) -> Option<u128> {
+        delegate!(self => max_priority_fee_per_gas())
+    }
+
+    fn max_fee_per_blob_gas(&self) -> Option<u128> {
+        delegate!(self => max_fee_per_blob_gas())
+    }
+
+    fn priority_fee_or_price(&self) -> u128 {
+        delegate!(self => priority_fee_or_price())
+    }
+
+    fn effective_gas_price(&self, base_fee: Option<u64>) -> u128 {
+        delegate!(self => effective_gas_price(base_fee))
+    }
+
+    fn is_dynamic_fee(&self) -> bool {
+        delegate!(self => is_dynamic_fee())
+    }
+
+    fn kind(&self) -> TxKind {
+        delegate!(self => kind())
+    }
+
+    fn is_create(&self) -> bool {
+        delegate!(self => is_create())
+    }
+
+    fn value(&self) -> U256 {
+        delegate!(self => value())
+    }
+
+    fn input(&self) -> &Bytes {
+        delegate!(self => input())
+    }
+
+    fn access_list(&self) -> Option<&AccessList> {
+        delegate!(self => access_list())
+    }
+
+    fn blob_versioned_hashes(&self) -> Option<&[B256]> {
+        delegate!(self => blob_versioned_hashes())
+    }
+
+    fn authorization_list(&self) -> Option<&[SignedAuthorization]> {
+        delegate!(self => authorization_list())
+    }
+}
+
+impl TransactionResponse for EthRpcTransaction {
+    fn tx_hash(&self) -> TxHash {
+        match self {
+            Self::System(tx) => tx.hash,
+            Self::Ethereum(tx) => tx.tx_hash(),
+        }
+    }
+
+    fn block_hash(&self) -> Option<BlockHash> {
+        match self {
+            Self::System(tx) => tx.block_hash,
+            Self::Ethereum(tx) => tx.block_hash,
+        }
+    }
+
+    fn block_number(&self) -> Option<u64> {
+        match self {
+            Self::System(tx) => tx.block_number,
+            Self::Ethereum(tx) => tx.block_number,
+        }
+    }
+
+    fn transaction_index(&self) -> Option<u64> {
+        match self {
+            Self::System(tx) => tx.transaction_index,
+            Self::Ethereum(tx) => tx.transaction_index,
+        }
+    }
+
+    fn from(&self) -> Address {
+        match self {
+            Self::System(tx) => tx.from,
+            Self::Ethereum(tx) => tx.from,
+        }
+    }
+
+    fn gas_price(&self) -> Option<u128> {
+        match self {
+            Self::System(tx) => Some(tx.tx.gas_price),
+            Self::Ethereum(tx) => TransactionResponse::gas_price(tx),
+        }
+    }
+
+    fn max_fee_per_gas(&self) -> Option<u128> {
+        match self {
+            Self::System(_) => None,
+            Self::Ethereum(tx) => TransactionResponse::max_fee_per_gas(tx),
+        }
+    }
+}
+
 /// Builds RPC transaction response for l1.
 #[derive(Debug, Clone, Copy, Default)]
 #[non_exhaustive]
@@ -34,7 +258,7 @@ impl TransactionCompat for EthTxBuilder
 where
     Self: Send + Sync,
 {
-    type Transaction = <Ethereum as Network>::TransactionResponse;
+    type Transaction = EthRpcTransaction;
 
     type Error = EthApiError;
 
@@ -44,6 +268,11 @@ where
         tx_info: TransactionInfo,
     ) -> Result<Self::Transaction, Self::Error> {
         let (tx, from) = tx.into_parts();
+        if let Some(system_tx) = tx.as_system() {
+            let tx = RpcSystemTransaction::new(system_tx.clone(), *tx.tx_hash(), tx_info);
+            return Ok(EthRpcTransaction::System(tx))
+        }
+
         let from = tx.recover_signer().unwrap_or(from);
         let inner: TxEnvelope = tx.into();
 
@@ -57,14 +286,14 @@ where
             })
             .unwrap_or_else(|| inner.max_fee_per_gas());
 
-        Ok(Transaction {
+        Ok(EthRpcTransaction::Ethereum(Transaction {
             inner,
             block_hash,
             block_number,
             transaction_index,
             from,
             effective_gas_price: Some(effective_gas_price),
-        })
+        }))
     }
 
     fn build_simulate_v1_transaction(
@@ -81,16 +310,92 @@ where
     }
 
     fn otterscan_api_truncate_input(tx: &mut Self::Transaction) {
-        let input = match &mut tx.inner {
-            TxEnvelope::Eip1559(tx) => &mut tx.tx_mut().input,
-            TxEnvelope::Eip2930(tx) => &mut tx.tx_mut().input,
-            TxEnvelope::Legacy(tx) => &mut tx.tx_mut().input,
-            TxEnvelope::Eip4844(tx) => match tx.tx_mut() {
-                TxEip4844Variant::TxEip4844(tx) => &mut tx.input,
-                TxEip4844Variant::TxEip4844WithSidecar(tx) => &mut tx.tx.input,
+        let input = match tx {
+            EthRpcTransaction::System(tx) => &mut tx.tx.input,
+            EthRpcTransaction::Ethereum(tx) => match &mut tx.inner {
+                TxEnvelope::Eip1559(tx) => &mut tx.tx_mut().input,
+                TxEnvelope::Eip2930(tx) => &mut tx.tx_mut().input,
+                TxEnvelope::Legacy(tx) => &mut tx.tx_mut().input,
+                TxEnvelope::Eip4844(tx) => match tx.tx_mut() {
+                    TxEip4844Variant::TxEip4844(tx) => &mut tx.input,
+                    TxEip4844Variant::TxEip4844WithSidecar(tx) => &mut tx.tx.input,
+                },
+                TxEnvelope::Eip7702(tx) => &mut tx.tx_mut().input,
             },
-            TxEnvelope::Eip7702(tx) => &mut tx.tx_mut().input,
         };
         *input = input.slice(..4);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, bytes};
+    use reth_primitives::Transaction as PrimitiveTransaction;
+
+    fn tx_info(hash: TxHash) -> TransactionInfo {
+        TransactionInfo {
+            hash: Some(hash),
+            index: Some(1),
+            block_hash: Some(B256::with_last_byte(1)),
+            block_number: Some(2),
+            base_fee: Some(7),
+        }
+    }
+
+    fn roundtrip(tx: &EthRpcTransaction) -> serde_json::Value {
+        let json = serde_json::to_value(tx).unwrap();
+        let decoded: EthRpcTransaction = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(&decoded, tx);
+        json
+    }
+
+    #[test]
+    fn system_transaction_roundtrip() {
+        let system_tx = TxSystem {
+            from: address!("2000000000000000000000000000000000000097"),
+            tx: TxLegacy {
+                chain_id: Some(999),
+                nonce: 3,
+                gas_limit: 21_000,
+                to: TxKind::Call(address!("d3e8763675e4c425df46cc3b5c0f6cbdac396046")),
+                input: bytes!("a9059cbb"),
+                ..Default::default()
+            },
+        };
+        let tx = TransactionSigned::from(system_tx.clone());
+        let hash = *tx.tx_hash();
+        let rpc_tx =
+            EthTxBuilder.fill(Recovered::new_unchecked(tx, system_tx.from), tx_info(hash)).unwrap();
+
+        let json = roundtrip(&rpc_tx);
+        assert_eq!(json["type"], "0x7f");
+        assert_eq!(json["from"], serde_json::to_value(system_tx.from).unwrap());
+        for field in ["r", "s", "v", "yParity"] {
+            assert!(json.get(field).is_none(), "{field} is set");
+        }
+
+        let EthRpcTransaction::System(system) = &rpc_tx else { panic!("not a system tx") };
+        assert_eq!(system.tx, system_tx.tx);
+        assert_eq!(rpc_tx.tx_hash(), hash);
+        assert_eq!(TransactionResponse::from(&rpc_tx), system_tx.from);
+        assert_eq!(rpc_tx.block_number(), Some(2));
+    }
+
+    #[test]
+    fn signed_transaction_roundtrip() {
+        let tx = TransactionSigned::new_unhashed(
+            PrimitiveTransaction::Legacy(TxLegacy { gas_price: 10, ..Default::default() }),
+            Signature::test_signature(),
+        );
+        let hash = *tx.tx_hash();
+        let rpc_tx = EthTxBuilder
+            .fill(Recovered::new_unchecked(tx, Address::with_last_byte(1)), tx_info(hash))
+            .unwrap();
+
+        let json = roundtrip(&rpc_tx);
+        assert_ne!(json["type"], "0x7f");
+        assert!(matches!(rpc_tx, EthRpcTransaction::Ethereum(_)));
+        assert_eq!(rpc_tx.tx_hash(), hash);
+    }
+}
@@ -17,7 +17,9 @@ pub use pubsub::EthPubSub;
 
 pub use helpers::{
     signer::DevSigner,
-    types::{EthTxBuilder, EthereumEthApiTypes},
+    types::{
+        EthRpcTransaction, EthRpcTypes, EthTxBuilder, EthereumEthApiTypes, RpcSystemTransaction,
+    },
 };
 
 pub use reth_rpc_eth_api::{EthApiServer, EthApiTypes, FullEthApiServer, RpcNodeCore};
@@ -11,6 +11,7 @@ use futures::StreamExt;
 use jsonrpsee::{
     server::SubscriptionMessage, types::ErrorObject, PendingSubscriptionSink, SubscriptionSink,
 };
+use reth_hyperliquid_types::{is_hl_node_compliant, with_hl_node_compliance};
 use reth_network_api::NetworkInfo;
 use reth_primitives::NodePrimitives;
 use reth_provider::{BlockNumReader, CanonStateSubscriptions};
@@ -20,7 +21,10 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::logs_utils;
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
 use reth_tasks::{TaskSpawner, TokioTaskExecutor};
-use reth_transaction_pool::{NewTransactionEvent, PoolConsensusTx, TransactionPool};
+use reth_transaction_pool::{
+    NewSubpoolTransactionStream, NewTransactionEvent, PoolConsensusTx, SubPool,
+    TransactionListenerKind, TransactionPool,
+};
 use serde::Serialize;
 use tokio_stream::{
     wrappers::{BroadcastStream, ReceiverStream},
@@ -37,6 +41,8 @@ pub struct EthPubSub<Eth> {
     inner: Arc<EthPubSubInner<Eth>>,
     /// The type that's used to spawn subscription tasks.
     subscription_task_spawner: Box<dyn TaskSpawner>,
+    /// hl-node compliant mode of the subscriptions, the node default if not set.
+    hl_node_compliant: Option<bool>,
 }
 
 // === impl EthPubSub ===
@@ -52,7 +58,13 @@ impl<Eth> EthPubSub<Eth> {
     /// Creates a new, shareable instance.
     pub fn with_spawner(eth_api: Eth, subscription_task_spawner: Box<dyn TaskSpawner>) -> Self {
         let inner = EthPubSubInner { eth_api };
-        Self { inner: Arc::new(inner), subscription_task_spawner }
+        Self { inner: Arc::new(inner), subscription_task_spawner, hl_node_compliant: None }
+    }
+
+    /// Serves the subscriptions in hl-node compliant mode `enabled` instead of the node default.
+    pub fn with_hl_node_compliance(mut self, enabled: bool) -> Self {
+        self.hl_node_compliant = Some(enabled);
+        self
     }
 }
 
@@ -75,9 +87,13 @@ where
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.inner.clone();
-        self.subscription_task_spawner.spawn(Box::pin(async move {
-            let _ = handle_accepted(pubsub, sink, kind, params).await;
-        }));
+        let hl_node_compliant = self.hl_node_compliant.unwrap_or_else(is_hl_node_compliant);
+        self.subscription_task_spawner.spawn(Box::pin(with_hl_node_compliance(
+            hl_node_compliant,
+            async move {
+                let _ = handle_accepted(pubsub, sink, kind, params).await;
+            },
+        )));
 
         Ok(())
     }
@@ -280,15 +296,24 @@ where
     Eth: RpcNodeCore<Pool: TransactionPool>,
 {
     /// Returns a stream that yields all transaction hashes emitted by the txpool.
+    ///
+    /// Private transactions are included, since transactions sent to the node are forwarded to the
+    /// upstream RPC and added to the pool as private transactions.
     fn pending_transaction_hashes_stream(&self) -> impl Stream<Item = TxHash> {
-        ReceiverStream::new(self.eth_api.pool().pending_transactions_listener())
+        ReceiverStream::new(
+            self.eth_api.pool().pending_transactions_listener_for(TransactionListenerKind::All),
+        )
     }
 
-    /// Returns a stream that yields all transactions emitted by the txpool.
+    /// Returns a stream that yields all transactions emitted by the txpool, private transactions
+    /// included.
     fn full_pending_transaction_stream(
         &self,
     ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
-        self.eth_api.pool().new_pending_pool_transactions_listener()
+        NewSubpoolTransactionStream::new(
+            self.eth_api.pool().new_transactions_listener_for(TransactionListenerKind::All),
+            SubPool::Pending,
+        )
     }
 }
 
@@ -39,6 +39,7 @@ pub mod eth;
 mod miner;
 mod net;
 mod otterscan;
+mod precompile_abi;
 mod reth;
 mod rpc;
 mod trace;
@@ -1,3 +1,4 @@
+use crate::precompile_abi::decode_trace_entry;
 use alloy_consensus::{BlockHeader, Transaction, Typed2718};
 use alloy_eips::{BlockId, BlockNumberOrTag};
 use alloy_network::{ReceiptResponse, TransactionResponse};
@@ -12,7 +13,7 @@ use alloy_rpc_types_trace::{
 };
 use async_trait::async_trait;
 use jsonrpsee::{core::RpcResult, types::ErrorObjectOwned};
-use reth_rpc_api::{EthApiServer, OtterscanServer};
+use reth_rpc_api::{EthApiServer, OtsTraceEntry, OtterscanServer};
 use reth_rpc_eth_api::{
     helpers::{EthTransactions, TraceExt},
     FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction, TransactionCompat,
@@ -140,31 +141,40 @@ where
     }
 
     /// Handler for `ots_traceTransaction`
-    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<TraceEntry>>> {
+    async fn trace_transaction(&self, tx_hash: TxHash) -> RpcResult<Option<Vec<OtsTraceEntry>>> {
         let traces = self
             .eth
             .spawn_trace_transaction_in_block(
                 tx_hash,
                 TracingInspectorConfig::default_parity(),
-                move |_tx_info, inspector, _, _| Ok(inspector.into_traces().into_nodes()),
+                move |tx_info, inspector, _, _| {
+                    Ok((
+                        tx_info.block_number.unwrap_or_default(),
+                        inspector.into_traces().into_nodes(),
+                    ))
+                },
             )
             .await
             .map_err(Into::into)?
-            .map(|traces| {
+            .map(|(block_number, traces)| {
                 traces
                     .into_iter()
-                    .map(|CallTraceNode { trace, .. }| TraceEntry {
-                        r#type: if trace.is_selfdestruct() {
-                            "SELFDESTRUCT".to_string()
-                        } else {
-                            trace.kind.to_string()
-                        },
-                        depth: trace.depth as u32,
-                        from: trace.caller,
-                        to: trace.address,
-                        value: trace.value,
-                        input: trace.data,
-                        output: trace.output,
+                    .map(|CallTraceNode { trace, .. }| {
+                        let success = trace.success;
+                        let entry = TraceEntry {
+                            r#type: if trace.is_selfdestruct() {
+                                "SELFDESTRUCT".to_string()
+                            } else {
+                                trace.kind.to_string()
+                            },
+                            depth: trace.depth as u32,
+                            from: trace.caller,
+                            to: trace.address,
+                            value: trace.value,
+                            input: trace.data,
+                            output: trace.output,
+                        };
+                        decode_trace_entry(entry, success, block_number)
                     })
                     .collect::<Vec<_>>()
             });
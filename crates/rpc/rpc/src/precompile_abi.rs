@@ -0,0 +1,82 @@
+//! Decoding of read precompile calls in traces.
+
+use alloy_rpc_types_trace::{
+    geth::{CallFrame, GethDebugTracerConfig, GethTrace},
+    otterscan::TraceEntry,
+};
+use reth_hyperliquid_types::decode_precompile_call;
+use reth_rpc_api::OtsTraceEntry;
+use serde_json::Value;
+
+/// `callTracer` option that annotates calls to the read precompiles with their decoded input and
+/// output.
+pub(crate) const DECODE_PRECOMPILES_OPTION: &str = "decodePrecompiles";
+
+/// Returns `true` if the tracer config enables [`DECODE_PRECOMPILES_OPTION`].
+pub(crate) fn decode_precompiles_enabled(config: &GethDebugTracerConfig) -> bool {
+    config.0.get(DECODE_PRECOMPILES_OPTION).and_then(Value::as_bool).unwrap_or(false)
+}
+
+/// Returns the call frame with a `decoded` field added to the calls to the read precompiles.
+pub(crate) fn decode_call_frame(frame: CallFrame, block: u64) -> GethTrace {
+    let Ok(mut value) = serde_json::to_value(&frame) else { return frame.into() };
+    annotate_call_frame(&frame, &mut value, block);
+    GethTrace::JS(value)
+}
+
+fn annotate_call_frame(frame: &CallFrame, value: &mut Value, block: u64) {
+    if let Some(to) = frame.to {
+        let output = frame.error.is_none().then_some(frame.output.as_deref()).flatten();
+        if let Some(decoded) = decode_precompile_call(to, block, &frame.input, output) {
+            if let (Some(object), Ok(decoded)) =
+                (value.as_object_mut(), serde_json::to_value(decoded))
+            {
+                object.insert("decoded".to_string(), decoded);
+            }
+        }
+    }
+    if let Some(calls) = value.get_mut("calls").and_then(Value::as_array_mut) {
+        for (call, value) in frame.calls.iter().zip(calls) {
+            annotate_call_frame(call, value, block);
+        }
+    }
+}
+
+/// Converts an otterscan trace entry, decoding it if it is a call to a read precompile.
+pub(crate) fn decode_trace_entry(entry: TraceEntry, success: bool, block: u64) -> OtsTraceEntry {
+    let output = success.then_some(entry.output.as_ref());
+    let decoded = decode_precompile_call(entry.to, block, &entry.input, output);
+    OtsTraceEntry { entry, decoded }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{address, Address, Bytes};
+
+    #[test]
+    fn annotates_nested_precompile_calls() {
+        let precompile_call = CallFrame {
+            to: Some(address!("0000000000000000000000000000000000000806")),
+            input: Bytes::from(vec![0; 32]),
+            output: Some(Bytes::from({
+                let mut output = vec![0; 32];
+                output[31] = 42;
+                output
+            })),
+            ..Default::default()
+        };
+        let frame = CallFrame {
+            to: Some(Address::with_last_byte(0x22)),
+            calls: vec![precompile_call],
+            ..Default::default()
+        };
+
+        let GethTrace::JS(value) = decode_call_frame(frame, 1) else { panic!("expected JSON") };
+        assert!(value.get("decoded").is_none());
+        assert_eq!(
+            value["calls"][0]["decoded"],
+            serde_json::json!({ "name": "markPx", "input": { "index": 0 }, "output": 42 })
+        );
+    }
+}
@@ -17,6 +17,7 @@ use jsonrpsee::core::RpcResult;
 use reth_chainspec::{EthChainSpec, EthereumHardfork, MAINNET, SEPOLIA};
 use reth_consensus_common::calc::{base_block_reward_pre_merge, block_reward, ommer_reward};
 use reth_evm::ConfigureEvmEnv;
+use reth_hyperliquid_types::hidden_tx_count;
 use reth_primitives_traits::{BlockBody, BlockHeader};
 use reth_provider::{BlockNumReader, BlockReader, ChainSpecProvider};
 use reth_revm::{database::StateProviderDatabase, db::CacheDB};
@@ -381,6 +382,15 @@ where
             maybe_traces.map(|traces| traces.into_iter().flatten().collect::<Vec<_>>());
 
         if let (Some(block), Some(traces)) = (maybe_block, maybe_traces.as_mut()) {
+            // drop the traces of hidden transactions and renumber the remaining ones
+            let hidden = hidden_tx_count(block.body().transactions()) as u64;
+            traces.retain(|trace| trace.transaction_position.is_none_or(|pos| pos >= hidden));
+            for position in
+                traces.iter_mut().filter_map(|trace| trace.transaction_position.as_mut())
+            {
+                *position -= hidden;
+            }
+
             if let Some(base_block_reward) = self.calculate_base_block_reward(block.header())? {
                 traces.extend(self.extract_reward_traces(
                     block.header(),
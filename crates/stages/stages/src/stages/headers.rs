@@ -281,6 +281,10 @@ where
                         error,
                     }))
                 }
+                Some(Err(HeadersDownloaderError::Download(error))) => {
+                    error!(target: "sync::stages::headers", %error, "Failed to download headers");
+                    return Poll::Ready(Err(StageError::Download(error)))
+                }
                 None => return Poll::Ready(Err(StageError::ChannelClosed)),
             }
         }
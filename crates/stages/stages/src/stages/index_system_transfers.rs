@@ -0,0 +1,203 @@
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use reth_config::config::IndexHistoryConfig;
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW},
+    models::{block_system_transfers, StoredSystemTransfer, SystemTransferKey},
+    tables,
+    transaction::DbTxMut,
+};
+use reth_primitives_traits::SignedTransaction;
+use reth_provider::{BlockReader, DBProvider};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::ops::RangeInclusive;
+use tracing::info;
+
+/// Stage indexing the transfers of HYPE and spot tokens from HyperCore to the EVM, made by the
+/// system transactions of each block. For more information take a look at
+/// [`tables::SystemTransfers`].
+///
+/// Blocks inserted outside of the pipeline are indexed by the provider as they are inserted.
+#[derive(Debug)]
+pub struct IndexSystemTransfersStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+}
+
+impl IndexSystemTransfersStage {
+    /// Create new instance of [`IndexSystemTransfersStage`].
+    pub const fn new(config: IndexHistoryConfig) -> Self {
+        Self { commit_threshold: config.commit_threshold }
+    }
+}
+
+impl Default for IndexSystemTransfersStage {
+    fn default() -> Self {
+        Self { commit_threshold: 100_000 }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexSystemTransfersStage
+where
+    Provider: DBProvider<Tx: DbTxMut> + BlockReader<Transaction: SignedTransaction>,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexSystemTransfers
+    }
+
+    /// Execute the stage.
+    fn execute(&mut self, provider: &Provider, input: ExecInput) -> Result<ExecOutput, StageError> {
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let (range, is_final_range) = input.next_block_range_with_threshold(self.commit_threshold);
+
+        info!(target: "sync::stages::index_system_transfers", ?range, "Indexing system transfers");
+        let mut cursor = provider.tx_ref().cursor_write::<tables::SystemTransfers>()?;
+        for (key, transfer) in system_transfers(provider, range.clone())? {
+            cursor.upsert(key, &transfer)?;
+        }
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: is_final_range })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        let mut cursor = provider.tx_ref().cursor_write::<tables::SystemTransfers>()?;
+        for (key, _) in system_transfers(provider, range)? {
+            if cursor.seek_exact(key)?.is_some() {
+                cursor.delete_current()?;
+            }
+        }
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Returns the system transfers of the blocks in `range`.
+fn system_transfers<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+) -> Result<Vec<(SystemTransferKey, StoredSystemTransfer)>, StageError>
+where
+    Provider: BlockReader<Transaction: SignedTransaction>,
+{
+    let headers = provider.headers_range(range.clone())?;
+    let bodies = provider.block_body_indices_range(range.clone())?;
+
+    let mut transfers = Vec::new();
+    for ((block_number, header), body) in range.zip(headers).zip(bodies) {
+        let transactions = body
+            .tx_num_range()
+            .map_while(|tx_num| provider.transaction_by_id_unhashed(tx_num).transpose());
+        transfers.extend(block_system_transfers(block_number, header.timestamp(), transactions)?);
+    }
+    Ok(transfers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{StorageKind, TestStageDB};
+    use alloy_consensus::TxLegacy;
+    use alloy_primitives::{Address, Bytes, PrimitiveSignature as Signature, TxKind, U256};
+    use reth_primitives::{SealedBlock, Transaction, TransactionSigned};
+    use reth_primitives_traits::transaction::signed::NATIVE_TOKEN_SYSTEM_ADDRESS;
+    use reth_provider::DatabaseProviderFactory;
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+
+    fn system_tx(to: Address, value: U256, input: Bytes) -> TransactionSigned {
+        TransactionSigned::new_unhashed(
+            Transaction::Legacy(TxLegacy {
+                to: TxKind::Call(to),
+                value,
+                input,
+                ..Default::default()
+            }),
+            Signature::new(U256::from(1), U256::from(1), true),
+        )
+    }
+
+    #[test]
+    fn execute_and_unwind_system_transfers() {
+        let db = TestStageDB::default();
+        let mut rng = generators::rng();
+        let recipient = Address::with_last_byte(0x11);
+        let token = Address::with_last_byte(0x22);
+
+        let mut transfer_input = vec![0xa9, 0x05, 0x9c, 0xbb];
+        transfer_input.extend_from_slice(recipient.into_word().as_slice());
+        transfer_input.extend_from_slice(&U256::from(7).to_be_bytes::<32>());
+
+        let blocks = (0..=2)
+            .map(|number| {
+                let block = random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { tx_count: Some(1), ..Default::default() },
+                );
+                if number == 0 {
+                    return block
+                }
+                let (header, mut body) = block.split_sealed_header_body();
+                body.transactions.insert(0, system_tx(recipient, U256::from(5), Bytes::new()));
+                body.transactions
+                    .insert(1, system_tx(token, U256::ZERO, transfer_input.clone().into()));
+                SealedBlock::from_sealed_parts(header, body)
+            })
+            .collect::<Vec<_>>();
+        db.insert_blocks(blocks.iter(), StorageKind::Static).unwrap();
+
+        let mut stage = IndexSystemTransfersStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let output =
+            stage.execute(&provider, ExecInput { target: Some(2), checkpoint: None }).unwrap();
+        assert_eq!(output, ExecOutput { checkpoint: StageCheckpoint::new(2), done: true });
+        provider.commit().unwrap();
+
+        let transfers = db.table::<tables::SystemTransfers>().unwrap();
+        assert_eq!(
+            transfers.iter().map(|(key, transfer)| (*key, transfer.amount)).collect::<Vec<_>>(),
+            vec![
+                (SystemTransferKey::new(recipient, token, 1, 1), U256::from(7)),
+                (SystemTransferKey::new(recipient, token, 2, 1), U256::from(7)),
+                (
+                    SystemTransferKey::new(recipient, NATIVE_TOKEN_SYSTEM_ADDRESS, 1, 0),
+                    U256::from(5)
+                ),
+                (
+                    SystemTransferKey::new(recipient, NATIVE_TOKEN_SYSTEM_ADDRESS, 2, 0),
+                    U256::from(5)
+                ),
+            ]
+        );
+        assert_eq!(transfers[0].1.timestamp, blocks[1].header().timestamp);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        stage
+            .unwind(
+                &provider,
+                UnwindInput { checkpoint: StageCheckpoint::new(2), unwind_to: 1, bad_block: None },
+            )
+            .unwrap();
+        provider.commit().unwrap();
+
+        let transfers = db.table::<tables::SystemTransfers>().unwrap();
+        assert_eq!(
+            transfers.into_iter().map(|(key, _)| key.block_number).collect::<Vec<_>>(),
+            vec![1, 1]
+        );
+    }
+}
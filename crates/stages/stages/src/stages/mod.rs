@@ -14,6 +14,8 @@ mod headers;
 mod index_account_history;
 /// Index history of storage changes
 mod index_storage_history;
+/// Index system transfers
+mod index_system_transfers;
 /// Stage for computing state root.
 mod merkle;
 mod prune;
@@ -32,6 +34,7 @@ pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
 pub use index_storage_history::*;
+pub use index_system_transfers::*;
 pub use merkle::*;
 pub use prune::*;
 pub use s3::*;
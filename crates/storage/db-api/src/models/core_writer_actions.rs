@@ -0,0 +1,88 @@
+//! CoreWriter action models.
+use crate::{
+    table::{Decode, Encode},
+    DatabaseError,
+};
+use alloy_primitives::{Address, BlockNumber};
+use serde::{Deserialize, Serialize};
+
+/// The size of [`CoreWriterActionKey`] encode bytes.
+/// The fields are: 20-byte sender, 4-byte action id, 8-byte block number, 8-byte transaction
+/// index and 8-byte log index
+const CORE_WRITER_ACTION_KEY_BYTES_SIZE: usize = 20 + 4 + 8 + 8 + 8;
+
+/// Key of a CoreWriter action, ordered by sender, action id and position in the chain.
+///
+/// `Sender | 1 | 200 | 3 | 7` -> limit order emitted by the eighth log of block 200, in its fourth
+/// transaction.
+#[derive(
+    Debug, Default, Clone, Copy, Eq, Ord, PartialOrd, PartialEq, Serialize, Deserialize, Hash,
+)]
+pub struct CoreWriterActionKey {
+    /// Address that sent the action to CoreWriter.
+    pub sender: Address,
+    /// Id of the action.
+    pub action_id: u32,
+    /// Number of the block the action is included in.
+    pub block_number: BlockNumber,
+    /// Index of the transaction in its block.
+    pub tx_index: u64,
+    /// Index of the log in its block.
+    pub log_index: u64,
+}
+
+impl CoreWriterActionKey {
+    /// Creates a new `CoreWriterActionKey`.
+    pub const fn new(
+        sender: Address,
+        action_id: u32,
+        block_number: BlockNumber,
+        tx_index: u64,
+        log_index: u64,
+    ) -> Self {
+        Self { sender, action_id, block_number, tx_index, log_index }
+    }
+}
+
+impl Encode for CoreWriterActionKey {
+    type Encoded = Vec<u8>;
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf: Vec<u8> = Vec::with_capacity(CORE_WRITER_ACTION_KEY_BYTES_SIZE);
+        buf.extend_from_slice(&Encode::encode(self.sender));
+        buf.extend_from_slice(&self.action_id.to_be_bytes());
+        buf.extend_from_slice(&self.block_number.to_be_bytes());
+        buf.extend_from_slice(&self.tx_index.to_be_bytes());
+        buf.extend_from_slice(&self.log_index.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for CoreWriterActionKey {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() != CORE_WRITER_ACTION_KEY_BYTES_SIZE {
+            return Err(DatabaseError::Decode)
+        }
+        let sender = Address::decode(&value[..20])?;
+        let action_id = u32::from_be_bytes(value[20..24].try_into().expect("4 bytes"));
+        let block_number = u64::decode(&value[24..32])?;
+        let tx_index = u64::decode(&value[32..40])?;
+        let log_index = u64::decode(&value[40..])?;
+
+        Ok(Self { sender, action_id, block_number, tx_index, log_index })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn core_writer_action_key_roundtrip() {
+        let key = CoreWriterActionKey::new(Address::with_last_byte(1), 0x01_0002, 200, 3, 7);
+        assert_eq!(CoreWriterActionKey::decode(&key.encode()).unwrap(), key);
+
+        let next_id = CoreWriterActionKey::new(Address::with_last_byte(1), 0x01_0003, 0, 0, 0);
+        assert!(key.encode() < next_id.encode());
+    }
+}
@@ -17,18 +17,22 @@ use serde::{Deserialize, Serialize};
 
 pub mod accounts;
 pub mod blocks;
+pub mod core_writer_actions;
 pub mod integer_list;
 pub mod sharded_key;
 pub mod storage_sharded_key;
+pub mod system_transfers;
 
 pub use accounts::*;
 pub use blocks::*;
+pub use core_writer_actions::CoreWriterActionKey;
 pub use integer_list::IntegerList;
 pub use reth_db_models::{
     AccountBeforeTx, ClientVersion, StaticFileBlockWithdrawals, StoredBlockBodyIndices,
     StoredBlockWithdrawals,
 };
 pub use sharded_key::ShardedKey;
+pub use system_transfers::{block_system_transfers, StoredSystemTransfer, SystemTransferKey};
 
 /// Macro that implements [`Encode`] and [`Decode`] for uint types.
 macro_rules! impl_uints {
@@ -233,6 +237,7 @@ impl_compression_for_compact!(
     StageCheckpoint,
     PruneCheckpoint,
     ClientVersion,
+    StoredSystemTransfer,
     // Non-DB
     GenesisAccount
 );
@@ -0,0 +1,157 @@
+//! System transfer models.
+use crate::{
+    table::{Decode, Encode},
+    DatabaseError,
+};
+use alloy_primitives::{Address, BlockNumber, B256, U256};
+use reth_codecs::Compact;
+use reth_primitives_traits::{transaction::signed::system_transfer, SignedTransaction};
+use serde::{Deserialize, Serialize};
+
+/// The size of [`SystemTransferKey`] encode bytes.
+/// The fields are: 20-byte recipient, 20-byte token, 8-byte block number and 8-byte transaction
+/// index
+const SYSTEM_TRANSFER_KEY_BYTES_SIZE: usize = 20 + 20 + 8 + 8;
+
+/// Key of a system transfer, ordered by recipient, token and position in the chain.
+///
+/// `Recipient | Token | 200 | 1` -> second transaction of block 200.
+#[derive(
+    Debug, Default, Clone, Copy, Eq, Ord, PartialOrd, PartialEq, Serialize, Deserialize, Hash,
+)]
+pub struct SystemTransferKey {
+    /// Address receiving the transfer.
+    pub recipient: Address,
+    /// ERC20 contract of the transferred spot token, or the HYPE system address.
+    pub token: Address,
+    /// Number of the block the transfer is included in.
+    pub block_number: BlockNumber,
+    /// Index of the system transaction in its block.
+    pub tx_index: u64,
+}
+
+impl SystemTransferKey {
+    /// Creates a new `SystemTransferKey`.
+    pub const fn new(
+        recipient: Address,
+        token: Address,
+        block_number: BlockNumber,
+        tx_index: u64,
+    ) -> Self {
+        Self { recipient, token, block_number, tx_index }
+    }
+}
+
+impl Encode for SystemTransferKey {
+    type Encoded = Vec<u8>;
+
+    fn encode(self) -> Self::Encoded {
+        let mut buf: Vec<u8> = Vec::with_capacity(SYSTEM_TRANSFER_KEY_BYTES_SIZE);
+        buf.extend_from_slice(&Encode::encode(self.recipient));
+        buf.extend_from_slice(&Encode::encode(self.token));
+        buf.extend_from_slice(&self.block_number.to_be_bytes());
+        buf.extend_from_slice(&self.tx_index.to_be_bytes());
+        buf
+    }
+}
+
+impl Decode for SystemTransferKey {
+    fn decode(value: &[u8]) -> Result<Self, DatabaseError> {
+        if value.len() != SYSTEM_TRANSFER_KEY_BYTES_SIZE {
+            return Err(DatabaseError::Decode)
+        }
+        let recipient = Address::decode(&value[..20])?;
+        let token = Address::decode(&value[20..40])?;
+        let block_number = u64::decode(&value[40..48])?;
+        let tx_index = u64::decode(&value[48..])?;
+
+        Ok(Self { recipient, token, block_number, tx_index })
+    }
+}
+
+/// Returns the transfers made by the system transactions of block `block_number`.
+///
+/// System transactions precede the user transactions of a block, so `transactions` is only read
+/// up to the first user transaction.
+pub fn block_system_transfers<T, E>(
+    block_number: BlockNumber,
+    timestamp: u64,
+    transactions: impl IntoIterator<Item = Result<T, E>>,
+) -> Result<Vec<(SystemTransferKey, StoredSystemTransfer)>, E>
+where
+    T: SignedTransaction,
+{
+    let mut transfers = Vec::new();
+    for (tx_index, transaction) in transactions.into_iter().enumerate() {
+        let transaction = transaction?;
+        if transaction.system_tx_sender().is_none() {
+            break
+        }
+        if let Some(transfer) = system_transfer(&transaction) {
+            transfers.push((
+                SystemTransferKey::new(
+                    transfer.recipient,
+                    transfer.token,
+                    block_number,
+                    tx_index as u64,
+                ),
+                StoredSystemTransfer {
+                    tx_hash: *transaction.tx_hash(),
+                    timestamp,
+                    amount: transfer.amount,
+                },
+            ));
+        }
+    }
+    Ok(transfers)
+}
+
+/// A system transfer, keyed by [`SystemTransferKey`].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct StoredSystemTransfer {
+    /// Hash of the system transaction.
+    pub tx_hash: B256,
+    /// Timestamp of the block the transfer is included in.
+    pub timestamp: u64,
+    /// Transferred amount, in the smallest unit of the token.
+    pub amount: U256,
+}
+
+impl Compact for StoredSystemTransfer {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(self.tx_hash.as_slice());
+        buf.put_u64(self.timestamp);
+        buf.put_slice(&self.amount.to_be_bytes::<32>());
+        32 + 8 + 32
+    }
+
+    fn from_compact(buf: &[u8], _len: usize) -> (Self, &[u8]) {
+        let tx_hash = B256::from_slice(&buf[..32]);
+        let timestamp = u64::from_be_bytes(buf[32..40].try_into().expect("8 bytes"));
+        let amount = U256::from_be_slice(&buf[40..72]);
+        (Self { tx_hash, timestamp, amount }, &buf[72..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::table::{Compress, Decompress};
+
+    #[test]
+    fn system_transfer_roundtrip() {
+        let key =
+            SystemTransferKey::new(Address::with_last_byte(1), Address::with_last_byte(2), 200, 1);
+        assert_eq!(SystemTransferKey::decode(&key.encode()).unwrap(), key);
+
+        let transfer = StoredSystemTransfer {
+            tx_hash: B256::with_last_byte(3),
+            timestamp: 1_700_000_000,
+            amount: U256::from(10).pow(U256::from(18)),
+        };
+        assert_eq!(StoredSystemTransfer::decompress(&transfer.compress()).unwrap(), transfer);
+    }
+}
@@ -21,13 +21,13 @@ use crate::{
         accounts::BlockNumberAddress,
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
-        AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
-        StoredBlockBodyIndices, StoredBlockWithdrawals,
+        AccountBeforeTx, ClientVersion, CompactU256, CoreWriterActionKey, IntegerList, ShardedKey,
+        StoredBlockBodyIndices, StoredBlockWithdrawals, StoredSystemTransfer, SystemTransferKey,
     },
     table::{Decode, DupSort, Encode, Table, TableInfo},
 };
 use alloy_consensus::Header;
-use alloy_primitives::{Address, BlockHash, BlockNumber, TxHash, TxNumber, B256};
+use alloy_primitives::{Address, BlockHash, BlockNumber, Bytes, TxHash, TxNumber, B256};
 use reth_primitives::{Receipt, StorageEntry, TransactionSigned};
 use reth_primitives_traits::{Account, Bytecode};
 use reth_prune_types::{PruneCheckpoint, PruneSegment};
@@ -525,6 +525,36 @@ tables! {
         type Key = ChainStateKey;
         type Value = BlockNumber;
     }
+
+    /// Stores the read precompile calls hl-node recorded for each block, MessagePack encoded.
+    ///
+    /// Entries are kept when blocks are unwound, since the blocks cannot be executed again
+    /// without them.
+    table BlockPrecompileCalls {
+        type Key = BlockNumber;
+        type Value = Bytes;
+    }
+
+    /// Stores the transfers of HYPE and spot tokens from HyperCore to the EVM made by system
+    /// transactions, by recipient and token.
+    table SystemTransfers {
+        type Key = SystemTransferKey;
+        type Value = StoredSystemTransfer;
+    }
+
+    /// Stores the actions sent from the EVM to HyperCore through the CoreWriter contract, by
+    /// sender and action id. Values are the encoded actions, as emitted by the `RawAction` event.
+    table CoreWriterActions {
+        type Key = CoreWriterActionKey;
+        type Value = Bytes;
+    }
+
+    /// Stores the spot token index of the ERC20 contracts linked to HyperCore spot tokens. The
+    /// senders of the system transactions calling these contracts are derived from it.
+    table SpotTokens {
+        type Key = Address;
+        type Value = u64;
+    }
 }
 
 /// Keys for the `ChainState` table.
@@ -663,7 +663,7 @@ mod tests {
         BlockHashReader, BlockNumReader, BlockWriter, DBProvider, HeaderSyncGapProvider,
         StorageLocation, TransactionsProvider,
     };
-    use alloy_primitives::{TxNumber, B256, U256};
+    use alloy_primitives::{Address, Bytes, TxNumber, B256, U256};
     use assert_matches::assert_matches;
     use rand::Rng;
     use reth_chainspec::ChainSpecBuilder;
@@ -671,7 +671,12 @@ mod tests {
         mdbx::DatabaseArguments,
         test_utils::{create_test_static_files_dir, ERROR_TEMPDIR},
     };
-    use reth_db_api::tables;
+    use reth_db_api::{
+        cursor::DbCursorRO,
+        models::CoreWriterActionKey,
+        tables,
+        transaction::{DbTx, DbTxMut},
+    };
     use reth_primitives::StaticFileSegment;
     use reth_primitives_traits::SignedTransaction;
     use reth_prune_types::{PruneMode, PruneModes};
@@ -802,6 +807,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn remove_core_writer_actions_above() {
+        let factory = create_test_provider_factory();
+        let provider = factory.provider_rw().unwrap();
+
+        // The actions of a sender are ordered by action id before block, so the actions of the
+        // removed blocks are interleaved with the ones that are kept.
+        let sender = Address::with_last_byte(0x11);
+        for block_number in 1..=3 {
+            for action_id in [4, 5] {
+                provider
+                    .tx_ref()
+                    .put::<tables::CoreWriterActions>(
+                        CoreWriterActionKey::new(sender, action_id, block_number, 0, 0),
+                        Bytes::from_static(&[1, 0, 0, 4]),
+                    )
+                    .unwrap();
+            }
+        }
+
+        provider.remove_core_writer_actions_above(1).unwrap();
+
+        let mut cursor = provider.tx_ref().cursor_read::<tables::CoreWriterActions>().unwrap();
+        let keys = cursor.walk(None).unwrap().map(|entry| entry.unwrap().0).collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            vec![
+                CoreWriterActionKey::new(sender, 4, 1, 0, 0),
+                CoreWriterActionKey::new(sender, 5, 1, 0, 0),
+            ]
+        );
+    }
+
     #[test]
     fn header_sync_gap_lookup() {
         let factory = create_test_provider_factory();
@@ -24,7 +24,7 @@ use alloy_eips::{eip2718::Encodable2718, eip4895::Withdrawals, BlockHashOrNumber
 use alloy_primitives::{
     keccak256,
     map::{hash_map, B256Map, HashMap, HashSet},
-    Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
+    Address, BlockHash, BlockNumber, Log, TxHash, TxNumber, B256, U256,
 };
 use itertools::Itertools;
 use rayon::slice::ParallelSliceMut;
@@ -33,8 +33,9 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     database::Database,
     models::{
-        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        ShardedKey, StoredBlockBodyIndices,
+        block_system_transfers, sharded_key, storage_sharded_key::StorageShardedKey,
+        AccountBeforeTx, BlockNumberAddress, CoreWriterActionKey, ShardedKey,
+        StoredBlockBodyIndices,
     },
     table::Table,
     tables,
@@ -42,6 +43,7 @@ use reth_db_api::{
     BlockNumberList, DatabaseError, PlainAccountState, PlainStorageState,
 };
 use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_hyperliquid_types::block_raw_actions;
 use reth_network_p2p::headers::downloader::SyncTarget;
 use reth_node_types::{BlockTy, BodyTy, HeaderTy, NodeTypes, ReceiptTy, TxTy};
 use reth_primitives::{
@@ -348,6 +350,8 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         last_block: BlockNumber,
         remove_from: StorageLocation,
     ) -> ProviderResult<()> {
+        self.remove_core_writer_actions_above(last_block)?;
+
         if remove_from.database() {
             // iterate over block body and remove receipts
             self.remove::<tables::Receipts<ReceiptTy<N>>>(from_tx..)?;
@@ -795,6 +799,43 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> DatabaseProvider<TX, N> {
         Ok(self.tx.commit()?)
     }
 
+    /// Indexes the CoreWriter actions emitted in block `block_number`, given the receipts of the
+    /// block.
+    pub fn insert_core_writer_actions<'a, R: TxReceipt<Log = Log> + 'a>(
+        &self,
+        block_number: BlockNumber,
+        receipts: impl IntoIterator<Item = &'a R>,
+    ) -> ProviderResult<()> {
+        for (tx_index, log_index, action) in block_raw_actions(receipts) {
+            self.tx.put::<tables::CoreWriterActions>(
+                CoreWriterActionKey::new(
+                    action.sender,
+                    action.action_id,
+                    block_number,
+                    tx_index,
+                    log_index,
+                ),
+                action.data,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Removes the CoreWriter actions emitted in the blocks above `block`.
+    ///
+    /// Actions are indexed even for blocks whose receipts are pruned, so they cannot be found
+    /// from the receipts and the whole table is scanned instead.
+    pub fn remove_core_writer_actions_above(&self, block: BlockNumber) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_write::<tables::CoreWriterActions>()?;
+        let mut walker = cursor.walk(None)?;
+        while let Some((key, _)) = walker.next().transpose()? {
+            if key.block_number > block {
+                walker.delete_current()?;
+            }
+        }
+        Ok(())
+    }
+
     /// Load shard and remove it. If list is empty, last shard was full or
     /// there are no shards at all.
     fn take_shard<T>(
@@ -1852,6 +1893,9 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> StateWriter
                 writer.increment_block(block_number)?;
             }
 
+            // Index the CoreWriter actions, even if the receipts are pruned.
+            self.insert_core_writer_actions(block_number, receipts)?;
+
             // Skip writing receipts if pruning configuration requires us to.
             if prunable_receipts
                 && self
@@ -2884,6 +2928,15 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
             next_tx_num += 1;
         }
 
+        // Index the transfers made by the system transactions
+        for (key, transfer) in block_system_transfers(
+            block_number,
+            block.header().timestamp(),
+            block.body().transactions_iter().map(Ok::<_, ProviderError>),
+        )? {
+            self.tx.put::<tables::SystemTransfers>(key, transfer)?;
+        }
+
         self.append_block_bodies(vec![(block_number, Some(block.into_body()))], write_to)?;
 
         debug!(
@@ -3015,6 +3068,19 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider + 'static> BlockWrite
 
         self.remove::<tables::TransactionSenders>(unwind_tx_from..)?;
 
+        // Remove the transfers made by the system transactions of the removed blocks
+        let mut block_indices_cursor = self.tx.cursor_read::<tables::BlockBodyIndices>()?;
+        for entry in block_indices_cursor.walk(Some(block + 1))? {
+            let (block_number, block_indices) = entry?;
+            let transactions = block_indices
+                .tx_num_range()
+                .map_while(|tx_num| self.transaction_by_id_unhashed(tx_num).transpose());
+            // Only the key is needed, so the timestamp is left out
+            for (key, _) in block_system_transfers(block_number, 0, transactions)? {
+                self.tx.delete::<tables::SystemTransfers>(key, None)?;
+            }
+        }
+
         self.remove_bodies_above(block, remove_from)?;
 
         Ok(())